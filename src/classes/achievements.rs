@@ -0,0 +1,225 @@
+//! The `Achievements` struct watches game events and unlocks named achievements.
+//!
+//! Every achievement is earned on a single level:
+//!
+//! - No Deaths: Clear a level without dying.
+//! - Pacifist: Clear a level without slaying an enemy.
+//! - Speed Demon: Clear a level in under `PAR_TIME`.
+//!
+//! Unlocked achievements are stored in a small text file, one per line. A missing
+//! file is treated as no achievements unlocked yet, and unknown lines are skipped.
+//!
+//! # Fields
+//!
+//! - `path` (`PathBuf`): The file unlocked achievements are read from and written to.
+//! - `unlocked` (`Vec<Achievement>`): The achievements unlocked so far, in the order they were earned.
+//! - `level_deaths` (`u32`): The deaths on the current level.
+//! - `level_kills` (`u32`): The enemies slain on the current level.
+//!
+//! # Methods
+//!
+//! - `load`: Reads the unlocked achievements from a file.
+//! - `observe`: Updates the tracked state with an event, returning the achievements it unlocked.
+//! - `is_unlocked`: Checks whether an achievement has been unlocked.
+//! - `save`: Writes the unlocked achievements back to the file.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut achievements = Achievements::load("achievements.txt");
+//!
+//! for achievement in achievements.observe(GameEvent::PlayerDied) {
+//!     println!("Unlocked: {}", achievement.name());
+//! }
+//! ```
+
+use crate::classes::types::GameEvent;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// File unlocked achievements are saved to
+pub const ACHIEVEMENTS_FILE: &str = "achievements.txt";
+// Clearing a level faster than this earns Speed Demon
+pub const PAR_TIME: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Achievement {
+    NoDeaths,
+    Pacifist,
+    SpeedDemon,
+}
+
+impl Achievement {
+    const ALL: [Achievement; 3] = [
+        Achievement::NoDeaths,
+        Achievement::Pacifist,
+        Achievement::SpeedDemon,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Achievement::NoDeaths => "No Deaths",
+            Achievement::Pacifist => "Pacifist",
+            Achievement::SpeedDemon => "Speed Demon",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::NoDeaths => "Cleared a level without dying",
+            Achievement::Pacifist => "Cleared a level without slaying an enemy",
+            Achievement::SpeedDemon => "Cleared a level under par",
+        }
+    }
+
+    // The name written to the achievements file
+    fn key(&self) -> &'static str {
+        match self {
+            Achievement::NoDeaths => "no_deaths",
+            Achievement::Pacifist => "pacifist",
+            Achievement::SpeedDemon => "speed_demon",
+        }
+    }
+}
+
+pub struct Achievements {
+    path: PathBuf,
+    unlocked: Vec<Achievement>,
+    level_deaths: u32,
+    level_kills: u32,
+}
+
+impl Achievements {
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let unlocked = fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                Achievement::ALL
+                    .into_iter()
+                    .find(|achievement| achievement.key() == line.trim())
+            })
+            .collect();
+
+        Self {
+            path,
+            unlocked,
+            level_deaths: 0,
+            level_kills: 0,
+        }
+    }
+
+    pub fn observe(&mut self, event: GameEvent) -> Vec<Achievement> {
+        let mut earned = Vec::new();
+
+        match event {
+            GameEvent::LevelStarted(_) => {
+                self.level_deaths = 0;
+                self.level_kills = 0;
+            }
+            GameEvent::PlayerDied => self.level_deaths += 1,
+            GameEvent::EnemySlain => self.level_kills += 1,
+            GameEvent::LevelCleared { time, .. } => {
+                if self.level_deaths == 0 {
+                    earned.push(Achievement::NoDeaths);
+                }
+                if self.level_kills == 0 {
+                    earned.push(Achievement::Pacifist);
+                }
+                if time < PAR_TIME {
+                    earned.push(Achievement::SpeedDemon);
+                }
+            }
+        }
+
+        earned.retain(|achievement| !self.is_unlocked(*achievement));
+        self.unlocked.extend(&earned);
+        earned
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents: String = self
+            .unlocked
+            .iter()
+            .map(|achievement| format!("{}\n", achievement.key()))
+            .collect();
+
+        fs::write(&self.path, contents)
+    }
+}
+
+#[cfg(test)]
+fn cleared(time: Duration) -> GameEvent {
+    GameEvent::LevelCleared {
+        level: 1,
+        time,
+        moves: 20,
+    }
+}
+
+#[test]
+fn test_clean_fast_clear_unlocks_everything_once() {
+    let mut achievements = Achievements::load(crate::classes::leaderboard::temp_path("missing"));
+
+    achievements.observe(GameEvent::LevelStarted(1));
+    assert_eq!(
+        achievements.observe(cleared(Duration::from_secs(10))),
+        vec![
+            Achievement::NoDeaths,
+            Achievement::Pacifist,
+            Achievement::SpeedDemon
+        ]
+    );
+
+    // Already unlocked achievements don't unlock again
+    achievements.observe(GameEvent::LevelStarted(2));
+    assert!(achievements
+        .observe(cleared(Duration::from_secs(10)))
+        .is_empty());
+}
+
+#[test]
+fn test_deaths_and_kills_only_count_on_their_level() {
+    let mut achievements = Achievements::load(crate::classes::leaderboard::temp_path("missing"));
+
+    achievements.observe(GameEvent::LevelStarted(1));
+    achievements.observe(GameEvent::PlayerDied);
+    achievements.observe(GameEvent::EnemySlain);
+    assert!(achievements
+        .observe(cleared(Duration::from_secs(90)))
+        .is_empty());
+
+    achievements.observe(GameEvent::LevelStarted(2));
+    achievements.observe(GameEvent::EnemySlain);
+    assert_eq!(
+        achievements.observe(cleared(Duration::from_secs(90))),
+        vec![Achievement::NoDeaths]
+    );
+    assert!(!achievements.is_unlocked(Achievement::Pacifist));
+}
+
+#[test]
+fn test_achievements_round_trip() {
+    let path = crate::classes::leaderboard::temp_path("achievements_round_trip");
+    let _ = fs::remove_file(&path);
+
+    let mut achievements = Achievements::load(&path);
+    achievements.observe(GameEvent::LevelStarted(1));
+    achievements.observe(GameEvent::EnemySlain);
+    achievements.observe(cleared(Duration::from_secs(5)));
+    achievements.save().unwrap();
+
+    let reloaded = Achievements::load(&path);
+    assert!(reloaded.is_unlocked(Achievement::NoDeaths));
+    assert!(reloaded.is_unlocked(Achievement::SpeedDemon));
+    assert!(!reloaded.is_unlocked(Achievement::Pacifist));
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,126 @@
+//! The `CarrySelect` struct is the menu shown after clearing the game, for starting New Game+.
+//!
+//! It lists the items the player finished the game with and keeps a cursor the
+//! player moves with the movement keys. Toggling an item marks whether it is
+//! carried over into the replay; every item starts out marked.
+//!
+//! # Fields
+//!
+//! - `items` (`Vec<ItemType>`): The items the player can carry over, in inventory display order.
+//! - `carried` (`Vec<bool>`): Whether each item is carried over.
+//! - `cursor` (`usize`): The index of the highlighted item.
+//!
+//! # Methods
+//!
+//! - `new`: Creates a menu for the given items, all of them carried, with the cursor on the first one.
+//! - `move_up` / `move_down`: Moves the cursor, stopping at the first and last item.
+//! - `toggle`: Flips whether the highlighted item is carried over.
+//! - `carried`: Returns the items marked to carry over.
+//! - `compose`: Builds the text of the menu.
+//! - `render`: Clears the terminal and draws the menu, returning any I/O error.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut select = CarrySelect::new(player.sorted_inventory());
+//! select.toggle();
+//! game.start_new_game_plus(select.carried());
+//! ```
+
+use crate::classes::types::ItemType;
+use crossterm::{
+    cursor::{Hide, MoveTo},
+    terminal::{Clear, ClearType},
+    ExecutableCommand,
+};
+use std::io::{self, stdout, Write};
+
+pub struct CarrySelect {
+    items: Vec<ItemType>,
+    carried: Vec<bool>,
+    cursor: usize,
+}
+
+impl CarrySelect {
+    pub fn new(items: Vec<ItemType>) -> Self {
+        Self {
+            carried: vec![true; items.len()],
+            items,
+            cursor: 0,
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < self.items.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(carried) = self.carried.get_mut(self.cursor) {
+            *carried = !*carried;
+        }
+    }
+
+    pub fn carried(&self) -> Vec<ItemType> {
+        self.items
+            .iter()
+            .zip(&self.carried)
+            .filter(|(_, carried)| **carried)
+            .map(|(item, _)| *item)
+            .collect()
+    }
+
+    pub fn compose(&self) -> String {
+        let mut frame = String::from(" New Game+\r\n\r\n");
+        frame.push_str(" Start over from level 1 with quicker guards, carrying:\r\n\r\n");
+
+        if self.items.is_empty() {
+            frame.push_str("   Nothing; you finished with an empty pack.\r\n");
+        }
+        for (index, item) in self.items.iter().enumerate() {
+            let cursor = if index == self.cursor { ">" } else { " " };
+            let mark = if self.carried[index] { "[x]" } else { "[ ]" };
+            frame.push_str(&format!(" {} {} {}\r\n", cursor, mark, item.description()));
+        }
+
+        frame.push_str("\r\n w/s: Move | Space: Carry | Enter: Start | q: Quit");
+        frame
+    }
+
+    pub fn render(&self) -> io::Result<()> {
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(MoveTo(0, 0))?;
+        stdout.execute(Hide)?;
+
+        write!(stdout, "{}", self.compose())?;
+        stdout.flush()
+    }
+}
+
+#[test]
+fn test_carry_select_toggles_highlighted_item() {
+    let mut select = CarrySelect::new(vec![ItemType::Axe, ItemType::Key, ItemType::WindChime]);
+    assert_eq!(
+        select.carried(),
+        vec![ItemType::Axe, ItemType::Key, ItemType::WindChime]
+    );
+
+    select.move_down();
+    select.toggle();
+    assert_eq!(select.carried(), vec![ItemType::Axe, ItemType::WindChime]);
+
+    // The cursor stops at the last item
+    select.move_down();
+    select.move_down();
+    select.toggle();
+    assert_eq!(select.carried(), vec![ItemType::Axe]);
+
+    assert!(select.compose().contains("> [ ] Wind Chime"));
+    assert!(select.compose().contains("  [x] Axe"));
+}
@@ -0,0 +1,699 @@
+//! The `Config` struct holds the runtime options of the game.
+//!
+//! Options are read from the command line when the game starts. Every option
+//! has a sensible default, so running the game without arguments behaves the
+//! same as before options were introduced.
+//!
+//! # Fields
+//!
+//! - `fps` (`u32`): The number of frames rendered per second.
+//! - `speed` (`f64`): Scales the pace of everything timed: the game clock, timed messages, and the frame rate.
+//!   At 0.5 the guards move half as fast and messages stay up twice as long. It must lie between `MIN_SPEED`
+//!   and `MAX_SPEED`; far beyond those, the game clock would stall or spin.
+//! - `ascii` (`bool`): Renders the game with plain ASCII characters instead of emoji.
+//! - `color` (`bool`): Colors the map glyphs in ASCII mode.
+//! - `theme` (`Theme`): The palette the map is colored with: a built-in theme, or one read from a theme file.
+//! - `spacious` (`bool`): Low-vision mode that spaces out the map with a blank column after each tile and a blank row after each row.
+//! - `diagonal` (`bool`): Enables 8-directional movement with `q`/`e`/`z`/`c` and the numpad.
+//! - `god` (`bool`): Debug mode that makes the player invincible.
+//! - `allow_skip` (`bool`): Debug option that lets playtesters skip a level without solving it.
+//! - `peaceful` (`bool`): Removes the patrol guards and keeps the oni and the boss from harming the player, for exploring the puzzles.
+//! - `deadly_traps` (`bool`): Hidden traps kill the player the first time they spring, instead of only once revealed.
+//! - `difficulty` (`Difficulty`): Scales the number of enemies on each level.
+//! - `validate` (`bool`): Checks all map files and exits instead of starting the game.
+//! - `list_levels` (`bool`): Prints a table of the levels, with their names and par times, and exits instead of starting the game.
+//! - `maps_dir` (`String`): The directory level files are read from.
+//! - `pack` (`Option<String>`): A map pack to play, installed as a subdirectory of `maps_dir`.
+//! - `sound` (`bool`): Rings the terminal bell on pickups, deaths, and level clears.
+//! - `flash` (`bool`): Briefly inverts the screen colors on deaths and boss clashes.
+//! - `confirm_quit` (`bool`): Asks for confirmation before quitting.
+//! - `select` (`bool`): Shows the level select menu before the game starts.
+//! - `dump_map` (`Option<usize>`): Prints the given level to stdout and exits instead of starting the game.
+//! - `map` (`Option<String>`): A standalone map file to play as a single-level game instead of the numbered levels.
+//! - `debug_perf` (`bool`): Shows the measured render, frame, and update times in the HUD.
+//! - `fog` (`bool`): Fog of war; only the tiles near the player are shown, farther while a lantern lights the way.
+//! - `danger_zone` (`bool`): Highlights the tiles the patrol guards could step onto next.
+//! - `dash` (`bool`): Lets a quick double tap of a direction dash two tiles.
+//! - `mouse` (`bool`): Captures the mouse, so a click on the map moves the player a step toward the clicked tile.
+//! - `mouse_pathfind` (`bool`): Clicks step along a shortest path to the clicked tile instead of straight toward it.
+//!   Implies `mouse`.
+//! - `demo` (`bool`): Shows a demo of the first level, played by a bot, until a key is pressed.
+//! - `leash` (`i32`): How far from its home, in steps, a hunting guard follows the player before giving up and heading back.
+//! - `seed` (`Option<u64>`): Seeds the random number generator, so wandering guards move the same way every run.
+//!   The demo always uses a seed, `DEMO_SEED` unless one is given.
+//! - `player_glyph` (`Option<String>`): A custom glyph to draw the player with instead of the ninja, padded to fill a map cell.
+//!   Glyphs wider than one map cell are rejected, since they would break the alignment of the map.
+//! - `enemy_glyph` (`Option<String>`): Likewise, a custom glyph for the patrol guards.
+//! - `give` (`Vec<ItemType>`): Debug option that puts the given items in the player's inventory at startup.
+//! - `run_log` (`PathBuf`): The file a summary of every run is appended to, `runs.txt` by default.
+//! - `leaderboard_file` (`PathBuf`): The file the best times are saved to, `leaderboard.txt` by default. A map pack
+//!   keeps its own file next to it.
+//! - `settings_file` (`PathBuf`): The file the options chosen on the options screen are saved to, `settings.txt` by default.
+//! - `achievements_file` (`PathBuf`): The file unlocked achievements are saved to, `achievements.txt` by default.
+//!
+//! # Methods
+//!
+//! - `default`: Provides the default configuration.
+//! - `from_args`: Parses command line arguments (without the program name) into a `Config`.
+//! - `from_args_with`: Parses command line arguments on top of an existing `Config`, such as one with saved settings applied.
+//! - `scratch`: The default configuration with every file a game writes moved to the temp directory, for unit tests.
+//! - `levels_dir`: The directory the level files are read from: the pack's subdirectory of `maps_dir`, or `maps_dir` itself.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let config = Config::from_args(std::env::args().skip(1))?;
+//! println!("Rendering at {} FPS", config.fps);
+//! ```
+
+use crate::classes::achievements::ACHIEVEMENTS_FILE;
+use crate::classes::leaderboard::LEADERBOARD_FILE;
+use crate::classes::level::DEFAULT_MAPS_DIR;
+use crate::classes::run_log::RUN_LOG_FILE;
+use crate::classes::settings::SETTINGS_FILE;
+use crate::classes::theme::Theme;
+use crate::classes::types::ItemType;
+use crate::classes::width::fit_cell;
+use std::path::{Path, PathBuf};
+
+// Steps a hunting guard strays from its home before giving up the chase, unless `--leash` says otherwise
+pub const DEFAULT_LEASH: i32 = 5;
+// The slowest and fastest `--speed` the game clock can keep up with
+pub const MIN_SPEED: f64 = 0.1;
+pub const MAX_SPEED: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    // Easy levels allow half as many moves again, hard levels a quarter fewer
+    pub fn move_budget(&self, limit: u32) -> u32 {
+        match self {
+            Difficulty::Easy => limit + limit / 2,
+            Difficulty::Normal => limit,
+            Difficulty::Hard => (limit - limit / 4).max(1),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub fps: u32,
+    pub speed: f64,
+    pub ascii: bool,
+    pub color: bool,
+    pub theme: Theme,
+    pub spacious: bool,
+    pub diagonal: bool,
+    pub god: bool,
+    pub allow_skip: bool,
+    pub peaceful: bool,
+    pub deadly_traps: bool,
+    pub difficulty: Difficulty,
+    pub validate: bool,
+    pub list_levels: bool,
+    pub maps_dir: String,
+    pub pack: Option<String>,
+    pub sound: bool,
+    pub flash: bool,
+    pub confirm_quit: bool,
+    pub select: bool,
+    pub dump_map: Option<usize>,
+    pub map: Option<String>,
+    pub give: Vec<ItemType>,
+    pub player_glyph: Option<String>,
+    pub enemy_glyph: Option<String>,
+    pub debug_perf: bool,
+    pub fog: bool,
+    pub danger_zone: bool,
+    pub dash: bool,
+    pub mouse: bool,
+    pub mouse_pathfind: bool,
+    pub demo: bool,
+    pub leash: i32,
+    pub seed: Option<u64>,
+    pub run_log: PathBuf,
+    pub leaderboard_file: PathBuf,
+    pub settings_file: PathBuf,
+    pub achievements_file: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            fps: 10,
+            speed: 1.0,
+            ascii: false,
+            color: false,
+            theme: Theme::default(),
+            spacious: false,
+            diagonal: false,
+            god: false,
+            allow_skip: false,
+            peaceful: false,
+            deadly_traps: false,
+            difficulty: Difficulty::Normal,
+            validate: false,
+            list_levels: false,
+            maps_dir: DEFAULT_MAPS_DIR.to_string(),
+            pack: None,
+            sound: true,
+            flash: true,
+            confirm_quit: true,
+            select: false,
+            dump_map: None,
+            map: None,
+            give: Vec::new(),
+            player_glyph: None,
+            enemy_glyph: None,
+            debug_perf: false,
+            fog: false,
+            danger_zone: false,
+            dash: false,
+            mouse: false,
+            mouse_pathfind: false,
+            demo: false,
+            leash: DEFAULT_LEASH,
+            seed: None,
+            run_log: PathBuf::from(RUN_LOG_FILE),
+            leaderboard_file: PathBuf::from(LEADERBOARD_FILE),
+            settings_file: PathBuf::from(SETTINGS_FILE),
+            achievements_file: PathBuf::from(ACHIEVEMENTS_FILE),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_args<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Self::from_args_with(Self::default(), args)
+    }
+
+    pub fn from_args_with<I>(base: Self, args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut config = base;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--fps" => {
+                    let value = args.next().ok_or("--fps requires a value")?;
+                    config.fps = match value.parse::<u32>() {
+                        Ok(fps) if fps > 0 => fps,
+                        _ => return Err(format!("Invalid frame rate: {}", value)),
+                    };
+                }
+                "--speed" => {
+                    let value = args.next().ok_or("--speed requires a value")?;
+                    config.speed = match value.parse::<f64>() {
+                        Ok(speed) if (MIN_SPEED..=MAX_SPEED).contains(&speed) => speed,
+                        _ => return Err(format!("Invalid speed: {}", value)),
+                    };
+                }
+                "--ascii" => config.ascii = true,
+                "--no-ascii" => config.ascii = false,
+                "--color" => config.color = true,
+                "--no-color" => config.color = false,
+                "--theme" => {
+                    let value = args
+                        .next()
+                        .ok_or("--theme requires a name or a file path")?;
+                    config.theme = Theme::resolve(&value)?;
+                }
+                "--spacious" => config.spacious = true,
+                "--diagonal" => config.diagonal = true,
+                "--god" => config.god = true,
+                "--allow-skip" => config.allow_skip = true,
+                "--debug-perf" => config.debug_perf = true,
+                "--fog" => config.fog = true,
+                "--danger-zone" => config.danger_zone = true,
+                "--dash" => config.dash = true,
+                "--mouse" => config.mouse = true,
+                "--mouse-pathfind" => {
+                    config.mouse = true;
+                    config.mouse_pathfind = true;
+                }
+                "--demo" => config.demo = true,
+                "--leash" => {
+                    let value = args.next().ok_or("--leash requires a value")?;
+                    config.leash = value
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|leash| *leash >= 0)
+                        .ok_or_else(|| format!("Invalid leash: {}", value))?;
+                }
+                "--seed" => {
+                    let value = args.next().ok_or("--seed requires a value")?;
+                    config.seed = Some(
+                        value
+                            .parse::<u64>()
+                            .map_err(|_| format!("Invalid seed: {}", value))?,
+                    );
+                }
+                "--peaceful" => config.peaceful = true,
+                "--deadly-traps" => config.deadly_traps = true,
+                "--difficulty" => {
+                    let value = args.next().ok_or("--difficulty requires a value")?;
+                    config.difficulty = Difficulty::parse(&value)
+                        .ok_or(format!("Unknown difficulty: {}", value))?;
+                }
+                "--validate" => config.validate = true,
+                "--list-levels" => config.list_levels = true,
+                "--sound" => config.sound = true,
+                "--no-sound" => config.sound = false,
+                "--no-flash" => config.flash = false,
+                "--no-confirm-quit" => config.confirm_quit = false,
+                "--select" => config.select = true,
+                "--dump-map" => {
+                    let value = args.next().ok_or("--dump-map requires a level number")?;
+                    config.dump_map = match value.parse::<usize>() {
+                        Ok(level) if level > 0 => Some(level),
+                        _ => return Err(format!("Invalid level number: {}", value)),
+                    };
+                }
+                "--give" => {
+                    let value = args.next().ok_or("--give requires a list of items")?;
+                    config.give = value
+                        .split(',')
+                        .map(|name| {
+                            ItemType::from_name(name.trim())
+                                .ok_or(format!("Unknown item: {}", name))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "--player-glyph" => {
+                    let value = args.next().ok_or("--player-glyph requires a glyph")?;
+                    config.player_glyph = Some(fit_glyph(&value)?);
+                }
+                "--enemy-glyph" => {
+                    let value = args.next().ok_or("--enemy-glyph requires a glyph")?;
+                    config.enemy_glyph = Some(fit_glyph(&value)?);
+                }
+                "--map" => {
+                    config.map = Some(args.next().ok_or("--map requires a file path")?);
+                }
+                "--maps-dir" => {
+                    config.maps_dir = args.next().ok_or("--maps-dir requires a value")?;
+                }
+                "--pack" => {
+                    config.pack = Some(args.next().ok_or("--pack requires a name")?);
+                }
+                _ => return Err(format!("Unknown option: {}", arg)),
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn levels_dir(&self) -> PathBuf {
+        match &self.pack {
+            Some(pack) => Path::new(&self.maps_dir).join(pack),
+            None => PathBuf::from(&self.maps_dir),
+        }
+    }
+}
+
+// Unit tests clear games too, and must not write to the player's own files
+#[cfg(test)]
+impl Config {
+    pub(crate) fn scratch() -> Self {
+        use crate::classes::leaderboard::temp_path;
+
+        Self {
+            run_log: temp_path("scratch_runs"),
+            leaderboard_file: temp_path("scratch_leaderboard"),
+            settings_file: temp_path("scratch_settings"),
+            achievements_file: temp_path("scratch_achievements"),
+            ..Self::default()
+        }
+    }
+}
+
+fn fit_glyph(value: &str) -> Result<String, String> {
+    fit_cell(value).ok_or(format!("Glyph must fit in a single map cell: {}", value))
+}
+
+#[test]
+fn test_config_defaults() {
+    let config = Config::from_args(Vec::new()).unwrap();
+
+    assert_eq!(config.fps, 10);
+    assert_eq!(config.speed, 1.0);
+    assert!(!config.ascii);
+    assert!(!config.color);
+    assert_eq!(config.theme, Theme::default());
+    assert!(!config.spacious);
+    assert!(!config.diagonal);
+    assert!(!config.god);
+    assert!(!config.allow_skip);
+    assert!(!config.peaceful);
+    assert!(!config.deadly_traps);
+    assert_eq!(config.difficulty, Difficulty::Normal);
+    assert!(!config.validate);
+    assert!(!config.list_levels);
+    assert_eq!(config.maps_dir, "maps");
+    assert_eq!(config.pack, None);
+    assert!(config.sound);
+    assert!(config.flash);
+    assert!(config.confirm_quit);
+    assert!(!config.select);
+    assert_eq!(config.dump_map, None);
+    assert_eq!(config.map, None);
+    assert!(config.give.is_empty());
+    assert_eq!(config.player_glyph, None);
+    assert_eq!(config.enemy_glyph, None);
+    assert!(!config.debug_perf);
+    assert!(!config.fog);
+    assert!(!config.danger_zone);
+    assert!(!config.dash);
+    assert!(!config.mouse);
+    assert!(!config.mouse_pathfind);
+    assert!(!config.demo);
+    assert_eq!(config.leash, DEFAULT_LEASH);
+    assert_eq!(config.seed, None);
+}
+
+#[test]
+fn test_config_fps_option() {
+    let args = vec!["--fps".to_string(), "30".to_string()];
+    let config = Config::from_args(args).unwrap();
+    assert_eq!(config.fps, 30);
+
+    assert!(Config::from_args(vec!["--fps".to_string()]).is_err());
+    assert!(Config::from_args(vec!["--fps".to_string(), "0".to_string()]).is_err());
+    assert!(Config::from_args(vec!["--unknown".to_string()]).is_err());
+}
+
+#[test]
+fn test_config_speed_option() {
+    let args = vec!["--speed".to_string(), "0.5".to_string()];
+    assert_eq!(Config::from_args(args).unwrap().speed, 0.5);
+
+    for value in ["0.1", "10"] {
+        let args = vec!["--speed".to_string(), value.to_string()];
+        assert!(Config::from_args(args).is_ok());
+    }
+
+    // Extremes would stall the game clock, or make it spin without end
+    for value in [
+        "0", "-1", "fast", "inf", "NaN", "1e-300", "0.09", "10.5", "1e12",
+    ] {
+        let args = vec!["--speed".to_string(), value.to_string()];
+        assert_eq!(
+            Config::from_args(args).err(),
+            Some(format!("Invalid speed: {}", value))
+        );
+    }
+}
+
+#[test]
+fn test_config_ascii_option() {
+    let config = Config::from_args(vec!["--ascii".to_string()]).unwrap();
+
+    assert!(config.ascii);
+}
+
+#[test]
+fn test_config_color_option() {
+    let config = Config::from_args(vec!["--color".to_string()]).unwrap();
+
+    assert!(config.color);
+}
+
+#[test]
+fn test_config_theme_option() {
+    let args = vec!["--theme".to_string(), "highcontrast".to_string()];
+    let config = Config::from_args(args).unwrap();
+    assert_eq!(Some(config.theme), Theme::named("highcontrast"));
+
+    assert!(Config::from_args(vec!["--theme".to_string()]).is_err());
+    let args = vec!["--theme".to_string(), "no_such_theme.txt".to_string()];
+    assert!(Config::from_args(args).is_err());
+}
+
+#[test]
+fn test_config_spacious_option() {
+    let config = Config::from_args(vec!["--spacious".to_string()]).unwrap();
+
+    assert!(config.spacious);
+}
+
+#[test]
+fn test_config_args_override_base() {
+    let base = Config {
+        sound: false,
+        difficulty: Difficulty::Hard,
+        ..Config::default()
+    };
+    let args = vec!["--difficulty".to_string(), "easy".to_string()];
+    let config = Config::from_args_with(base, args).unwrap();
+
+    assert!(!config.sound);
+    assert_eq!(config.difficulty, Difficulty::Easy);
+}
+
+#[test]
+fn test_config_args_turn_saved_settings_back() {
+    let base = Config {
+        ascii: true,
+        color: true,
+        sound: false,
+        ..Config::default()
+    };
+    let args = ["--no-ascii", "--no-color", "--sound"].map(String::from);
+    let config = Config::from_args_with(base, args).unwrap();
+
+    assert!(!config.ascii);
+    assert!(!config.color);
+    assert!(config.sound);
+}
+
+#[test]
+fn test_config_diagonal_option() {
+    let config = Config::from_args(vec!["--diagonal".to_string()]).unwrap();
+
+    assert!(config.diagonal);
+}
+
+#[test]
+fn test_config_god_option() {
+    let config = Config::from_args(vec!["--god".to_string()]).unwrap();
+
+    assert!(config.god);
+}
+
+#[test]
+fn test_config_allow_skip_option() {
+    let config = Config::from_args(vec!["--allow-skip".to_string()]).unwrap();
+
+    assert!(config.allow_skip);
+}
+
+#[test]
+fn test_config_debug_perf_option() {
+    let config = Config::from_args(vec!["--debug-perf".to_string()]).unwrap();
+
+    assert!(config.debug_perf);
+}
+
+#[test]
+fn test_config_fog_option() {
+    let config = Config::from_args(vec!["--fog".to_string()]).unwrap();
+
+    assert!(config.fog);
+}
+
+#[test]
+fn test_config_danger_zone_option() {
+    let config = Config::from_args(vec!["--danger-zone".to_string()]).unwrap();
+
+    assert!(config.danger_zone);
+}
+
+#[test]
+fn test_config_list_levels_option() {
+    let config = Config::from_args(vec!["--list-levels".to_string()]).unwrap();
+
+    assert!(config.list_levels);
+}
+
+#[test]
+fn test_config_dash_option() {
+    let config = Config::from_args(vec!["--dash".to_string()]).unwrap();
+
+    assert!(config.dash);
+}
+
+#[test]
+fn test_config_mouse_options() {
+    let config = Config::from_args(vec!["--mouse".to_string()]).unwrap();
+    assert!(config.mouse);
+    assert!(!config.mouse_pathfind);
+
+    let config = Config::from_args(vec!["--mouse-pathfind".to_string()]).unwrap();
+    assert!(config.mouse);
+    assert!(config.mouse_pathfind);
+}
+
+#[test]
+fn test_config_demo_and_seed_options() {
+    let args = vec!["--demo".to_string(), "--seed".to_string(), "42".to_string()];
+    let config = Config::from_args(args).unwrap();
+
+    assert!(config.demo);
+    assert_eq!(config.seed, Some(42));
+    assert!(Config::from_args(vec!["--seed".to_string(), "soon".to_string()]).is_err());
+}
+
+#[test]
+fn test_config_leash_option() {
+    let config = Config::from_args(vec!["--leash".to_string(), "8".to_string()]).unwrap();
+
+    assert_eq!(config.leash, 8);
+    assert!(Config::from_args(vec!["--leash".to_string(), "-1".to_string()]).is_err());
+}
+
+#[test]
+fn test_config_peaceful_option() {
+    let config = Config::from_args(vec!["--peaceful".to_string()]).unwrap();
+
+    assert!(config.peaceful);
+}
+
+#[test]
+fn test_config_difficulty_option() {
+    let args = vec!["--difficulty".to_string(), "hard".to_string()];
+    assert_eq!(
+        Config::from_args(args).unwrap().difficulty,
+        Difficulty::Hard
+    );
+
+    let args = vec!["--difficulty".to_string(), "nightmare".to_string()];
+    assert!(Config::from_args(args).is_err());
+}
+
+#[test]
+fn test_config_no_sound_option() {
+    let config = Config::from_args(vec!["--no-sound".to_string()]).unwrap();
+
+    assert!(!config.sound);
+}
+
+#[test]
+fn test_config_pack_option() {
+    let config = Config::from_args(vec!["--pack".to_string(), "forest".to_string()]).unwrap();
+
+    assert_eq!(config.pack.as_deref(), Some("forest"));
+    assert_eq!(config.levels_dir(), Path::new("maps").join("forest"));
+    assert_eq!(Config::default().levels_dir(), Path::new("maps"));
+    assert!(Config::from_args(vec!["--pack".to_string()]).is_err());
+}
+
+#[test]
+fn test_config_no_flash_option() {
+    let config = Config::from_args(vec!["--no-flash".to_string()]).unwrap();
+
+    assert!(!config.flash);
+}
+
+#[test]
+fn test_config_no_confirm_quit_option() {
+    let config = Config::from_args(vec!["--no-confirm-quit".to_string()]).unwrap();
+
+    assert!(!config.confirm_quit);
+}
+
+#[test]
+fn test_config_deadly_traps_option() {
+    let config = Config::from_args(vec!["--deadly-traps".to_string()]).unwrap();
+
+    assert!(config.deadly_traps);
+}
+
+#[test]
+fn test_config_dump_map_option() {
+    let args = vec!["--dump-map".to_string(), "3".to_string()];
+    assert_eq!(Config::from_args(args).unwrap().dump_map, Some(3));
+
+    let args = vec!["--dump-map".to_string(), "zero".to_string()];
+    assert!(Config::from_args(args).is_err());
+}
+
+#[test]
+fn test_config_give_option() {
+    let args = vec!["--give".to_string(), "axe,sword,key".to_string()];
+    assert_eq!(
+        Config::from_args(args).unwrap().give,
+        vec![ItemType::Axe, ItemType::Sword, ItemType::Key]
+    );
+
+    let args = vec!["--give".to_string(), "wind-chime,dragonsword".to_string()];
+    assert_eq!(
+        Config::from_args(args).unwrap().give,
+        vec![ItemType::WindChime, ItemType::DragonSword]
+    );
+
+    let args = vec!["--give".to_string(), "axe,lightsaber".to_string()];
+    assert_eq!(
+        Config::from_args(args).err(),
+        Some("Unknown item: lightsaber".to_string())
+    );
+    assert!(Config::from_args(vec!["--give".to_string()]).is_err());
+}
+
+#[test]
+fn test_config_glyph_options() {
+    let args = vec![
+        "--player-glyph".to_string(),
+        "🦊".to_string(),
+        "--enemy-glyph".to_string(),
+        "&".to_string(),
+    ];
+    let config = Config::from_args(args).unwrap();
+    assert_eq!(config.player_glyph, Some("🦊".to_string()));
+    assert_eq!(config.enemy_glyph, Some("& ".to_string()));
+
+    let args = vec!["--player-glyph".to_string(), "🦊🦊".to_string()];
+    assert_eq!(
+        Config::from_args(args).err(),
+        Some("Glyph must fit in a single map cell: 🦊🦊".to_string())
+    );
+    assert!(Config::from_args(vec!["--player-glyph".to_string(), "abc".to_string()]).is_err());
+    assert!(Config::from_args(vec!["--player-glyph".to_string(), String::new()]).is_err());
+}
+
+#[test]
+fn test_config_map_option() {
+    let args = vec!["--map".to_string(), "custom.txt".to_string()];
+    assert_eq!(
+        Config::from_args(args).unwrap().map,
+        Some("custom.txt".to_string())
+    );
+
+    assert!(Config::from_args(vec!["--map".to_string()]).is_err());
+}
@@ -0,0 +1,136 @@
+//! The `Demo` struct plays a level on its own, as an attract loop shown before the game starts.
+//!
+//! A bot follows the same shortest path the hints suggest, one step after every
+//! few ticks of the game clock. The demo game is seeded, so the guards wander the
+//! same way every time, and it records nothing: no leaderboard times and no
+//! achievements. When the bot clears the level, or finds no way to the goal,
+//! the demo starts the level over.
+//!
+//! # Fields
+//!
+//! - `game` (`Game`): The game the bot plays, built with `Game::demo`.
+//! - `player` (`Player`): The bot's player.
+//! - `steps` (`u32`): The moves the bot has made since the level last started over.
+//!
+//! # Constants
+//!
+//! - `DEMO_SEED`: The seed of the demo game unless `--seed` gives another.
+//! - `DEMO_TICKS_PER_STEP`: The ticks of the game clock between two moves of the bot.
+//!
+//! # Methods
+//!
+//! - `new`: Creates a demo of the first level, or of the custom map given with `--map`, or reports why that level
+//!   couldn't be loaded.
+//! - `with_level`: Creates a demo of any level.
+//! - `step`: Advances the clock and makes the bot's next move, returning how it went.
+//! - `steps`: The moves made since the level last started over.
+//! - `render`: Draws the demo game, with a prompt to press any key in place of the controls.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut demo = Demo::new(&config)?;
+//! while !event::poll(DEMO_STEP_DURATION)? {
+//!     demo.step();
+//!     demo.render()?;
+//! }
+//! ```
+
+use crate::classes::config::Config;
+use crate::classes::game::{Game, GameError};
+use crate::classes::level::Level;
+use crate::classes::player::{MoveResult, Player};
+use std::io;
+
+pub const DEMO_SEED: u64 = 1;
+pub const DEMO_TICKS_PER_STEP: u32 = 3;
+
+pub struct Demo {
+    game: Game,
+    player: Player,
+    steps: u32,
+}
+
+impl Demo {
+    pub fn new(config: &Config) -> Result<Self, GameError> {
+        let level = match &config.map {
+            Some(path) => Level::read(path),
+            None => Level::read(Level::path(config.levels_dir(), 1)),
+        }
+        .map_err(|error| GameError::Level(1, error))?;
+        Ok(Self::with_level(config, level))
+    }
+
+    pub fn with_level(config: &Config, level: Level) -> Self {
+        let config = Config {
+            seed: Some(config.seed.unwrap_or(DEMO_SEED)),
+            ..config.clone()
+        };
+        let game = Game::demo(&config, level);
+        let player = game.init_player();
+
+        Self {
+            game,
+            player,
+            steps: 0,
+        }
+    }
+
+    pub fn step(&mut self) -> MoveResult {
+        for _ in 0..DEMO_TICKS_PER_STEP {
+            self.game.tick(&mut self.player);
+        }
+
+        let Some(dir) = self.game.next_hint(&self.player.pos) else {
+            self.start_over();
+            return MoveResult::Blocked;
+        };
+
+        let result = self.game.try_move(&mut self.player, dir);
+        self.steps += 1;
+        if matches!(result, MoveResult::NextLevel | MoveResult::GameClear) {
+            self.start_over();
+        }
+        result
+    }
+
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    pub fn render(&mut self) -> io::Result<()> {
+        self.game.render(&self.player)
+    }
+
+    fn start_over(&mut self) {
+        self.game.reset();
+        self.player = self.game.init_player();
+        self.steps = 0;
+    }
+}
+
+#[test]
+fn test_demo_reports_a_missing_level() {
+    let config = Config {
+        map: Some("maps/no_such_level.txt".to_string()),
+        ..Config::scratch()
+    };
+    assert!(matches!(Demo::new(&config), Err(GameError::Level(1, _))));
+}
+
+#[test]
+fn test_demo_bot_reaches_the_goal() {
+    let level: Level = "ttttttt\ntpsssst\nttttsgt\nttttttt".parse().unwrap();
+    let mut demo = Demo::with_level(&Config::scratch(), level);
+
+    // The shortest way is five steps: along the corridor, then down onto the goal
+    for step in 1..5 {
+        assert_eq!(demo.step(), MoveResult::Moved);
+        assert_eq!(demo.steps(), step);
+    }
+    assert_eq!(demo.step(), MoveResult::GameClear);
+
+    // Then the demo starts over
+    assert_eq!(demo.steps(), 0);
+    assert_eq!(demo.player.pos, demo.game.get_player_start());
+}
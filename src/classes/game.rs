@@ -7,14 +7,182 @@
 //! - `level` (`Level`): The instance of the current level, managing the map and environmental data.
 //! - `ui` (`UI`): The User Interface handler for rendering the game state.
 //! - `boss_health` (`u8`): The current health points of the boss entity.
+//! - `clock` (`u64`): The number of ticks the game clock has advanced; everything that moves on its own is driven by it.
+//! - `enemy_tick` (`u32`): The number of enemy updates performed so far, used to time stuns.
+//! - `stunned_enemies` (`Vec<(Position, u32)>`): Enemies stunned by the Wind Chime and the tick their stun ends.
+//! - `respawns` (`Vec<(Enemy, u64)>`): Slain guards waiting to come back on a level with `# respawn:`, and the clock tick they're due.
+//! - `invincible` (`bool`): Debug "god mode"; enemies, oni, and the boss can no longer kill the player.
+//! - `peaceful` (`bool`): Peaceful mode; levels load without patrol guards, and the oni and the boss stand still and only block the way.
+//! - `hints_remaining` (`u8`): The number of hints the player may still use on the current level.
+//! - `deadly_traps` (`bool`): Whether a hidden trap kills the player the moment it springs.
+//! - `deaths` (`u32`): The number of times the player has died during this run.
+//! - `kills` (`u32`): The number of enemies, oni, and bosses the player has slain during this run.
+//! - `run_moves` (`u32`): The moves made over the whole run, across every level.
+//! - `items_collected` (`u32`): The items the player has picked up, found, or received during this run.
+//! - `run_started` (`Instant`): When this run began, for the run summary.
+//! - `run_log` (`PathBuf`): The file run summaries are appended to, the `run_log` of the `Config`.
+//! - `run_logged` (`bool`): Whether this run is already in the run log, so a cleared run isn't logged twice on exit.
+//! - `last_bump` (`Option<(BlockingType, Instant)>`): The last blocker the player bumped into, used to throttle bump messages.
+//! - `show_danger_zone` (`bool`): Whether the tiles the guards could step onto next are highlighted, with `--danger-zone`.
+//! - `speed` (`f64`): The `--speed` factor scaling how long a tick of the game clock lasts.
+//! - `difficulty` (`Difficulty`): Scales the enemies of each level after it is loaded.
+//! - `maps_dir` (`String`): The directory level files are loaded from, the subdirectory of the chosen map pack if any.
+//! - `leaderboard` (`Leaderboard`): The best completion time and move count of each level. A map pack has a
+//!   leaderboard file of its own.
+//! - `achievements` (`Achievements`): The achievements unlocked so far, fed by the events of the game.
+//! - `level_started` (`Instant`): When the current level began, for timing the run.
+//! - `level_moves` (`u32`): The number of moves made on the current level.
+//! - `moves_remaining` (`Option<u32>`): The moves left before the level fails, on levels whose header sets a move limit.
+//!   The difficulty scales the limit.
+//! - `visited` (`HashSet<Position>`): Every tile the player has stood on during the current level, for the explored statistic.
+//! - `checkpoint` (`Option<Position>`): The last checkpoint reached on the current level, where the player respawns.
+//! - `confirm_quit` (`bool`): Whether quitting asks for confirmation first.
+//! - `quit_pending` (`bool`): Whether the player pressed quit and hasn't answered the confirmation yet.
+//! - `allow_skip` (`bool`): Debug option that lets playtesters skip levels.
+//! - `skip_pending` (`bool`): Whether the player asked to skip the level and hasn't answered the confirmation yet.
+//! - `level_inventory` (`Vec<ItemType>`): The items the player carried into the current level, restored by a practice restart.
+//! - `starting_inventory` (`Vec<ItemType>`): The items the player starts the run with, as given with `--give`.
+//! - `new_game_plus` (`bool`): Whether this is a New Game+ replay, where the guards move more often. It survives `reset`.
+//! - `single_level` (`Option<Level>`): The standalone map of a single-level game, reloaded in place of the numbered level files.
+//! - `rng` (`StdRng`): Drives the wandering guards. Seeded with `--seed` for runs that repeat exactly, otherwise from the OS.
+//! - `demo` (`bool`): Whether the bot of the demo is playing, in which case nothing is recorded on the leaderboard or as an achievement.
+//! - `escort` (`Option<Position>`): Where the rescued princess is following the player, once they have met her.
+//! - `leash` (`i32`): How far from home a hunting guard follows the player, from `--leash`.
+//! - `fog` (`bool`): Whether the map is covered in fog of war beyond the player's vision radius, with `--fog`.
+//! - `diagonal` (`bool`): Whether the player can move diagonally, so a click can lead to a diagonal step.
+//! - `mouse_pathfind` (`bool`): Whether a click follows a shortest path to the clicked tile, with `--mouse-pathfind`.
+//! - `light_ticks` (`u32`): The ticks left of the light the player took from a lantern, which lets them see farther in the fog.
+//! - `companion` (`Option<Position>`): Where the companion is following the player, once they have walked up to it.
+//! - `footprints` (`VecDeque<Position>`): The snow tiles the player last walked off, oldest first, at most `FOOTPRINT_TRAIL_LENGTH`.
+//! - `flipped_switches` (`HashSet<u8>`): The switches of the current level that are flipped, their walls open or closed.
+//! - `switch_tiles` (`HashMap<Position, TileType>`): The tile each cell linked to a switch started out as, before its
+//!   first flip, so flipping back restores the layout.
 //!
 //! # Methods
 //!
 //! - `default`: Provides a default implementation for the game.
 //! - `new`: Creates a new instance of the `Game` initialized with the first level, UI, and default settings.
-//! - `init_player`: Initializes the player for the current level, positioning them in the starting location.
+//!   It panics if the first level can't be loaded; `try_new` reports that as a `GameError` instead.
+//! - `with_config`: Creates a new instance of the `Game` using the given `Config`. Likewise, `try_with_config` is its fallible form.
+//! - `with_level`: Creates a single-level game from a custom map, which ends with the game clear screen at its goal.
+//!   Its times aren't recorded on the leaderboard.
+//! - `demo`: Creates a single-level game for the demo bot to play, which records nothing and prompts the viewer to press a key.
+//! - `init_player`: Initializes the player for the current level, positioning them in the starting location
+//!   with the items they carried into it.
 //! - `check_collision`: Checks if the given position collides with any object or boundary in the game and returns a `CollisionType`.
+//!   It ignores how the position is entered; it is the same as `check_collision_from` with `from` equal to `pos`.
+//! - `check_collision_from`: Like `check_collision`, but for a step from one position to an adjacent one, so
+//!   direction-dependent rules apply too: a diagonal step can't cut between two blockers, and a one-way tile
+//!   can't be entered against its arrow. The main loop resolves every move with this.
+//! - `cuts_corner`: Checks whether a diagonal move would squeeze between two blocking tiles.
+//! - `enters_against_one_way`: Checks whether a move would enter a one-way tile against its direction.
+//! - `try_move`: Has the player take a step in the given direction, applying every movement rule and interaction.
+//! - `resolve_move`: Resolves the player's pending move against the level: checks for collisions, runs the
+//!   matching interaction, and moves on to the next level at the goal. Swimming through shallow water costs
+//!   stamina and counts as two moves; every step on land recovers some stamina. `try_move` is built on this.
+//! - `handle_bump`: Cancels a move into a blocking tile and explains why the player can't pass.
+//! - `handle_door`: Opens a locked door with a key. Whether the key is used up depends on the level header.
+//! - `close_door_behind`: On levels whose doors close, locks an open door again once the player steps off it.
+//! - `after_step`: Marks the new tile explored, closes a door behind the player, and leaves a footprint in the snow,
+//!   whether the player walked or a conveyor carried them.
 //! - `handle_interaction`: Handles player interactions based on their pending movement and interactions with interactive objects like items, doors, or enemies.
+//! - `update_oni`: Moves each oni one step toward a nearby player; an oni that catches the player is resolved like the player walking into it.
+//! - `step_toward`: The neighboring tile that brings a chaser closest to its target among the tiles it may enter, if any gets closer.
+//!   Oni and hunting guards both chase with it.
+//! - `spikes_raised`: Whether spike tiles are currently raised. They alternate every `SPIKE_PHASE_TICKS` ticks of the clock,
+//!   starting sunk; entering raised spikes kills the player.
+//! - `use_wind_chime`: Stuns the enemies around the player if they carry the Wind Chime.
+//! - `shortest_path_to_goal`: Finds a shortest path from the level start to the goal or princess using breadth-first search.
+//! - `shortest_path_from`: Same as `shortest_path_to_goal`, starting from any position.
+//! - `shortest_path_to`: Finds a shortest path from one position to any other, ending with the step into the target.
+//!   The goal and the princess aren't walked through on the way.
+//! - `greedy_step`: The step that heads most directly toward a position: along the longer way to go, or the shorter
+//!   one when that is blocked, and diagonally when diagonal movement is on. Never `None` unless already there.
+//! - `click_step`: The step a mouse click at a terminal cell asks for, toward the tile drawn there; along a shortest
+//!   path with `--mouse-pathfind`, otherwise the greedy step.
+//! - `available_interactions`: Lists what the player could do by walking into each neighboring tile, such as
+//!   opening a door or picking up an item, with the item each one takes and whether the player carries it.
+//!   Hazards and secrets are left out.
+//! - `next_hint`: Returns the first step of a shortest path from the given position to the goal.
+//! - `request_hint`: Uses up one hint and highlights the next step toward the goal for the player.
+//! - `handle_game_clear`: Shows the victory screen with the stats of the run.
+//! - `handle_give_up`: Shows the defeat screen with the stats of the run, when the player quits.
+//! - `request_quit`: Starts quitting; returns `true` when the game should exit right away, or asks for confirmation.
+//! - `answer_quit`: Resolves a pending quit; returns `true` when the player confirmed it.
+//! - `request_skip`: With `--allow-skip`, asks the player to confirm skipping the current level.
+//! - `answer_skip`: Resolves a pending skip, skipping the level when the player confirmed it.
+//! - `skip_level`: Gives up on the current level and moves on without recording it, or clears the game after the last level.
+//! - `apply_settings`: Switches to settings chosen on the options screen. Display changes show on the next frame, a new difficulty applies from the next level.
+//! - `emit`: Reports a `GameEvent` to the achievements, announcing any that unlock.
+//! - `show_message`: Adds a message to the message log.
+//! - `dismiss_messages`: Clears the messages a key press dismisses, such as the explanation of a bump.
+//! - `toggle_photo_mode` / `is_photo_mode`: Enter or leave photo mode, which freezes the game so the camera can pan
+//!   around the map with `pan_camera` and snap back onto the player with `recenter_camera`.
+//! - `describe_surroundings`: Puts a description of the player's surroundings in words on the message log, for screen readers.
+//! - `set_perf_stats`: Passes the timings measured by the main loop on to the performance overlay.
+//! - `open_overlay` / `close_overlay`: Show or dismiss a full-screen overlay such as the inventory.
+//! - `max_levels`: The number of levels in the game.
+//! - `best_times`: The best recorded time of every level, `None` for levels not completed yet.
+//! - `run_summary`: Sums up the run so far as a `RunSummary`: the level reached, moves, deaths, kills, time, and items.
+//! - `log_run`: Appends the run summary to the run log, once per run. A run is logged as soon as the game is cleared,
+//!   before New Game+ can start it over, and otherwise when the player quits. The demo bot's runs aren't logged.
+//! - `reset`: Starts the whole run over from the first level, clearing deaths, kills, and every other per-run state.
+//!   `init_player` then gives a fresh player.
+//! - `start_new_game_plus`: Starts the run over as New Game+ after clearing the game, carrying the chosen items into level 1.
+//! - `enemy_move_ticks`: The ticks between two moves of the guards; fewer in New Game+.
+//! - `clock`: The number of ticks the game clock has advanced so far.
+//! - `tick_interval`: The real time between two ticks of the game clock at the current speed, which the main loop runs on.
+//! - `practice_restart`: Reloads the current level from scratch for practice. Unlike dying, it doesn't count as a death;
+//!   the level timer and moves start over, and the player gets back the items they entered the level with. Hints already
+//!   used stay used.
+//! - `load_level`: Jumps to the given level, resetting the per-level state. A level with no goal or princess is skipped with a message, since it could never be finished.
+//! - `tick`: Advances the game clock by one tick, moving enemies, oni, and platforms when their turn comes up.
+//!   Before anything else moves, a conveyor under the player carries them a tile along.
+//!   Spikes rising under the player kill them.
+//!   The main loop calls it every `TICK_DURATION`; tests can call it directly to step the game deterministically.
+//! - `wait`: Lets the player stand still while the clock runs up to the guards' next step, for timing puzzles.
+//!   The player doesn't move, and no move is counted.
+//! - `danger_zone`: The tiles the guards could step onto with their next move: every open tile next to a guard
+//!   that isn't stunned, by the same collision rules the guards move by.
+//! - `vision_radius`: How far the player sees in fog of war: `FOG_VISION_RADIUS`, or `LANTERN_VISION_RADIUS` while next to a
+//!   lantern or carrying its light.
+//! - `revealed_tiles`: The tiles within the player's vision radius, the only ones drawn in fog of war.
+//! - `update_light`: Refills the lantern light while the player stands next to a lantern, and burns it down otherwise.
+//! - `update_enemies`: Moves each guard that isn't stunned. Wanderers step in a random direction, while patrollers march along their row or column and turn around when blocked.
+//!   Hunters close in on a player within `HUNT_SIGHT_RADIUS`, but never stray more than `leash` steps from home; once the player
+//!   slips beyond the leash, they head back home.
+//! - `update_respawns`: Brings back the slain guards that are due on a level with `# respawn:`, as long as the level has fewer
+//!   guards than its `# max enemies:`. A guard comes back on the first free spawn tile of the level, or at its home on a level
+//!   without spawn tiles, and waits while nowhere is free.
+//! - `update_gates`: Opens the gates whose pressure plate is weighed down and closes the others, after every move and tick.
+//!   The player, their followers, and the guards all weigh plates down, so a guard can be lured onto one.
+//! - `update_conveyors`: Carries the player one tile along the conveyor they stand on, unless something blocks the way.
+//! - `update_platforms`: Moves every platform one tile along its track, carrying the player if they stand on it.
+//! - `dash`: Moves the player twice in a direction, as the combo of a double tap. The second step is only taken
+//!   when it leads onto open ground, so a dash stops early at anything in the way.
+//! - `count_move`: Counts a committed move toward the current level's move total, spending one from the move limit if there is one.
+//! - `count_moves`: Likewise, for a step that costs several moves, such as one onto mud or shallow water.
+//! - `run_out_of_moves`: Fails the level once the move limit is used up without reaching the goal, restarting it like a practice restart.
+//! - `reach_checkpoint`: Makes a checkpoint tile the player stepped on their new respawn point.
+//! - `read_sign`: Shows the text of a sign the player stepped on, as written in the level header.
+//! - `flip_switch`: Flips the switch the player stepped on, turning each of its linked tiles from wall to open ground
+//!   or back. Flipping it again restores the tiles as they started out.
+//! - `settle_switches`: Brings every tile linked to a switch in line with the switch. A wall never rises on someone
+//!   standing there; it stays pending until the tile is free, after every move and tick.
+//! - `is_switch_flipped`: Whether a switch of the current level has been flipped an odd number of times.
+//! - `respawn_position`: The latest checkpoint reached on this level, or the level start.
+//! - `handle_player_death`: Counts the death and sends the player back to their respawn position.
+//! - `meet_princess`: The princess joins the player, who must now escort her to the goal (or back to the level start).
+//! - `join_companion`: The companion at the tile the player walked into starts following them.
+//! - `follow_player`: Moves the companion onto the tile the player just left, and an escorted princess onto the tile
+//!   the companion left, or onto the player's if there is no companion.
+//! - `render`: Draws the current frame, failing with the terminal's I/O error.
+//! - `reach_goal`: Completes the level and moves on, unless the princess is still waiting to be rescued
+//!   or the player lacks an item the level header requires.
+//! - `complete_level`: Records the level's time and moves on the leaderboard when they beat the best time,
+//!   and sums up the level with how much of it was explored.
+//! - `explored_percent`: The share of the level's open tiles, those that don't block the player, that they have stood on.
+//! - `craft`: Applies the first recipe from `recipes::RECIPES` that matches the tile the player walks into.
 //! - `find_tile`: A helper method to find the position of a specific tile type in the map.
 //! - `has_any_tile`: Checks if any of the specified tile types exist on the current level map.
 //!
@@ -31,13 +199,27 @@
 //! game.handle_interaction(&mut player);
 //! ```
 
-use crate::classes::level::Level;
-use crate::classes::player::Player;
+use crate::classes::achievements::Achievements;
+use crate::classes::config::{Config, Difficulty};
+use crate::classes::leaderboard::Leaderboard;
+use crate::classes::level::{DoorMode, Enemy, KeyUse, Level, LevelError, Patrol};
+use crate::classes::player::{MoveResult, Player};
+use crate::classes::recipes;
+use crate::classes::run_log::RunSummary;
+use crate::classes::settings::Settings;
+use crate::classes::timing::{scaled, tick_duration, PerfStats};
 use crate::classes::types::{
-    BlockingType, CollisionType, InteractiveType, ItemType, Position, TileType,
+    BlockingType, CollisionType, Direction, GameEvent, InteractiveType, ItemType, Position,
+    TileType,
 };
-use crate::classes::ui::UI;
-use rand::Rng;
+use crate::classes::ui::{Overlay, UI};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub struct Game {
     current_level: usize,
@@ -45,8 +227,123 @@ pub struct Game {
     pub level: Level,
     ui: UI,
     boss_health: u8,
+    clock: u64,
+    enemy_tick: u32,
+    stunned_enemies: Vec<(Position, u32)>,
+    respawns: Vec<(Enemy, u64)>,
+    footprints: VecDeque<Position>,
+    flipped_switches: HashSet<u8>,
+    switch_tiles: HashMap<Position, TileType>,
+    pub invincible: bool,
+    peaceful: bool,
+    hints_remaining: u8,
+    pub deaths: u32,
+    pub kills: u32,
+    run_moves: u32,
+    items_collected: u32,
+    run_started: Instant,
+    run_log: PathBuf,
+    run_logged: bool,
+    deadly_traps: bool,
+    last_bump: Option<(BlockingType, Instant)>,
+    show_danger_zone: bool,
+    speed: f64,
+    difficulty: Difficulty,
+    maps_dir: String,
+    leaderboard: Leaderboard,
+    achievements: Achievements,
+    level_started: Instant,
+    pub level_moves: u32,
+    pub moves_remaining: Option<u32>,
+    visited: HashSet<Position>,
+    checkpoint: Option<Position>,
+    escort: Option<Position>,
+    companion: Option<Position>,
+    leash: i32,
+    fog: bool,
+    diagonal: bool,
+    mouse_pathfind: bool,
+    light_ticks: u32,
+    level_inventory: Vec<ItemType>,
+    starting_inventory: Vec<ItemType>,
+    new_game_plus: bool,
+    single_level: Option<Level>,
+    confirm_quit: bool,
+    quit_pending: bool,
+    allow_skip: bool,
+    skip_pending: bool,
+    rng: StdRng,
+    demo: bool,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum GoalOutcome {
+    NextLevel,
+    GameClear,
+    Blocked,
+}
+
+// Something the player could do by walking into a neighboring tile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interaction {
+    pub dir: Direction,
+    pub kind: InteractiveType,
+    // The item it takes, if any
+    pub item: Option<ItemType>,
+    // Whether the player carries what it takes
+    pub available: bool,
+}
+
+// Why a game couldn't be created
+#[derive(Debug, PartialEq)]
+pub enum GameError {
+    // The level with this number couldn't be loaded, or can never be finished
+    Level(usize, LevelError),
+    // There is no map pack with this name in the maps directory
+    MissingPack(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::Level(number, error) => write!(f, "Level {}: {}", number, error),
+            GameError::MissingPack(pack) => write!(f, "There is no map pack named {}", pack),
+        }
+    }
+}
+
+// Ticks between two moves of the enemies, the oni, and the moving platforms
+const ENEMY_MOVE_TICKS: u64 = 5;
+// The guards of a New Game+ replay move this often instead
+const NEW_GAME_PLUS_ENEMY_MOVE_TICKS: u64 = 3;
+const ONI_MOVE_TICKS: u64 = 10;
+const PLATFORM_MOVE_TICKS: u64 = 8;
+// Spikes stay sunk for this many ticks, then raised for as many
+const SPIKE_PHASE_TICKS: u64 = 10;
+// Enemies within this many tiles of the player (in any direction, so a Chebyshev distance) are stunned
+const WIND_CHIME_RADIUS: i32 = 2;
+// Bumping into the same kind of blocker again within this time stays silent
+const BUMP_MESSAGE_COOLDOWN: Duration = Duration::from_secs(2);
+// How long a bump message stays up, unless the next key press clears it sooner
+const BUMP_MESSAGE_DURATION: Duration = Duration::from_secs(3);
+// Sword hits it takes to defeat the boss
+const BOSS_HEALTH: u8 = 3;
+// Number of hints available on each level
+const HINTS_PER_LEVEL: u8 = 3;
+// An oni starts chasing the player within this many steps (a Manhattan distance)
+const ONI_SIGHT_RADIUS: i32 = 4;
+// A hunting guard notices the player within this many steps (a Manhattan distance)
+const HUNT_SIGHT_RADIUS: i32 = 4;
+// Number of enemy updates a stunned enemy skips
+const WIND_CHIME_STUN_TICKS: u32 = 6;
+// How far the player sees in fog of war (a Chebyshev distance), and how far by lantern light
+const FOG_VISION_RADIUS: i32 = 2;
+const LANTERN_VISION_RADIUS: i32 = 4;
+// Ticks a lantern's light lasts once the player walks away from it
+const LANTERN_LIGHT_TICKS: u32 = 50;
+// Footprints left in the snow before the oldest fill in again
+const FOOTPRINT_TRAIL_LENGTH: usize = 6;
+
 impl Default for Game {
     fn default() -> Self {
         Self::new()
@@ -55,39 +352,172 @@ impl Default for Game {
 
 impl Game {
     pub fn new() -> Self {
+        Self::try_new().expect("Failed to load first level")
+    }
+
+    pub fn try_new() -> Result<Self, GameError> {
+        Self::try_with_config(&Config::default())
+    }
+
+    pub fn with_config(config: &Config) -> Self {
+        Self::try_with_config(config).expect("Failed to load first level")
+    }
+
+    pub fn try_with_config(config: &Config) -> Result<Self, GameError> {
+        let levels_dir = config.levels_dir();
+        // The built-in levels stay at 10, while a pack has as many as it ships
+        let max_levels = match &config.pack {
+            Some(pack) if !levels_dir.is_dir() => return Err(GameError::MissingPack(pack.clone())),
+            Some(_) => Level::count(&levels_dir),
+            None => 10,
+        };
+        let level = Level::read(Level::path(&levels_dir, 1))
+            .and_then(|level| level.check_exit().map(|_| level))
+            .map_err(|error| GameError::Level(1, error))?;
+        Ok(Self::with_levels(config, level, max_levels))
+    }
+
+    pub fn with_level(config: &Config, level: Level) -> Self {
+        let mut game = Self::with_levels(config, level.clone(), 1);
+        game.single_level = Some(level);
+        game
+    }
+
+    pub fn demo(config: &Config, level: Level) -> Self {
+        let mut game = Self::with_level(config, level);
+        game.demo = true;
+        game.ui.set_prompt(Some(" Demo — press any key to play"));
+        game
+    }
+
+    fn with_levels(config: &Config, level: Level, max_levels: usize) -> Self {
         let current_level = 1;
-        let max_levels = 10;
-        let level = Level::load(current_level).expect("Failed to load first level");
-        let ui = UI::new();
+        if let Err(error) = level.check_exit() {
+            panic!("Level {} can't be played: {}", current_level, error);
+        }
+        let mut ui = UI::with_config(config);
+        ui.set_hints_remaining(HINTS_PER_LEVEL);
 
-        Self {
+        let mut game = Self {
             current_level,
             max_levels,
             level,
             ui,
-            boss_health: 3,
-        }
+            boss_health: BOSS_HEALTH,
+            clock: 0,
+            enemy_tick: 0,
+            stunned_enemies: Vec::new(),
+            respawns: Vec::new(),
+            footprints: VecDeque::with_capacity(FOOTPRINT_TRAIL_LENGTH),
+            flipped_switches: HashSet::new(),
+            switch_tiles: HashMap::new(),
+            invincible: config.god,
+            peaceful: config.peaceful,
+            hints_remaining: HINTS_PER_LEVEL,
+            deaths: 0,
+            kills: 0,
+            run_moves: 0,
+            items_collected: 0,
+            run_started: Instant::now(),
+            run_log: config.run_log.clone(),
+            run_logged: false,
+            deadly_traps: config.deadly_traps,
+            last_bump: None,
+            show_danger_zone: config.danger_zone,
+            speed: config.speed,
+            difficulty: config.difficulty,
+            maps_dir: config.levels_dir().to_string_lossy().into_owned(),
+            leaderboard: match &config.pack {
+                Some(pack) => {
+                    Leaderboard::load(Leaderboard::pack_path(&config.leaderboard_file, pack))
+                }
+                None => Leaderboard::load(&config.leaderboard_file),
+            },
+            achievements: Achievements::load(&config.achievements_file),
+            level_started: Instant::now(),
+            level_moves: 0,
+            moves_remaining: None,
+            visited: HashSet::new(),
+            checkpoint: None,
+            escort: None,
+            companion: None,
+            leash: config.leash,
+            fog: config.fog,
+            diagonal: config.diagonal,
+            mouse_pathfind: config.mouse_pathfind,
+            light_ticks: 0,
+            level_inventory: config.give.clone(),
+            starting_inventory: config.give.clone(),
+            new_game_plus: false,
+            single_level: None,
+            confirm_quit: config.confirm_quit,
+            quit_pending: false,
+            allow_skip: config.allow_skip,
+            skip_pending: false,
+            rng: match config.seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_os_rng(),
+            },
+            demo: false,
+        };
+
+        game.apply_difficulty();
+        game.moves_remaining = game.move_budget();
+        game.visited.insert(game.level.player_start);
+        game.emit(GameEvent::LevelStarted(current_level));
+        game
     }
 
     pub fn init_player(&self) -> Player {
-        let mut player = Player::new();
+        let mut player = Player::with_items(self.level_inventory.clone());
         player.reset_position(self.level.player_start);
         player
     }
 
     pub fn check_collision(&self, pos: &Position) -> CollisionType {
+        self.check_collision_from(pos, pos)
+    }
+
+    pub fn check_collision_from(&self, from: &Position, to: &Position) -> CollisionType {
+        if let Some(dir) = Direction::between(from, to) {
+            if self.cuts_corner(from, dir) {
+                // Report the blocker in the way, so the player is told what stopped them
+                let vertical = Position {
+                    row: to.row,
+                    col: from.col,
+                };
+                return self.collision_at(&vertical);
+            }
+            if self.enters_against_one_way(to, dir) {
+                return CollisionType::Blocking(BlockingType::OneWay);
+            }
+        }
+
+        self.collision_at(to)
+    }
+
+    fn collision_at(&self, pos: &Position) -> CollisionType {
         // Check bounds
         if pos.row < 0
             || pos.row >= self.level.map_size.0 as i16
             || pos.col < 0
             || pos.col >= self.level.map_size.1 as i16
         {
-            return CollisionType::Blocking(BlockingType::Wall);
+            return CollisionType::OutOfBounds;
+        }
+
+        // With the princess in tow, the way back to the start is a way out
+        if self.escort.is_some() && *pos == self.level.player_start {
+            return CollisionType::Goal;
         }
 
         // Check static obstacles
-        match self.level.map[pos.row as usize][pos.col as usize] {
+        let tile = self.level.map[pos.row as usize][pos.col as usize];
+        match tile {
             TileType::Wall => return CollisionType::Blocking(BlockingType::Wall),
+            TileType::CrackedWall => {
+                return CollisionType::Interactive(InteractiveType::CrackedWall)
+            }
             TileType::Bamboo => return CollisionType::Blocking(BlockingType::Bamboo),
             TileType::Mountain => return CollisionType::Blocking(BlockingType::Mountain),
             TileType::Water => return CollisionType::Blocking(BlockingType::Water),
@@ -97,9 +527,10 @@ impl Game {
             TileType::FlameA => return CollisionType::Blocking(BlockingType::FlameA),
             TileType::FlameB => return CollisionType::Blocking(BlockingType::FlameB),
             TileType::FlameC => return CollisionType::Blocking(BlockingType::FlameC),
-            TileType::Lantern => return CollisionType::Blocking(BlockingType::Lantern),
+            TileType::Lantern => return CollisionType::Interactive(InteractiveType::Lantern),
             TileType::Goal => return CollisionType::Goal,
             TileType::Princess => return CollisionType::Princess,
+            TileType::Companion => return CollisionType::Interactive(InteractiveType::Companion),
             TileType::Axe => {
                 return CollisionType::Interactive(InteractiveType::Item(ItemType::Axe))
             }
@@ -129,18 +560,195 @@ impl Game {
             TileType::CrystalA => return CollisionType::Interactive(InteractiveType::CrystalA),
             TileType::CrystalB => return CollisionType::Interactive(InteractiveType::CrystalB),
             TileType::CrystalC => return CollisionType::Interactive(InteractiveType::CrystalC),
-            TileType::Oni => return CollisionType::Interactive(InteractiveType::Oni),
             TileType::Boss => return CollisionType::Interactive(InteractiveType::Boss),
-            _ => {}
+            TileType::HiddenTrap => return CollisionType::Interactive(InteractiveType::HiddenTrap),
+            TileType::Trap => return CollisionType::Interactive(InteractiveType::Trap),
+            TileType::Spikes => return CollisionType::Interactive(InteractiveType::Spikes),
+            TileType::Gate(_) => return CollisionType::Blocking(BlockingType::Gate),
+            // The track can only be crossed while a platform is there
+            TileType::MovingPlatform => {
+                if !self
+                    .level
+                    .platforms
+                    .iter()
+                    .any(|platform| platform.pos == *pos)
+                {
+                    return CollisionType::Blocking(BlockingType::Track);
+                }
+            }
+            // Swimming is allowed, but `resolve_move` charges stamina for it
+            TileType::ShallowWater => {}
+            // Plain terrain and tiles left behind by interactions (an opened door,
+            // a looted cottage, a crafted canoe, ...) never block the player
+            TileType::Empty
+            | TileType::DoorOpen
+            | TileType::Tomb
+            | TileType::Canoe
+            | TileType::Alembic
+            | TileType::Link
+            | TileType::HookEnd
+            | TileType::Checkpoint
+            | TileType::OneWay(_)
+            | TileType::Pressure(_)
+            | TileType::GateOpen(_)
+            | TileType::Sign(_)
+            | TileType::Mud
+            | TileType::Snow
+            | TileType::Conveyor(_)
+            | TileType::Switch(_) => {}
         }
 
-        if self.level.enemies.contains(pos) {
+        if self.level.has_enemy_at(pos) {
             return CollisionType::Interactive(InteractiveType::Enemy);
         }
 
+        if self.level.oni.contains(pos) {
+            return CollisionType::Interactive(InteractiveType::Oni);
+        }
+
         CollisionType::None
     }
 
+    pub fn cuts_corner(&self, from: &Position, dir: Direction) -> bool {
+        if !dir.is_diagonal() {
+            return false;
+        }
+
+        let (dy, dx) = dir.delta();
+        let vertical = Position {
+            row: from.row + dy,
+            col: from.col,
+        };
+        let horizontal = Position {
+            row: from.row,
+            col: from.col + dx,
+        };
+
+        matches!(self.collision_at(&vertical), CollisionType::Blocking(_))
+            && matches!(self.collision_at(&horizontal), CollisionType::Blocking(_))
+    }
+
+    pub fn try_move(&mut self, player: &mut Player, dir: Direction) -> MoveResult {
+        player.move_in(dir);
+        self.resolve_move(player)
+    }
+
+    pub fn resolve_move(&mut self, player: &mut Player) -> MoveResult {
+        let Some(new_pos) = player.get_pending_move() else {
+            return MoveResult::Blocked;
+        };
+        let previous = player.pos;
+
+        let result = match self.check_collision_from(&player.pos, &new_pos) {
+            CollisionType::None => {
+                let tile = self.level.get_tile(&new_pos);
+                if tile == Some(TileType::ShallowWater) {
+                    if !player.swim() {
+                        self.handle_bump(player, BlockingType::Exhausted);
+                        return MoveResult::Blocked;
+                    }
+                } else {
+                    player.rest();
+                }
+
+                player.commit_move();
+                self.count_moves(tile.map_or(1, |tile| tile.movement_cost()));
+                self.follow_player(previous);
+                self.reach_checkpoint(&player.pos);
+                self.read_sign(&player.pos);
+                self.flip_switch(player);
+                MoveResult::Moved
+            }
+            CollisionType::Goal => match self.reach_goal(player) {
+                GoalOutcome::NextLevel => MoveResult::NextLevel,
+                GoalOutcome::GameClear => MoveResult::GameClear,
+                GoalOutcome::Blocked => MoveResult::Blocked,
+            },
+            CollisionType::Princess => {
+                self.meet_princess(player);
+                self.count_move();
+                MoveResult::Interacted
+            }
+            CollisionType::Interactive(_) => {
+                self.handle_interaction(player);
+                if player.pos == new_pos {
+                    self.count_move();
+                    self.follow_player(previous);
+                }
+                MoveResult::Interacted
+            }
+            CollisionType::Blocking(blocking_type) => {
+                self.handle_bump(player, blocking_type);
+                MoveResult::Blocked
+            }
+            CollisionType::OutOfBounds => {
+                player.cancel_move();
+                MoveResult::Blocked
+            }
+        };
+
+        self.update_gates(player);
+        self.settle_switches(player);
+
+        // Reaching the goal with the last move still counts
+        if matches!(result, MoveResult::Moved | MoveResult::Interacted) {
+            self.after_step(player, &previous);
+            self.run_out_of_moves(player);
+        }
+
+        result
+    }
+
+    // What every change of tile leaves behind, whether the player walked or was carried
+    fn after_step(&mut self, player: &Player, previous: &Position) {
+        self.visited.insert(player.pos);
+        self.close_door_behind(player, previous);
+        self.leave_footprint(player, previous);
+    }
+
+    fn close_door_behind(&mut self, player: &Player, previous: &Position) {
+        if self.level.header.doors == DoorMode::Closing
+            && player.pos != *previous
+            && self.level.get_tile(previous) == Some(TileType::DoorOpen)
+        {
+            self.level.set_tile(previous, TileType::Door);
+            self.ui.show_message("   The door locks again behind you ");
+        }
+    }
+
+    fn leave_footprint(&mut self, player: &Player, previous: &Position) {
+        if player.pos == *previous || self.level.get_tile(previous) != Some(TileType::Snow) {
+            return;
+        }
+        if self.footprints.len() == FOOTPRINT_TRAIL_LENGTH {
+            self.footprints.pop_front();
+        }
+        self.footprints.push_back(*previous);
+    }
+
+    pub fn enters_against_one_way(&self, to: &Position, dir: Direction) -> bool {
+        self.level
+            .get_tile(to)
+            .is_some_and(|tile| !tile.allows_entry(dir))
+    }
+
+    pub fn handle_bump(&mut self, player: &mut Player, blocking_type: BlockingType) -> bool {
+        player.cancel_move();
+
+        let throttled = matches!(
+            self.last_bump,
+            Some((last, at)) if last == blocking_type && at.elapsed() < scaled(BUMP_MESSAGE_COOLDOWN, self.speed)
+        );
+
+        if !throttled {
+            self.ui
+                .show_message_for(blocking_type.message(), BUMP_MESSAGE_DURATION, true);
+            self.last_bump = Some((blocking_type, Instant::now()));
+        }
+
+        !throttled
+    }
+
     pub fn handle_interaction(&mut self, player: &mut Player) {
         if let Some(new_pos) = player.get_pending_move() {
             if let CollisionType::Interactive(interactive_type) = self.check_collision(&new_pos) {
@@ -181,9 +789,246 @@ impl Game {
                     InteractiveType::Boss => {
                         self.handle_boss(player, &new_pos);
                     }
+                    InteractiveType::HiddenTrap | InteractiveType::Trap => {
+                        self.handle_trap(player, &new_pos);
+                    }
+                    InteractiveType::Spikes => {
+                        self.handle_spikes(player);
+                    }
+                    InteractiveType::Companion => {
+                        self.join_companion(player, &new_pos);
+                    }
+                    InteractiveType::Lantern => {
+                        self.handle_lantern(player);
+                    }
+                    InteractiveType::CrackedWall => {
+                        self.handle_cracked_wall(player, &new_pos);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn use_wind_chime(&mut self, player: &Player) -> bool {
+        if !player.has_item(ItemType::WindChime) {
+            return false;
+        }
+
+        let stun_end = self.enemy_tick + WIND_CHIME_STUN_TICKS;
+
+        for enemy in &self.level.enemies {
+            if enemy.pos.chebyshev(&player.pos) <= WIND_CHIME_RADIUS {
+                self.stunned_enemies.retain(|(pos, _)| *pos != enemy.pos);
+                self.stunned_enemies.push((enemy.pos, stun_end));
+            }
+        }
+
+        self.ui.show_message("   🎐 The wind chime rings out 🎐");
+        true
+    }
+
+    fn is_stunned(&self, pos: &Position) -> bool {
+        self.stunned_enemies
+            .iter()
+            .any(|(stunned, until)| stunned == pos && self.enemy_tick < *until)
+    }
+
+    pub fn shortest_path_to_goal(&self) -> Option<Vec<Direction>> {
+        self.shortest_path_from(&self.level.player_start)
+    }
+
+    pub fn shortest_path_from(&self, start: &Position) -> Option<Vec<Direction>> {
+        self.search_path(start, None)
+    }
+
+    pub fn shortest_path_to(&self, start: &Position, target: &Position) -> Option<Vec<Direction>> {
+        self.search_path(start, Some(target))
+    }
+
+    // Searches breadth-first for the target, or for the goal or princess when there is none
+    fn search_path(&self, start: &Position, target: Option<&Position>) -> Option<Vec<Direction>> {
+        let (rows, cols) = (
+            self.level.map_size.0 as usize,
+            self.level.map_size.1 as usize,
+        );
+        // The direction used to first reach each tile, for walking the path back
+        let mut came_from: Vec<Vec<Option<Direction>>> = vec![vec![None; cols]; rows];
+        let mut visited = vec![vec![false; cols]; rows];
+        let mut queue = std::collections::VecDeque::new();
+
+        // Starting outside the map can never reach the goal
+        self.level.get_tile(start)?;
+        visited[start.row as usize][start.col as usize] = true;
+        queue.push_back(*start);
+
+        while let Some(pos) = queue.pop_front() {
+            for (dir, next, _) in self.level.neighbors(&pos) {
+                let collision = self.check_collision_from(&pos, &next);
+                let reached = match target {
+                    Some(target) => next == *target,
+                    None => matches!(collision, CollisionType::Goal | CollisionType::Princess),
+                };
+                if reached {
+                    came_from[next.row as usize][next.col as usize] = Some(dir);
+                    return Some(self.trace_path(&came_from, start, &next));
+                }
+
+                let walkable = match collision {
+                    // Items and companions are picked up on the way; enemies move, so they never block for good.
+                    // Hidden traps look like empty ground, so hints mustn't give them away.
+                    // Spikes sink again, so they are only a matter of timing.
+                    CollisionType::None
+                    | CollisionType::Interactive(InteractiveType::Item(_))
+                    | CollisionType::Interactive(InteractiveType::Companion)
+                    | CollisionType::Interactive(InteractiveType::Enemy)
+                    | CollisionType::Interactive(InteractiveType::HiddenTrap)
+                    | CollisionType::Interactive(InteractiveType::Spikes) => true,
+                    _ => false,
+                };
+
+                if walkable && !visited[next.row as usize][next.col as usize] {
+                    visited[next.row as usize][next.col as usize] = true;
+                    came_from[next.row as usize][next.col as usize] = Some(dir);
+                    queue.push_back(next);
                 }
             }
         }
+
+        None
+    }
+
+    pub fn available_interactions(&self, player: &Player) -> Vec<Interaction> {
+        self.level
+            .neighbors(&player.pos)
+            .filter_map(|(dir, pos, tile)| {
+                let CollisionType::Interactive(kind) = self.check_collision(&pos) else {
+                    return None;
+                };
+                let item = match kind {
+                    // Walking into these is no action, and a hint would give the secrets away
+                    InteractiveType::HiddenTrap
+                    | InteractiveType::Trap
+                    | InteractiveType::Spikes
+                    | InteractiveType::CrackedWall => return None,
+                    InteractiveType::WoodLog | InteractiveType::Door | InteractiveType::Rock => {
+                        recipes::find(&self.level, player, &pos)
+                            .or_else(|| {
+                                recipes::RECIPES.iter().find(|recipe| recipe.target == tile)
+                            })
+                            .map(|recipe| recipe.item)
+                    }
+                    InteractiveType::HookStart => Some(ItemType::Hook),
+                    InteractiveType::Enemy => Some(ItemType::Sword),
+                    InteractiveType::Oni => Some(ItemType::WindChime),
+                    InteractiveType::Boss => Some(ItemType::DragonSword),
+                    InteractiveType::Item(_)
+                    | InteractiveType::Cottage
+                    | InteractiveType::CrystalA
+                    | InteractiveType::CrystalB
+                    | InteractiveType::CrystalC
+                    | InteractiveType::Companion
+                    | InteractiveType::Lantern => None,
+                };
+
+                Some(Interaction {
+                    dir,
+                    kind,
+                    item,
+                    available: item.is_none_or(|item| player.has_item(item)),
+                })
+            })
+            .collect()
+    }
+
+    pub fn greedy_step(&self, from: &Position, to: &Position) -> Option<Direction> {
+        let (dy, dx) = (to.row - from.row, to.col - from.col);
+        let vertical = match dy.signum() {
+            -1 => Some(Direction::Up),
+            1 => Some(Direction::Down),
+            _ => None,
+        };
+        let horizontal = match dx.signum() {
+            -1 => Some(Direction::Left),
+            1 => Some(Direction::Right),
+            _ => None,
+        };
+        let (longer, shorter) = if dy.abs() >= dx.abs() {
+            (vertical, horizontal)
+        } else {
+            (horizontal, vertical)
+        };
+        let diagonal = Direction::toward(from, to).filter(|dir| self.diagonal && dir.is_diagonal());
+
+        let steps: Vec<Direction> = diagonal.into_iter().chain(longer).chain(shorter).collect();
+        // With every way blocked, bumping into the first one at least tells the player why
+        steps
+            .iter()
+            .copied()
+            .find(|dir| {
+                !matches!(
+                    self.check_collision_from(from, &from.offset(*dir)),
+                    CollisionType::Blocking(_) | CollisionType::OutOfBounds
+                )
+            })
+            .or(steps.first().copied())
+    }
+
+    pub fn click_step(&self, player: &Player, column: u16, row: u16) -> Option<Direction> {
+        let target = self.ui.tile_at(&self.level, player, column, row)?;
+        if self.mouse_pathfind {
+            let path = self.shortest_path_to(&player.pos, &target);
+            if let Some(dir) = path.and_then(|path| path.first().copied()) {
+                return Some(dir);
+            }
+        }
+        self.greedy_step(&player.pos, &target)
+    }
+
+    pub fn next_hint(&self, from: &Position) -> Option<Direction> {
+        self.shortest_path_from(from)
+            .and_then(|path| path.first().copied())
+    }
+
+    pub fn request_hint(&mut self, player: &Player) -> Option<Direction> {
+        if self.hints_remaining == 0 {
+            self.ui.show_message("   No hints left on this level ");
+            return None;
+        }
+
+        let hint = self.next_hint(&player.pos);
+
+        match hint {
+            Some(dir) => {
+                self.hints_remaining -= 1;
+                self.ui.show_hint(player.pos.offset(dir));
+            }
+            None => self
+                .ui
+                .show_message("   Walking alone won't get you there "),
+        }
+
+        self.ui.set_hints_remaining(self.hints_remaining);
+        hint
+    }
+
+    fn trace_path(
+        &self,
+        came_from: &[Vec<Option<Direction>>],
+        start: &Position,
+        end: &Position,
+    ) -> Vec<Direction> {
+        let mut path = Vec::new();
+        let mut current = *end;
+
+        while current != *start {
+            let dir = came_from[current.row as usize][current.col as usize]
+                .expect("Every visited tile has a direction");
+            path.push(dir);
+            current = current.offset(dir.opposite());
+        }
+
+        path.reverse();
+        path
     }
 
     fn find_tile(&self, tile_type: TileType) -> Option<Position> {
@@ -211,68 +1056,71 @@ impl Game {
 
     fn handle_item_pickup(&mut self, player: &mut Player, pos: &Position, item_type: ItemType) {
         // Add the item to player's inventory
-        player.add_item(item_type);
+        self.collect_item(player, item_type);
 
         // Remove the item from the map
         self.level.map[pos.row as usize][pos.col as usize] = TileType::Empty;
 
         // Allow movement to this tile
         player.commit_move();
+        self.ui.beep();
     }
 
-    fn handle_wood_log(&mut self, player: &mut Player, pos: &Position) {
-        if player.has_item(ItemType::Axe) {
-            // Check for water to the right
-            let water_pos = Position {
-                row: pos.row,
-                col: pos.col + 1,
-            };
+    fn collect_item(&mut self, player: &mut Player, item_type: ItemType) {
+        player.add_item(item_type);
+        self.items_collected += 1;
+    }
 
-            let is_water_to_right = self.level.get_tile(&water_pos) == Some(TileType::Water);
+    // Uses the first matching recipe, returning whether there was one
+    fn craft(&mut self, player: &mut Player, pos: &Position) -> bool {
+        let Some(recipe) = recipes::find(&self.level, player, pos) else {
+            return false;
+        };
 
-            if is_water_to_right {
-                // Water to the right - create canoe
-                self.level.set_tile(&water_pos, TileType::Canoe);
-                self.level.set_tile(pos, TileType::Empty);
-                player.remove_item(ItemType::Axe);
-                self.ui.show_message("   You crafted a canoe ");
-                player.cancel_move();
-            } else {
-                // No water - just remove log
-                self.level.set_tile(pos, TileType::Empty);
-                player.remove_item(ItemType::Axe);
-                self.ui.show_message("   You chopped the log ");
-                player.commit_move();
-            }
+        self.level.set_tile(pos, recipe.result);
+        if let (Some((dir, _)), Some(tile)) = (recipe.beside, recipe.beside_result) {
+            self.level.set_tile(&pos.offset(dir), tile);
+        }
+        // On levels with multi-use keys the key survives the recipe that opens the door
+        if recipe.item != ItemType::Key || self.level.header.keys != KeyUse::Multi {
+            player.remove_item(recipe.item);
+        }
+        self.ui.show_message(recipe.message);
+
+        if recipe.passes {
+            player.commit_move();
         } else {
             player.cancel_move();
         }
+        true
     }
 
-    fn handle_door(&mut self, player: &mut Player, pos: &Position) {
-        if player.has_item(ItemType::Key) {
-            self.level.set_tile(pos, TileType::DoorOpen);
-            player.remove_item(ItemType::Key);
-            self.ui.show_message("   You opened the door ");
+    fn handle_wood_log(&mut self, player: &mut Player, pos: &Position) {
+        if !self.craft(player, pos) {
+            self.ui
+                .show_message("   The log is too heavy to move — you need an axe ");
             player.cancel_move();
-        } else {
+        }
+    }
+
+    fn handle_door(&mut self, player: &mut Player, pos: &Position) {
+        if !self.craft(player, pos) {
+            self.ui
+                .show_message("   The door is locked — you need a key ");
             player.cancel_move();
         }
     }
 
     fn handle_cottage(&mut self, player: &mut Player, pos: &Position) {
         self.level.set_tile(pos, TileType::Tomb);
-        player.add_item(ItemType::Bomb);
+        self.collect_item(player, ItemType::Bomb);
         self.ui.show_message("   You found a bomb ");
         player.cancel_move();
     }
 
     fn handle_rock(&mut self, player: &mut Player, pos: &Position) {
-        if player.has_item(ItemType::Bomb) {
-            self.level.set_tile(pos, TileType::Empty);
-            player.remove_item(ItemType::Bomb);
-            self.ui.show_message("  💥 The rock crumbles to dust 💥");
-
+        if self.craft(player, pos) {
+            // The blast shakes loose a fresh bomb in every looted cottage
             for row in 0..self.level.map_size.0 as usize {
                 for col in 0..self.level.map_size.1 as usize {
                     if self.level.map[row][col] == TileType::Tomb {
@@ -283,42 +1131,60 @@ impl Game {
 
             player.cancel_move();
         } else {
+            self.ui
+                .show_message("   This rock is too hard — you need a bomb ");
             player.cancel_move();
         }
     }
 
-    fn handle_hook_start(&mut self, player: &mut Player, pos: &Position) {
-        if player.has_item(ItemType::Hook) {
-            let hook_end = self.find_tile(TileType::HookEnd);
-
-            if let Some(end_pos) = hook_end {
-                self.level.set_tile(pos, TileType::Link);
-
-                if pos.row == end_pos.row {
-                    let start_col = pos.col.min(end_pos.col);
-                    let end_col = pos.col.max(end_pos.col);
+    fn handle_cracked_wall(&mut self, player: &mut Player, pos: &Position) {
+        // Without a tool it gives nothing away, bumping like any other wall
+        if !self.craft(player, pos) {
+            self.handle_bump(player, BlockingType::Wall);
+        }
+    }
 
-                    for col in start_col..=end_col {
-                        let current = Position { row: pos.row, col };
-                        self.level.set_tile(&current, TileType::Link);
-                    }
-                } else if pos.col == end_pos.col {
-                    let start_row = pos.row.min(end_pos.row);
-                    let end_row = pos.row.max(end_pos.row);
+    fn handle_hook_start(&mut self, player: &mut Player, pos: &Position) {
+        player.cancel_move();
+        if !player.has_item(ItemType::Hook) {
+            return;
+        }
+        let Some(end_pos) = self.find_tile(TileType::HookEnd) else {
+            return;
+        };
+        let Some(line) = Self::hook_line(pos, &end_pos) else {
+            self.ui
+                .show_message("   The anchor isn't in line with the hook point ");
+            return;
+        };
 
-                    for row in start_row..=end_row {
-                        let current = Position { row, col: pos.col };
-                        self.level.set_tile(&current, TileType::Link);
-                    }
-                }
-            }
+        for current in &line {
+            self.level.set_tile(current, TileType::Link);
+        }
+        player.remove_item(ItemType::Hook);
 
-            player.remove_item(ItemType::Hook);
-            self.ui.show_message("   You hooked the link ");
-            player.cancel_move();
+        // The hook pulls the player across in one move, landing next to the anchor
+        let previous = player.pos;
+        let landing = line[line.len() - 2];
+        if landing != previous {
+            player.reset_position(landing);
+            self.count_move();
+            self.follow_player(previous);
+            self.ui.show_message("   You swing across on the hook ");
         } else {
-            player.cancel_move();
+            self.ui.show_message("   You hooked the link ");
+        }
+    }
+
+    // Every position on the straight row or column from `from` to `to`, both
+    // ends included; `None` when the two aren't in line.
+    fn hook_line(from: &Position, to: &Position) -> Option<Vec<Position>> {
+        if from.row != to.row && from.col != to.col {
+            return None;
         }
+        let dir = Direction::toward(from, to)?;
+        let steps = from.manhattan(to) as i16;
+        Some((0..=steps).map(|i| from.neighbor(dir, i)).collect())
     }
 
     fn handle_crystal(&mut self, player: &mut Player, pos: &Position) {
@@ -342,7 +1208,7 @@ impl Game {
         let all_flames_removed = !self.has_any_tile(&flame_types);
 
         if all_flames_removed {
-            player.add_item(ItemType::WindChime);
+            self.collect_item(player, ItemType::WindChime);
             self.ui.show_message("   🎐 You received a Wind Chime! 🎐");
         }
 
@@ -351,21 +1217,62 @@ impl Game {
 
     fn handle_oni(&mut self, player: &mut Player, pos: &Position) {
         if player.has_item(ItemType::WindChime) {
-            self.level.set_tile(pos, TileType::Empty);
+            self.level.oni.retain(|oni| oni != pos);
             player.remove_item(ItemType::WindChime);
             self.ui.show_message("   The wind chime cleanses the air ");
-            player.add_item(ItemType::DragonSword);
+            self.collect_item(player, ItemType::DragonSword);
             self.ui.show_message("   You found a Dragon Sword ");
+            self.record_kill();
+            player.commit_move();
+        } else if self.invincible {
+            // God mode walks straight through the encounter
             player.commit_move();
+        } else if self.peaceful {
+            self.ui
+                .show_message("   The oni bars your way — a wind chime would cleanse it ");
+            player.cancel_move();
         } else {
-            self.handle_player_death();
-            player.reset_position(self.get_player_start());
+            self.ui
+                .show_message("   The oni's curse overwhelms you — you need a wind chime ");
+            self.handle_player_death(player);
+        }
+    }
+
+    fn handle_trap(&mut self, player: &mut Player, pos: &Position) {
+        let hidden = self.level.get_tile(pos) == Some(TileType::HiddenTrap);
+        // The map itself remembers a sprung trap, the way an opened door becomes `DoorOpen`
+        self.level.set_tile(pos, TileType::Trap);
+
+        if hidden && !self.deadly_traps {
+            self.ui
+                .show_message("   A trap springs beneath you! Watch your step ");
+            player.commit_move();
+        } else if self.invincible {
+            // God mode walks straight through the encounter
+            player.commit_move();
+        } else {
+            self.ui.show_message("   The spikes get you ");
+            self.handle_player_death(player);
+        }
+    }
+
+    pub fn spikes_raised(&self) -> bool {
+        (self.clock / SPIKE_PHASE_TICKS) % 2 == 1
+    }
+
+    fn handle_spikes(&mut self, player: &mut Player) {
+        if !self.spikes_raised() || self.invincible {
+            player.commit_move();
+        } else {
+            self.ui.show_message("   The spikes get you ");
+            self.handle_player_death(player);
         }
     }
 
     fn handle_boss(&mut self, player: &mut Player, pos: &Position) {
         if player.has_item(ItemType::DragonSword) {
             self.ui.show_message("   ⚔️\u{200B} Clash! ⚔️\u{200B}");
+            self.ui.flash();
 
             if self.boss_health > 0 {
                 self.boss_health -= 1;
@@ -373,22 +1280,34 @@ impl Game {
                 if self.boss_health > 0 {
                     self.ui
                         .show_message("   You are pushed away by the strong impact...");
-                    self.ui
-                        .show_message(&format!("   Boss health: {}/3", self.boss_health));
+                    self.ui.show_message(&format!(
+                        "   Boss health: {}/{}",
+                        self.boss_health, BOSS_HEALTH
+                    ));
                 }
 
                 if self.boss_health == 0 {
-                    self.level.set_tile(pos, TileType::Empty);
+                    // A boss can span a block of tiles, all of which fall together
+                    for tile in self.level.connected(pos) {
+                        self.level.set_tile(&tile, TileType::Empty);
+                    }
                     self.ui.show_message("  💥 Boss defeated! 💥");
+                    self.record_kill();
                     player.commit_move();
                     return;
                 }
             }
 
             player.reset_position(self.get_player_start());
+        } else if self.invincible {
+            // God mode walks straight through the encounter
+            player.commit_move();
+        } else if self.peaceful {
+            self.ui
+                .show_message("   The boss blocks the way — you need the Dragon Sword ");
+            player.cancel_move();
         } else {
-            self.handle_player_death();
-            player.reset_position(self.get_player_start());
+            self.handle_player_death(player);
         }
     }
 
@@ -398,248 +1317,3354 @@ impl Game {
             player.remove_item(ItemType::Sword);
             self.ui
                 .show_message("   You slayed an enemy, a small victory ");
+            self.record_kill();
+            player.commit_move();
+        } else if self.invincible {
+            // God mode walks straight through the encounter
             player.commit_move();
+        } else if self.companion.take().is_some() {
+            // The companion throws itself in the guard's way, holding it off for a while
+            player.cancel_move();
+            let stun_end = self.enemy_tick + WIND_CHIME_STUN_TICKS;
+            self.stunned_enemies.retain(|(stunned, _)| stunned != pos);
+            self.stunned_enemies.push((*pos, stun_end));
+            self.ui
+                .show_message("   Your companion holds the guard off, and is lost ");
         } else {
-            self.handle_player_death();
-            player.reset_position(self.get_player_start());
+            self.handle_player_death(player);
         }
     }
 
-    pub fn remove_enemy(&mut self, pos: &Position) {
-        self.level.enemies.retain(|enemy| enemy != pos);
+    pub fn run_summary(&self) -> RunSummary {
+        RunSummary {
+            ended: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            level: self.current_level,
+            moves: self.run_moves,
+            deaths: self.deaths,
+            kills: self.kills,
+            time: self.run_started.elapsed(),
+            items: self.items_collected,
+        }
     }
 
-    pub fn update_enemies(&mut self) {
-        let mut rng = rand::rng();
-        let directions = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+    pub fn log_run(&mut self) -> io::Result<()> {
+        // The demo bot's runs are no runs of the player's
+        if self.demo || self.run_logged {
+            return Ok(());
+        }
+        self.run_logged = true;
+        self.run_summary().append(&self.run_log)
+    }
 
-        let mut enemies = std::mem::take(&mut self.level.enemies);
+    // The run ends here, even if New Game+ starts another one right away
+    fn clear_game(&mut self) -> GoalOutcome {
+        // The run log is a nicety; failing to write it shouldn't spoil the clear
+        let _ = self.log_run();
+        GoalOutcome::GameClear
+    }
 
-        for enemy in &mut enemies {
-            if rng.random_bool(0.8) {
-                let (dy, dx) = directions[rng.random_range(0..4)];
-                let new_pos = Position {
-                    row: enemy.row + dy,
-                    col: enemy.col + dx,
-                };
+    fn record_kill(&mut self) {
+        self.kills += 1;
+        self.emit(GameEvent::EnemySlain);
+    }
 
-                if self.check_collision(&new_pos) == CollisionType::None {
-                    *enemy = new_pos;
-                }
-            }
+    pub fn remove_enemy(&mut self, pos: &Position) {
+        if let Some(ticks) = self.level.header.respawn_ticks {
+            let slain = self.level.enemies.iter().filter(|enemy| enemy.pos == *pos);
+            // A huge delay from the level header means never, rather than an overflow
+            let due = self.clock.saturating_add(ticks);
+            let respawns: Vec<(Enemy, u64)> = slain
+                .map(|enemy| (Enemy::patrolling(enemy.home, enemy.patrol), due))
+                .collect();
+            self.respawns.extend(respawns);
+        }
+        self.level.enemies.retain(|enemy| enemy.pos != *pos);
+        self.stunned_enemies.retain(|(stunned, _)| stunned != pos);
+    }
+
+    fn handle_lantern(&mut self, player: &mut Player) {
+        // Without fog there's nothing to light up, so the lantern is just in the way
+        if !self.fog {
+            self.handle_bump(player, BlockingType::Lantern);
+            return;
         }
 
-        self.level.enemies = enemies;
+        player.cancel_move();
+        self.light_ticks = LANTERN_LIGHT_TICKS;
+        self.ui
+            .show_message("   🏮 You take a light from the lantern ");
     }
 
-    pub fn advance_level(&mut self) -> bool {
-        self.current_level += 1;
-        if self.current_level <= self.max_levels {
-            if let Some(new_level) = Level::load(self.current_level) {
-                self.level = new_level;
-                true
-            } else {
-                false
-            }
+    fn is_near_lantern(&self, pos: &Position) -> bool {
+        (-1..=1).any(|row| {
+            (-1..=1).any(|col| {
+                let near = Position {
+                    row: pos.row + row,
+                    col: pos.col + col,
+                };
+                self.level.get_tile(&near) == Some(TileType::Lantern)
+            })
+        })
+    }
+
+    pub fn vision_radius(&self, player: &Player) -> i32 {
+        if self.light_ticks > 0 || self.is_near_lantern(&player.pos) {
+            LANTERN_VISION_RADIUS
+        } else {
+            FOG_VISION_RADIUS
+        }
+    }
+
+    pub fn revealed_tiles(&self, player: &Player) -> HashSet<Position> {
+        let radius = self.vision_radius(player) as i16;
+        (-radius..=radius)
+            .flat_map(|row| {
+                (-radius..=radius).map(move |col| Position {
+                    row: player.pos.row + row,
+                    col: player.pos.col + col,
+                })
+            })
+            .filter(|pos| self.level.get_tile(pos).is_some())
+            .collect()
+    }
+
+    pub fn update_light(&mut self, player: &Player) {
+        if self.is_near_lantern(&player.pos) {
+            self.light_ticks = LANTERN_LIGHT_TICKS;
         } else {
+            self.light_ticks = self.light_ticks.saturating_sub(1);
+        }
+    }
+
+    pub fn update_respawns(&mut self, player: &Player) {
+        let cap = self.level.header.max_enemies.unwrap_or(usize::MAX);
+        let clock = self.clock;
+        let mut respawns = std::mem::take(&mut self.respawns);
+
+        // A guard that is due waits while the level is full or there's nowhere free to come back
+        respawns.retain(|(enemy, due)| {
+            if clock < *due || self.level.enemies.len() >= cap {
+                return true;
+            }
+            let free = |pos: &Position| {
+                *pos != player.pos && self.check_collision(pos) == CollisionType::None
+            };
+            let spot = if self.level.spawns.is_empty() {
+                Some(enemy.home).filter(free)
+            } else {
+                self.level.spawns.iter().copied().find(free)
+            };
+            let Some(pos) = spot else {
+                return true;
+            };
+            self.level
+                .enemies
+                .push(Enemy::patrolling(pos, enemy.patrol));
             false
+        });
+
+        self.respawns = respawns;
+    }
+
+    pub fn danger_zone(&self) -> HashSet<Position> {
+        self.level
+            .enemies
+            .iter()
+            .filter(|enemy| !self.is_stunned(&enemy.pos))
+            .flat_map(|enemy| self.level.neighbors(&enemy.pos))
+            .map(|(_, next, _)| next)
+            .filter(|next| self.check_collision(next) == CollisionType::None)
+            .collect()
+    }
+
+    pub fn update_enemies(&mut self, player: &Player) {
+        if self.peaceful {
+            return;
+        }
+
+        self.enemy_tick += 1;
+        let tick = self.enemy_tick;
+        self.stunned_enemies.retain(|(_, until)| tick < *until);
+
+        let mut enemies = std::mem::take(&mut self.level.enemies);
+
+        for enemy in &mut enemies {
+            if self.is_stunned(&enemy.pos) {
+                continue;
+            }
+
+            match enemy.patrol {
+                Patrol::Wander => {
+                    if self.rng.random_bool(0.8) {
+                        let dir = Direction::ALL[self.rng.random_range(0..Direction::ALL.len())];
+                        let new_pos = enemy.pos.offset(dir);
+
+                        if self.check_collision(&new_pos) == CollisionType::None {
+                            enemy.pos = new_pos;
+                        }
+                    }
+                }
+                // Patrollers march along their line and turn back at anything in the way
+                Patrol::Horizontal | Patrol::Vertical => {
+                    if self.check_collision(&enemy.pos.offset(enemy.heading)) != CollisionType::None
+                    {
+                        enemy.turn_around();
+                    }
+
+                    let new_pos = enemy.pos.offset(enemy.heading);
+                    if self.check_collision(&new_pos) == CollisionType::None {
+                        enemy.pos = new_pos;
+                    }
+                }
+                Patrol::Hunt => {
+                    // A player beyond the leash has escaped, so the hunter heads home
+                    let chasing = enemy.pos.manhattan(&player.pos) <= HUNT_SIGHT_RADIUS
+                        && player.pos.manhattan(&enemy.home) <= self.leash;
+                    let target = if chasing { player.pos } else { enemy.home };
+
+                    let step = self.step_toward(&enemy.pos, &target, |next| {
+                        *next != player.pos
+                            && next.manhattan(&enemy.home) <= self.leash
+                            && self.check_collision(next) == CollisionType::None
+                    });
+                    if let Some(next) = step {
+                        enemy.pos = next;
+                    }
+                }
+            }
         }
+
+        self.level.enemies = enemies;
     }
 
-    pub fn get_player_start(&self) -> Position {
-        self.level.player_start
+    // The neighbor of `from` among those `allowed` that comes closest to `target`,
+    // or `None` when no step gets any closer
+    fn step_toward(
+        &self,
+        from: &Position,
+        target: &Position,
+        allowed: impl Fn(&Position) -> bool,
+    ) -> Option<Position> {
+        let distance = from.manhattan(target);
+        self.level
+            .neighbors(from)
+            .map(|(_, next, _)| next)
+            .filter(|next| allowed(next))
+            .min_by_key(|next| next.manhattan(target))
+            .filter(|next| next.manhattan(target) < distance)
     }
 
-    pub fn handle_player_death(&self) {
-        self.ui.show_death_message();
+    pub fn meet_princess(&mut self, player: &mut Player) {
+        let previous = player.pos;
+        player.commit_move();
+        self.level.set_tile(&player.pos, TileType::Empty);
+        self.escort = Some(previous);
+        self.ui
+            .show_message("   🧝‍♀️ The princess joins you — lead her to safety ");
     }
 
-    pub fn handle_game_clear(&self) {
-        self.ui.show_game_clear_message();
+    pub fn is_escorting(&self) -> bool {
+        self.escort.is_some()
     }
 
-    pub fn render(&mut self, player: &Player) {
-        self.ui.render(&self.level, player);
+    pub fn join_companion(&mut self, player: &mut Player, pos: &Position) {
+        // The companion stays where it stood until the player moves on
+        player.cancel_move();
+        self.level.set_tile(pos, TileType::Empty);
+        self.companion = Some(*pos);
+        self.ui
+            .show_message("   🐕 A companion joins you — it will guard your back ");
     }
-}
 
-#[test]
-fn test_wall_collisions() {
-    let game = Game::new();
+    pub fn follow_player(&mut self, previous: Position) {
+        // The followers walk in single file, the companion first
+        let mut trail = previous;
+        for follower in [&mut self.companion, &mut self.escort]
+            .into_iter()
+            .flatten()
+        {
+            trail = std::mem::replace(follower, trail);
+        }
+    }
 
-    let mut wall_pos = None;
+    pub fn reach_goal(&mut self, player: &mut Player) -> GoalOutcome {
+        if self.escort.is_none() && self.has_any_tile(&[TileType::Princess]) {
+            player.cancel_move();
+            self.ui
+                .show_message("   You can't leave without the princess ");
+            return GoalOutcome::Blocked;
+        }
 
-    for row in 0..game.level.map_size.0 as usize {
-        for col in 0..game.level.map_size.1 as usize {
-            if game.level.map[row][col] == TileType::Wall {
-                wall_pos = Some(Position {
-                    row: row as i16,
-                    col: col as i16,
-                });
-                break;
+        if !player.inventory_contains_all(&self.level.header.required_items) {
+            player.cancel_move();
+            self.ui
+                .show_message("   The gate won't open — you're missing something ");
+            return GoalOutcome::Blocked;
+        }
+
+        self.count_move();
+        if let Some(goal) = player.get_pending_move() {
+            self.visited.insert(goal);
+        }
+        self.complete_level();
+
+        if self.escort.is_some() {
+            player.commit_move();
+            self.clear_game()
+        } else if self.advance_level() {
+            player.reset_position(self.get_player_start());
+            self.level_inventory = player.inventory.clone();
+            GoalOutcome::NextLevel
+        } else {
+            self.clear_game()
+        }
+    }
+
+    pub fn update_oni(&mut self, player: &mut Player) {
+        if self.peaceful {
+            return;
+        }
+
+        let target = player.pos;
+
+        let mut oni = std::mem::take(&mut self.level.oni);
+        let mut caught = None;
+
+        for pos in &mut oni {
+            if pos.manhattan(&target) > ONI_SIGHT_RADIUS {
+                continue;
+            }
+
+            // Step along whichever direction closes the distance
+            let step = self.step_toward(pos, &target, |next| {
+                *next == target || self.check_collision(next) == CollisionType::None
+            });
+
+            match step {
+                Some(next) if next == target => caught = Some(*pos),
+                Some(next) => *pos = next,
+                None => {}
             }
         }
-        if wall_pos.is_some() {
-            break;
+
+        self.level.oni = oni;
+
+        if let Some(pos) = caught {
+            self.handle_oni(player, &pos);
         }
     }
 
-    if let Some(wall_pos) = wall_pos {
-        match game.check_collision(&wall_pos) {
-            CollisionType::Blocking(BlockingType::Wall) => {
-                // This is the expected behavior
+    pub fn tick(&mut self, player: &mut Player) {
+        self.clock += 1;
+
+        // Conveyors carry the player before anything else moves
+        self.update_conveyors(player);
+
+        if self.clock.is_multiple_of(self.enemy_move_ticks()) {
+            self.update_enemies(player);
+        }
+        self.update_respawns(player);
+        if self.clock.is_multiple_of(ONI_MOVE_TICKS) {
+            self.update_oni(player);
+        }
+        if self.clock.is_multiple_of(PLATFORM_MOVE_TICKS) {
+            self.update_platforms(player);
+        }
+
+        // Spikes rising under the player are as deadly as walking into them
+        if self.spikes_raised()
+            && !self.invincible
+            && self.level.get_tile(&player.pos) == Some(TileType::Spikes)
+        {
+            self.ui.show_message("   The spikes spring up beneath you ");
+            self.handle_player_death(player);
+        }
+
+        // A platform or a death may have carried the player on or off a plate
+        self.update_gates(player);
+        self.settle_switches(player);
+        self.update_light(player);
+    }
+
+    pub fn wait(&mut self, player: &mut Player) {
+        // Pass time until the guards take their next step
+        loop {
+            self.tick(player);
+            if self.clock.is_multiple_of(self.enemy_move_ticks()) {
+                break;
             }
-            other => panic!(
-                "Wall should return Blocking(Wall) collision, got {:?}",
-                other
-            ),
         }
-    } else {
-        panic!("Couldn't find a wall in the level map");
     }
-}
 
-#[test]
-fn test_enemy_collisions() {
-    let game = Game::new();
+    pub fn update_gates(&mut self, player: &Player) {
+        // The player, their followers, and the guards all weigh a plate down
+        let weights: Vec<Position> = std::iter::once(player.pos)
+            .chain(self.escort)
+            .chain(self.companion)
+            .chain(self.level.enemies.iter().map(|enemy| enemy.pos))
+            .collect();
+        let weighted: Vec<u8> = weights
+            .iter()
+            .filter_map(|pos| match self.level.get_tile(pos) {
+                Some(TileType::Pressure(id)) => Some(id),
+                _ => None,
+            })
+            .collect();
 
-    for enemy_pos in &game.level.enemies {
-        match game.check_collision(enemy_pos) {
-            CollisionType::Interactive(InteractiveType::Enemy) => {
-                // This is the expected behavior
+        for row in 0..self.level.map_size.0 as i16 {
+            for col in 0..self.level.map_size.1 as i16 {
+                let pos = Position { row, col };
+                // A gate never closes on someone standing in it
+                let occupied = weights.contains(&pos);
+
+                match self.level.get_tile(&pos) {
+                    Some(TileType::Gate(id)) if weighted.contains(&id) => {
+                        self.level.set_tile(&pos, TileType::GateOpen(id));
+                    }
+                    Some(TileType::GateOpen(id)) if !weighted.contains(&id) && !occupied => {
+                        self.level.set_tile(&pos, TileType::Gate(id));
+                    }
+                    _ => {}
+                }
             }
-            other => panic!(
-                "Enemy should return Interactive(Enemy) collision, got {:?}",
-                other
-            ),
         }
     }
-}
 
-#[test]
-fn test_out_of_bounds_collisions() {
-    let game = Game::new();
+    pub fn update_conveyors(&mut self, player: &mut Player) {
+        let Some(TileType::Conveyor(dir)) = self.level.get_tile(&player.pos) else {
+            return;
+        };
+        let next = player.pos.offset(dir);
+        if self.check_collision_from(&player.pos, &next) != CollisionType::None {
+            return;
+        }
 
-    let test_positions = [
-        Position { row: -1, col: 0 },
-        Position { row: 0, col: -1 },
-        Position {
-            row: game.level.map_size.0 as i16,
-            col: 0,
-        },
-        Position {
-            row: 0,
-            col: game.level.map_size.1 as i16,
-        },
-    ];
+        let previous = player.pos;
+        player.reset_position(next);
+        self.follow_player(previous);
+        self.reach_checkpoint(&player.pos);
+        self.read_sign(&player.pos);
+        self.flip_switch(player);
+        self.after_step(player, &previous);
+    }
 
-    for pos in &test_positions {
-        match game.check_collision(pos) {
-            CollisionType::Blocking(_) => {
-                // This is the expected behavior
+    pub fn update_platforms(&mut self, player: &mut Player) {
+        for platform in &mut self.level.platforms {
+            let carrying = platform.pos == player.pos;
+            platform.advance();
+
+            if carrying {
+                player.pos = platform.pos;
             }
-            other => panic!(
-                "Out of bounds position should return Blocking collision, got {:?}",
-                other
-            ),
         }
     }
-}
 
-#[test]
-fn test_item_collisions() {
-    let mut map = vec![vec![TileType::Empty; 5]; 5];
-    map[2][2] = TileType::Key;
+    pub fn dash(&mut self, player: &mut Player, dir: Direction) -> MoveResult {
+        let result = self.try_move(player, dir);
+        if result != MoveResult::Moved {
+            return result;
+        }
 
-    let level = Level {
-        map,
-        enemies: vec![],
-        player_start: Position { row: 0, col: 0 },
-        map_size: (5, 5),
-    };
+        // The second step only goes ahead onto open ground
+        let next = player.pos.offset(dir);
+        if self.check_collision_from(&player.pos, &next) != CollisionType::None {
+            return result;
+        }
+        self.try_move(player, dir)
+    }
 
-    let mut game = Game::new();
-    let _ = std::mem::replace(&mut game.level, level);
+    pub fn count_move(&mut self) {
+        self.count_moves(1);
+    }
 
-    let key_pos = Position { row: 2, col: 2 };
+    pub fn count_moves(&mut self, cost: u32) {
+        self.level_moves += cost;
+        self.run_moves += cost;
+        if let Some(remaining) = &mut self.moves_remaining {
+            *remaining = remaining.saturating_sub(cost);
+        }
+    }
 
-    match game.check_collision(&key_pos) {
-        CollisionType::Interactive(InteractiveType::Item(ItemType::Key)) => {
-            // This is the expected behavior
+    fn move_budget(&self) -> Option<u32> {
+        self.level
+            .header
+            .move_limit
+            .map(|limit| self.difficulty.move_budget(limit))
+    }
+
+    pub fn run_out_of_moves(&mut self, player: &mut Player) -> bool {
+        if self.moves_remaining != Some(0) || !self.restart_level(player) {
+            return false;
         }
-        other => panic!(
-            "Key position should return Interactive(Item(Key)) collision, got {:?}",
-            other
-        ),
+
+        self.ui.beep();
+        self.ui
+            .show_message("   ⌛ Out of moves! The level starts over ");
+        true
     }
-}
 
-#[test]
-fn test_goal_collisions() {
-    if let Some(level) = Level::load(1) {
-        let mut goal_pos = None;
+    pub fn complete_level(&mut self) -> bool {
+        let time = self.level_started.elapsed();
+        self.ui.beep();
 
-        for row in 0..level.map_size.0 as usize {
-            for col in 0..level.map_size.1 as usize {
-                if level.map[row][col] == TileType::Goal {
-                    goal_pos = Some(Position {
-                        row: row as i16,
-                        col: col as i16,
-                    });
-                    break;
-                }
-            }
-            if goal_pos.is_some() {
-                break;
+        // A custom map would overwrite the record of the numbered level it stands in for
+        let is_best = self.single_level.is_none()
+            && !self.demo
+            && self
+                .leaderboard
+                .record(self.current_level, time, self.level_moves);
+
+        if is_best {
+            self.ui.show_message(&format!(
+                "   🏆 New best time: {} 🏆",
+                Leaderboard::format_time(time)
+            ));
+            if self.leaderboard.save().is_err() {
+                self.ui.show_message("   Couldn't save the leaderboard ");
             }
         }
 
-        if let Some(goal_pos) = goal_pos {
-            let mut game = Game::new();
-            let _ = std::mem::replace(&mut game.level, level);
+        self.ui.show_message(&format!(
+            "   Level {} cleared, {}% explored ",
+            self.current_level,
+            self.explored_percent()
+        ));
 
-            match game.check_collision(&goal_pos) {
-                CollisionType::Goal => {
-                    // This is the expected behavior
+        self.emit(GameEvent::LevelCleared {
+            level: self.current_level,
+            time,
+            moves: self.level_moves,
+        });
+
+        is_best
+    }
+
+    pub fn explored_percent(&self) -> u32 {
+        let mut open = 0;
+        let mut explored = 0;
+
+        for row in 0..self.level.map_size.0 as i16 {
+            for col in 0..self.level.map_size.1 as i16 {
+                let pos = Position { row, col };
+                if !matches!(self.check_collision(&pos), CollisionType::Blocking(_)) {
+                    open += 1;
+                    if self.visited.contains(&pos) {
+                        explored += 1;
+                    }
                 }
-                other => panic!(
-                    "Goal position should return Goal collision, got {:?}",
-                    other
-                ),
             }
-        } else {
-            panic!("Couldn't find a goal in level 1");
         }
-    } else {
-        panic!("Couldn't load level 1");
+
+        if open == 0 {
+            return 0;
+        }
+        explored * 100 / open
     }
-}
 
-#[test]
-fn test_empty_space_collisions() {
-    let game = Game::new();
-    let player = game.init_player();
+    pub fn max_levels(&self) -> usize {
+        self.max_levels
+    }
 
-    let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+    pub fn best_times(&self) -> Vec<Option<Duration>> {
+        (1..=self.max_levels)
+            .map(|level| self.leaderboard.best(level).map(|record| record.time))
+            .collect()
+    }
 
-    for (dy, dx) in &directions {
-        let test_pos = Position {
-            row: player.pos.row + dy,
-            col: player.pos.col + dx,
-        };
+    pub fn reset(&mut self) {
+        self.level_inventory = self.starting_inventory.clone();
+        self.deaths = 0;
+        self.kills = 0;
+        self.run_moves = 0;
+        self.items_collected = 0;
+        self.run_started = Instant::now();
+        self.run_logged = false;
+        self.boss_health = BOSS_HEALTH;
+        self.clock = 0;
+        self.enemy_tick = 0;
+        self.last_bump = None;
+        self.quit_pending = false;
+        self.skip_pending = false;
+        self.ui.set_prompt(None);
+        self.ui.close_overlay();
+        self.load_level(1);
+    }
 
-        if let Some(tile) = game.level.get_tile(&test_pos) {
-            if tile == TileType::Empty {
-                match game.check_collision(&test_pos) {
-                    CollisionType::None => {
-                        // This is the expected behavior
-                        return;
-                    }
-                    other => panic!("Empty space should return None collision, got {:?}", other),
-                }
-            }
+    pub fn start_new_game_plus(&mut self, carried: Vec<ItemType>) {
+        self.new_game_plus = true;
+        self.starting_inventory = carried;
+        self.reset();
+        self.ui
+            .show_message("   New Game+ — the guards are quicker this time ");
+    }
+
+    pub fn enemy_move_ticks(&self) -> u64 {
+        if self.new_game_plus {
+            NEW_GAME_PLUS_ENEMY_MOVE_TICKS
+        } else {
+            ENEMY_MOVE_TICKS
         }
     }
 
-    println!("Couldn't find an empty space near player start, skipping test");
+    pub fn clock(&self) -> u64 {
+        self.clock
+    }
+
+    pub fn tick_interval(&self) -> Duration {
+        tick_duration(self.speed)
+    }
+
+    pub fn practice_restart(&mut self, player: &mut Player) {
+        // Restarting doesn't hand out fresh hints
+        let hints_remaining = self.hints_remaining;
+        if self.restart_level(player) {
+            self.hints_remaining = hints_remaining;
+            self.ui.set_hints_remaining(hints_remaining);
+            self.ui.show_message("   Practice restart ");
+        }
+    }
+
+    fn restart_level(&mut self, player: &mut Player) -> bool {
+        if !self.load_level(self.current_level) {
+            return false;
+        }
+
+        self.boss_health = BOSS_HEALTH;
+        *player = self.init_player();
+        true
+    }
+
+    pub fn advance_level(&mut self) -> bool {
+        self.load_level(self.current_level + 1)
+    }
+
+    pub fn load_level(&mut self, level_number: usize) -> bool {
+        if level_number <= self.max_levels {
+            let new_level = match &self.single_level {
+                Some(level) => Ok(level.clone()),
+                None => Level::read(Level::path(&self.maps_dir, level_number)),
+            };
+
+            if let Ok(new_level) = new_level {
+                // A level that can never be finished would leave the player stuck, so skip it
+                if let Err(error) = new_level.check_exit() {
+                    self.ui
+                        .show_message(&format!("   Skipped level {}: {} ", level_number, error));
+                    return self.load_level(level_number + 1);
+                }
+
+                self.current_level = level_number;
+                self.level = new_level;
+                self.apply_difficulty();
+                self.level_started = Instant::now();
+                self.level_moves = 0;
+                self.moves_remaining = self.move_budget();
+                self.visited = HashSet::from([self.level.player_start]);
+                self.checkpoint = None;
+                self.escort = None;
+                self.companion = None;
+                self.light_ticks = 0;
+                self.stunned_enemies.clear();
+                self.respawns.clear();
+                self.footprints.clear();
+                self.flipped_switches.clear();
+                self.switch_tiles.clear();
+                self.hints_remaining = HINTS_PER_LEVEL;
+                self.ui.set_hints_remaining(self.hints_remaining);
+                self.emit(GameEvent::LevelStarted(level_number));
+                true
+            } else {
+                if let Err(error) = new_level {
+                    self.ui.show_message(&format!(
+                        "   Couldn't load level {}: {} ",
+                        level_number, error
+                    ));
+                }
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    // Hard mode adds a guard next to every other one, easy mode removes every other guard
+    fn apply_difficulty(&mut self) {
+        // Peaceful mode has no guards at all
+        if self.peaceful {
+            self.level.enemies.clear();
+            return;
+        }
+
+        match self.difficulty {
+            Difficulty::Normal => {}
+            Difficulty::Easy => {
+                let mut index = 0;
+                self.level.enemies.retain(|_| {
+                    index += 1;
+                    index % 2 == 1
+                });
+            }
+            Difficulty::Hard => {
+                let originals = self.level.enemies.clone();
+
+                for enemy in originals.iter().step_by(2) {
+                    if let Some(spawn) = self.find_spawn_near(&enemy.pos) {
+                        self.level
+                            .enemies
+                            .push(Enemy::patrolling(spawn, enemy.patrol));
+                    }
+                }
+            }
+        }
+    }
+
+    fn find_spawn_near(&self, origin: &Position) -> Option<Position> {
+        for distance in 1..=2 {
+            for dir in Direction::ALL {
+                let pos = origin.neighbor(dir, distance);
+
+                if pos != self.level.player_start
+                    && self.check_collision(&pos) == CollisionType::None
+                {
+                    return Some(pos);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn get_player_start(&self) -> Position {
+        self.level.player_start
+    }
+
+    pub fn reach_checkpoint(&mut self, pos: &Position) -> bool {
+        if self.level.get_tile(pos) != Some(TileType::Checkpoint) || self.checkpoint == Some(*pos) {
+            return false;
+        }
+
+        self.checkpoint = Some(*pos);
+        self.ui.show_message("   🚩 Checkpoint reached ");
+        true
+    }
+
+    pub fn read_sign(&mut self, pos: &Position) {
+        let Some(TileType::Sign(id)) = self.level.get_tile(pos) else {
+            return;
+        };
+
+        let message = match self.level.header.signs.get(id as usize) {
+            Some(text) => format!("   🪧 {} ", text),
+            None => "   🪧 The sign is too weathered to read ".to_string(),
+        };
+        self.ui.show_message(&message);
+    }
+
+    pub fn flip_switch(&mut self, player: &Player) {
+        let Some(TileType::Switch(id)) = self.level.get_tile(&player.pos) else {
+            return;
+        };
+        let Some(tiles) = self.level.header.switches.get(id as usize).cloned() else {
+            self.ui.show_message("   The switch is stuck ");
+            return;
+        };
+
+        // Remember how each tile started out, the first time its switch is flipped
+        for pos in tiles {
+            if let Some(tile @ (TileType::Wall | TileType::Empty)) = self.level.get_tile(&pos) {
+                self.switch_tiles.entry(pos).or_insert(tile);
+            }
+        }
+
+        if !self.flipped_switches.remove(&id) {
+            self.flipped_switches.insert(id);
+        }
+        self.settle_switches(player);
+        self.ui
+            .show_message("   Click! Somewhere, stone grinds against stone ");
+    }
+
+    pub fn settle_switches(&mut self, player: &Player) {
+        let occupied: Vec<Position> = std::iter::once(player.pos)
+            .chain(self.escort)
+            .chain(self.companion)
+            .chain(self.level.enemies.iter().map(|enemy| enemy.pos))
+            .collect();
+
+        let mut changes = Vec::new();
+        for (id, tiles) in self.level.header.switches.iter().enumerate() {
+            let flipped = self.flipped_switches.contains(&(id as u8));
+            for pos in tiles {
+                let Some(&original) = self.switch_tiles.get(pos) else {
+                    continue;
+                };
+                let wanted = match (original, flipped) {
+                    (TileType::Wall, true) => TileType::Empty,
+                    (TileType::Empty, true) => TileType::Wall,
+                    (tile, _) => tile,
+                };
+                if !(wanted == TileType::Wall && occupied.contains(pos)) {
+                    changes.push((*pos, wanted));
+                }
+            }
+        }
+
+        for (pos, tile) in changes {
+            self.level.set_tile(&pos, tile);
+        }
+    }
+
+    pub fn is_switch_flipped(&self, id: u8) -> bool {
+        self.flipped_switches.contains(&id)
+    }
+
+    pub fn respawn_position(&self) -> Position {
+        self.checkpoint.unwrap_or(self.level.player_start)
+    }
+
+    pub fn handle_player_death(&mut self, player: &mut Player) {
+        self.deaths += 1;
+        self.emit(GameEvent::PlayerDied);
+        self.ui.show_death_message();
+        self.ui.beep();
+        self.ui.flash();
+        player.reset_position(self.respawn_position());
+        // The followers gather around the player again
+        for follower in [&mut self.companion, &mut self.escort]
+            .into_iter()
+            .flatten()
+        {
+            *follower = player.pos;
+        }
+    }
+
+    pub fn handle_game_clear(&mut self) -> io::Result<()> {
+        self.ui.show_victory_screen(&self.run_summary())
+    }
+
+    pub fn handle_give_up(&mut self) -> io::Result<()> {
+        self.ui.show_defeat_screen(&self.run_summary())
+    }
+
+    pub fn request_quit(&mut self) -> bool {
+        if !self.confirm_quit {
+            return true;
+        }
+
+        self.quit_pending = true;
+        self.ui.set_prompt(Some(" Quit? (y/n)"));
+        false
+    }
+
+    pub fn is_quit_pending(&self) -> bool {
+        self.quit_pending
+    }
+
+    pub fn answer_quit(&mut self, confirmed: bool) -> bool {
+        self.quit_pending = false;
+        self.ui.set_prompt(None);
+        confirmed
+    }
+
+    pub fn request_skip(&mut self) -> bool {
+        if !self.allow_skip {
+            return false;
+        }
+
+        self.skip_pending = true;
+        self.ui.set_prompt(Some(" Skip this level? (y/n)"));
+        true
+    }
+
+    pub fn is_skip_pending(&self) -> bool {
+        self.skip_pending
+    }
+
+    pub fn answer_skip(&mut self, confirmed: bool, player: &mut Player) -> Option<GoalOutcome> {
+        self.skip_pending = false;
+        self.ui.set_prompt(None);
+        confirmed.then(|| self.skip_level(player))
+    }
+
+    pub fn skip_level(&mut self, player: &mut Player) -> GoalOutcome {
+        if self.advance_level() {
+            player.reset_position(self.get_player_start());
+            self.level_inventory = player.inventory.clone();
+            self.ui.show_message("   Level skipped ");
+            GoalOutcome::NextLevel
+        } else {
+            self.clear_game()
+        }
+    }
+
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.difficulty = settings.difficulty;
+        self.ui.apply_settings(settings);
+    }
+
+    pub fn emit(&mut self, event: GameEvent) {
+        // The demo bot's runs are no achievement of the player's
+        if self.demo {
+            return;
+        }
+        let unlocked = self.achievements.observe(event);
+
+        for achievement in &unlocked {
+            self.ui.show_message(&format!(
+                "   🏅 Achievement unlocked: {} 🏅",
+                achievement.name()
+            ));
+        }
+
+        if !unlocked.is_empty() && self.achievements.save().is_err() {
+            self.ui.show_message("   Couldn't save the achievements ");
+        }
+    }
+
+    pub fn show_message(&mut self, message: &str) {
+        self.ui.show_message(message);
+    }
+
+    pub fn toggle_photo_mode(&mut self, player: &Player) {
+        self.ui.toggle_photo_mode(player);
+        self.ui.set_prompt(if self.ui.is_photo_mode() {
+            Some(" Photo mode — move: Pan | r: Back to you | p: Resume")
+        } else {
+            None
+        });
+    }
+
+    pub fn is_photo_mode(&self) -> bool {
+        self.ui.is_photo_mode()
+    }
+
+    pub fn pan_camera(&mut self, dir: Direction) {
+        self.ui.pan_camera(dir, &self.level);
+    }
+
+    pub fn recenter_camera(&mut self, player: &Player) {
+        self.ui.recenter_camera(player);
+    }
+
+    pub fn describe_surroundings(&mut self, player: &Player) {
+        let description = self.ui.describe(&self.level, player);
+        self.ui.show_message(&format!("   {}", description));
+    }
+
+    pub fn dismiss_messages(&mut self) {
+        self.ui.dismiss_messages();
+    }
+
+    pub fn set_perf_stats(&mut self, perf: PerfStats) {
+        self.ui.set_perf_stats(perf);
+    }
+
+    pub fn open_overlay(&mut self, overlay: Overlay) {
+        self.ui.open_overlay(overlay);
+    }
+
+    pub fn close_overlay(&mut self) -> bool {
+        self.ui.close_overlay()
+    }
+
+    pub fn render(&mut self, player: &Player) -> io::Result<()> {
+        let best = self
+            .leaderboard
+            .best(self.current_level)
+            .filter(|_| self.single_level.is_none())
+            .map(|record| record.time);
+        self.ui.set_level_times(self.level_started.elapsed(), best);
+        self.ui.set_escort(self.escort);
+        self.ui.set_companion(self.companion);
+        self.ui
+            .set_footprints(self.footprints.iter().copied().collect());
+        self.ui.set_spikes_raised(self.spikes_raised());
+        self.ui.set_moves_remaining(self.moves_remaining);
+        self.ui.set_usable_items(
+            self.available_interactions(player)
+                .into_iter()
+                .filter(|interaction| interaction.available)
+                .filter_map(|interaction| Some((interaction.dir, interaction.item?)))
+                .collect(),
+        );
+        if self.show_danger_zone {
+            self.ui.set_danger_zone(self.danger_zone());
+        }
+        if self.fog {
+            self.ui.set_fog(Some(self.revealed_tiles(player)));
+        }
+        self.ui.render(&self.level, player)
+    }
+}
+
+#[test]
+fn test_wall_collisions() {
+    let game = Game::with_config(&Config::scratch());
+
+    let mut wall_pos = None;
+
+    for row in 0..game.level.map_size.0 as usize {
+        for col in 0..game.level.map_size.1 as usize {
+            if game.level.map[row][col] == TileType::Wall {
+                wall_pos = Some(Position {
+                    row: row as i16,
+                    col: col as i16,
+                });
+                break;
+            }
+        }
+        if wall_pos.is_some() {
+            break;
+        }
+    }
+
+    if let Some(wall_pos) = wall_pos {
+        match game.check_collision(&wall_pos) {
+            CollisionType::Blocking(BlockingType::Wall) => {
+                // This is the expected behavior
+            }
+            other => panic!(
+                "Wall should return Blocking(Wall) collision, got {:?}",
+                other
+            ),
+        }
+    } else {
+        panic!("Couldn't find a wall in the level map");
+    }
+}
+
+#[test]
+fn test_enemy_collisions() {
+    let game = Game::with_config(&Config::scratch());
+
+    for enemy in &game.level.enemies {
+        match game.check_collision(&enemy.pos) {
+            CollisionType::Interactive(InteractiveType::Enemy) => {
+                // This is the expected behavior
+            }
+            other => panic!(
+                "Enemy should return Interactive(Enemy) collision, got {:?}",
+                other
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_out_of_bounds_collisions() {
+    let game = Game::with_config(&Config::scratch());
+
+    let test_positions = [
+        Position { row: -1, col: 0 },
+        Position { row: 0, col: -1 },
+        Position {
+            row: game.level.map_size.0 as i16,
+            col: 0,
+        },
+        Position {
+            row: 0,
+            col: game.level.map_size.1 as i16,
+        },
+    ];
+
+    for pos in &test_positions {
+        match game.check_collision(pos) {
+            CollisionType::OutOfBounds => {
+                // This is the expected behavior
+            }
+            other => panic!(
+                "Out of bounds position should return OutOfBounds collision, got {:?}",
+                other
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_item_collisions() {
+    let mut map = vec![vec![TileType::Empty; 5]; 5];
+    map[2][2] = TileType::Key;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (5, 5),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+
+    let key_pos = Position { row: 2, col: 2 };
+
+    match game.check_collision(&key_pos) {
+        CollisionType::Interactive(InteractiveType::Item(ItemType::Key)) => {
+            // This is the expected behavior
+        }
+        other => panic!(
+            "Key position should return Interactive(Item(Key)) collision, got {:?}",
+            other
+        ),
+    }
+}
+
+#[test]
+fn test_goal_collisions() {
+    if let Some(level) = Level::load(1) {
+        let mut goal_pos = None;
+
+        for row in 0..level.map_size.0 as usize {
+            for col in 0..level.map_size.1 as usize {
+                if level.map[row][col] == TileType::Goal {
+                    goal_pos = Some(Position {
+                        row: row as i16,
+                        col: col as i16,
+                    });
+                    break;
+                }
+            }
+            if goal_pos.is_some() {
+                break;
+            }
+        }
+
+        if let Some(goal_pos) = goal_pos {
+            let mut game = Game::with_config(&Config::scratch());
+            let _ = std::mem::replace(&mut game.level, level);
+
+            match game.check_collision(&goal_pos) {
+                CollisionType::Goal => {
+                    // This is the expected behavior
+                }
+                other => panic!(
+                    "Goal position should return Goal collision, got {:?}",
+                    other
+                ),
+            }
+        } else {
+            panic!("Couldn't find a goal in level 1");
+        }
+    } else {
+        panic!("Couldn't load level 1");
+    }
+}
+
+#[test]
+fn test_empty_space_collisions() {
+    let game = Game::with_config(&Config::scratch());
+    let player = game.init_player();
+
+    let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+    for (dy, dx) in &directions {
+        let test_pos = Position {
+            row: player.pos.row + dy,
+            col: player.pos.col + dx,
+        };
+
+        if let Some(tile) = game.level.get_tile(&test_pos) {
+            if tile == TileType::Empty {
+                match game.check_collision(&test_pos) {
+                    CollisionType::None => {
+                        // This is the expected behavior
+                        return;
+                    }
+                    other => panic!("Empty space should return None collision, got {:?}", other),
+                }
+            }
+        }
+    }
+
+    println!("Couldn't find an empty space near player start, skipping test");
+}
+
+#[test]
+fn test_wind_chime_stuns_nearby_enemies() {
+    let map = vec![vec![TileType::Empty; 15]; 15];
+    let near = Position { row: 3, col: 4 };
+    let far = Position { row: 10, col: 10 };
+
+    let level = Level {
+        map,
+        enemies: vec![Enemy::new(near), Enemy::new(far)],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 2, col: 2 },
+        map_size: (15, 15),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let mut player = game.init_player();
+
+    // Without the chime nothing happens
+    assert!(!game.use_wind_chime(&player));
+
+    player.add_item(ItemType::WindChime);
+    assert!(game.use_wind_chime(&player));
+
+    let mut far_moved = false;
+    for _ in 0..WIND_CHIME_STUN_TICKS - 1 {
+        game.update_enemies(&player);
+        assert!(game.level.has_enemy_at(&near));
+        far_moved |= !game.level.has_enemy_at(&far);
+    }
+
+    assert!(far_moved, "Enemies outside the radius should keep moving");
+    assert!(game.stunned_enemies.iter().any(|(pos, _)| *pos == near));
+}
+
+#[test]
+fn test_diagonal_corner_cut_rejection() {
+    let mut map = vec![vec![TileType::Empty; 5]; 5];
+    map[1][2] = TileType::Wall;
+    map[2][1] = TileType::Wall;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 2, col: 2 },
+        map_size: (5, 5),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let from = Position { row: 2, col: 2 };
+
+    // Squeezing between the two walls is not allowed
+    assert!(game.cuts_corner(&from, Direction::UpLeft));
+
+    // A single wall beside the diagonal does not block it
+    assert!(!game.cuts_corner(&from, Direction::UpRight));
+    assert!(!game.cuts_corner(&from, Direction::DownLeft));
+
+    // Cardinal moves never cut corners
+    assert!(!game.cuts_corner(&from, Direction::Up));
+}
+
+#[test]
+fn test_invincible_player_survives_enemy() {
+    let map = vec![vec![TileType::Empty; 5]; 5];
+    let enemy = Position { row: 2, col: 3 };
+
+    let level = Level {
+        map,
+        enemies: vec![Enemy::new(enemy)],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (5, 5),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    game.invincible = true;
+
+    let mut player = game.init_player();
+    player.reset_position(Position { row: 2, col: 2 });
+    player.move_right();
+    game.handle_interaction(&mut player);
+
+    // The player was not sent back to the start
+    assert_eq!(player.pos, enemy);
+    assert_eq!(player.get_pending_move(), None);
+}
+
+#[test]
+fn test_shortest_path_to_goal() {
+    let mut map = vec![vec![TileType::Empty; 5]; 5];
+    // A wall across the middle with a single gap on the right
+    for tile in map[2].iter_mut().take(4) {
+        *tile = TileType::Wall;
+    }
+    map[0][0] = TileType::Goal;
+    map[3][4] = TileType::Key;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 4, col: 0 },
+        map_size: (5, 5),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+
+    let path = game
+        .shortest_path_to_goal()
+        .expect("Goal should be reachable");
+    // Four steps right, four up, four left
+    assert_eq!(path.len(), 12);
+
+    let mut pos = game.level.player_start;
+    for dir in &path {
+        pos = pos.offset(*dir);
+    }
+    assert_eq!(pos, Position { row: 0, col: 0 });
+
+    // Closing the gap makes the goal unreachable
+    game.level
+        .set_tile(&Position { row: 2, col: 4 }, TileType::Wall);
+    assert_eq!(game.shortest_path_to_goal(), None);
+}
+
+#[test]
+fn test_bundled_levels_without_tool_puzzles_are_solvable() {
+    // These levels can be finished by walking alone
+    for level_num in [1, 2, 4] {
+        let mut game = Game::with_config(&Config::scratch());
+        let level = Level::load(level_num).expect("Failed to load level");
+        let _ = std::mem::replace(&mut game.level, level);
+
+        assert!(
+            game.shortest_path_to_goal().is_some(),
+            "Level {} should be solvable",
+            level_num
+        );
+    }
+}
+
+#[test]
+fn test_hint_matches_solver_first_step() {
+    let mut map = vec![vec![TileType::Empty; 4]; 4];
+    map[1][1] = TileType::Wall;
+    map[1][2] = TileType::Wall;
+    map[0][3] = TileType::Goal;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 3, col: 1 },
+        map_size: (4, 4),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let player = game.init_player();
+
+    let path = game.shortest_path_to_goal().unwrap();
+    assert_eq!(game.next_hint(&player.pos), path.first().copied());
+
+    // Hints run out after the per-level limit
+    for _ in 0..HINTS_PER_LEVEL {
+        assert_eq!(game.request_hint(&player), path.first().copied());
+    }
+    assert_eq!(game.request_hint(&player), None);
+}
+
+#[test]
+fn test_death_counter() {
+    let mut game = Game::with_config(&Config::scratch());
+    let mut player = game.init_player();
+    assert_eq!(game.deaths, 0);
+
+    for _ in 0..3 {
+        game.handle_player_death(&mut player);
+    }
+
+    assert_eq!(game.deaths, 3);
+    assert!(game
+        .ui
+        .compose_victory_screen(&game.run_summary())
+        .contains("3 deaths"));
+}
+
+#[test]
+fn test_large_boss_shares_health_across_its_tiles() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntp00tt\nts00tt\ntttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    player.add_item(ItemType::DragonSword);
+
+    // Hitting any part of the boss wears down the same health
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.boss_health, 2);
+    assert_eq!(player.pos, game.get_player_start());
+
+    game.try_move(&mut player, Direction::Down);
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.boss_health, 1);
+    assert_eq!(
+        game.level.get_tile(&Position { row: 2, col: 3 }),
+        Some(TileType::Boss)
+    );
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.boss_health, 0);
+    assert_eq!(game.kills, 1);
+    for (row, col) in [(1, 2), (1, 3), (2, 2), (2, 3)] {
+        assert_eq!(
+            game.level.get_tile(&Position { row, col }),
+            Some(TileType::Empty)
+        );
+    }
+}
+
+#[test]
+fn test_only_successful_encounters_count_as_kills() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, "ttttt\ntpeet\nttttt".parse().unwrap());
+    let mut player = game.init_player();
+
+    player.add_item(ItemType::Sword);
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.kills, 1);
+    assert_eq!(game.deaths, 0);
+
+    // The sword is spent, so the next guard wins
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.kills, 1);
+    assert_eq!(game.deaths, 1);
+}
+
+#[test]
+fn test_bump_messages_are_throttled() {
+    let mut game = Game::with_config(&Config::scratch());
+    let mut player = game.init_player();
+
+    player.move_up();
+    assert!(game.handle_bump(&mut player, BlockingType::Water));
+    assert_eq!(player.get_pending_move(), None);
+
+    // Repeated bumps into the same blocker stay quiet
+    player.move_up();
+    assert!(!game.handle_bump(&mut player, BlockingType::Water));
+    assert_eq!(player.get_pending_move(), None);
+
+    // A different blocker is reported right away
+    assert!(game.handle_bump(&mut player, BlockingType::Bamboo));
+}
+
+#[test]
+fn test_missing_item_messages() {
+    let mut map = vec![vec![TileType::Empty; 5]; 5];
+    map[1][2] = TileType::Door;
+    map[2][1] = TileType::Rock;
+    map[2][3] = TileType::WoodLog;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![Position { row: 3, col: 2 }],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (5, 5),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let mut player = game.init_player();
+    let center = Position { row: 2, col: 2 };
+
+    let cases = [
+        (Direction::Up, "you need a key"),
+        (Direction::Left, "you need a bomb"),
+        (Direction::Right, "you need an axe"),
+    ];
+
+    for (dir, expected) in cases {
+        player.reset_position(center);
+        player.move_in(dir);
+        game.handle_interaction(&mut player);
+
+        assert!(game.ui.last_message().unwrap().contains(expected));
+        assert_eq!(player.pos, center);
+        assert_eq!(player.get_pending_move(), None);
+    }
+
+    // The oni still kills, but now explains why
+    player.reset_position(center);
+    player.move_down();
+    game.handle_interaction(&mut player);
+
+    assert_eq!(game.deaths, 1);
+    assert_eq!(player.pos, game.get_player_start());
+}
+
+#[test]
+fn test_post_interaction_tiles_are_walkable() {
+    let walkable = [
+        TileType::Empty,
+        TileType::DoorOpen,
+        TileType::Tomb,
+        TileType::Canoe,
+        TileType::Alembic,
+        TileType::Link,
+    ];
+
+    let level = Level {
+        map: vec![walkable.to_vec()],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (1, walkable.len() as u8),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+
+    for (col, tile) in walkable.iter().enumerate() {
+        assert!(tile.is_walkable());
+        assert_eq!(
+            game.check_collision(&Position {
+                row: 0,
+                col: col as i16
+            }),
+            CollisionType::None,
+            "{:?} should be walkable",
+            tile
+        );
+    }
+}
+
+#[test]
+fn test_difficulty_scales_enemies() {
+    let enemy_count = |difficulty| {
+        let config = Config {
+            difficulty,
+            ..Config::scratch()
+        };
+        let mut game = Game::with_config(&config);
+        assert!(game.advance_level() && game.advance_level());
+
+        for enemy in &game.level.enemies {
+            assert_ne!(enemy.pos, game.level.player_start);
+            assert_eq!(game.level.get_tile(&enemy.pos), Some(TileType::Empty));
+        }
+        game.level.enemies.len()
+    };
+
+    let easy = enemy_count(Difficulty::Easy);
+    let normal = enemy_count(Difficulty::Normal);
+    let hard = enemy_count(Difficulty::Hard);
+
+    assert!(easy < normal);
+    assert!(normal < hard);
+}
+
+#[test]
+fn test_respawn_at_latest_checkpoint() {
+    let mut map = vec![vec![TileType::Empty; 5]; 1];
+    map[0][2] = TileType::Checkpoint;
+
+    let level = Level {
+        map,
+        enemies: vec![Enemy::new(Position { row: 0, col: 4 })],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (1, 5),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let mut player = game.init_player();
+
+    // Dying before any checkpoint goes back to the level start
+    player.reset_position(Position { row: 0, col: 1 });
+    game.handle_player_death(&mut player);
+    assert_eq!(player.pos, Position { row: 0, col: 0 });
+
+    // Plain tiles don't count as checkpoints
+    assert!(!game.reach_checkpoint(&Position { row: 0, col: 1 }));
+
+    let checkpoint = Position { row: 0, col: 2 };
+    player.reset_position(checkpoint);
+    assert!(game.reach_checkpoint(&checkpoint));
+    assert!(!game.reach_checkpoint(&checkpoint));
+
+    player.move_right();
+    player.commit_move();
+    player.move_right();
+    game.handle_interaction(&mut player);
+
+    assert_eq!(game.deaths, 2);
+    assert_eq!(player.pos, checkpoint);
+}
+
+#[test]
+fn test_princess_must_be_escorted_to_clear() {
+    let mut map = vec![vec![TileType::Empty; 4]; 1];
+    map[0][0] = TileType::Goal;
+    map[0][3] = TileType::Princess;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 1 },
+        map_size: (1, 4),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    game.leaderboard = Leaderboard::load(crate::classes::leaderboard::temp_path("escort"));
+    game.achievements = Achievements::load(crate::classes::leaderboard::temp_path(
+        "escort_achievements",
+    ));
+    let mut player = game.init_player();
+
+    // Leaving without the princess isn't allowed
+    player.move_left();
+    assert_eq!(game.reach_goal(&mut player), GoalOutcome::Blocked);
+    assert_eq!(player.pos, Position { row: 0, col: 1 });
+    assert!(!game.is_escorting());
+
+    player.move_right();
+    player.commit_move();
+    player.move_right();
+    assert_eq!(
+        game.check_collision(&player.get_pending_move().unwrap()),
+        CollisionType::Princess
+    );
+    game.meet_princess(&mut player);
+    assert!(game.is_escorting());
+    assert_eq!(game.escort, Some(Position { row: 0, col: 2 }));
+
+    // She follows one step behind
+    let previous = player.pos;
+    player.move_left();
+    player.commit_move();
+    game.follow_player(previous);
+    assert_eq!(game.escort, Some(Position { row: 0, col: 3 }));
+
+    // The level start now counts as a way out as well as the goal
+    assert_eq!(
+        game.check_collision(&Position { row: 0, col: 1 }),
+        CollisionType::Goal
+    );
+
+    player.reset_position(Position { row: 0, col: 1 });
+    player.move_left();
+    assert_eq!(game.reach_goal(&mut player), GoalOutcome::GameClear);
+}
+
+#[test]
+fn test_oni_chases_and_kills_without_wind_chime() {
+    let level = Level {
+        map: vec![vec![TileType::Empty; 8]; 1],
+        enemies: vec![],
+        oni: vec![Position { row: 0, col: 7 }],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (1, 8),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let mut player = game.init_player();
+
+    // Out of sight, the oni stays put
+    game.update_oni(&mut player);
+    assert_eq!(game.level.oni, vec![Position { row: 0, col: 7 }]);
+
+    player.reset_position(Position { row: 0, col: 4 });
+    game.update_oni(&mut player);
+    assert_eq!(game.level.oni, vec![Position { row: 0, col: 6 }]);
+    game.update_oni(&mut player);
+    assert_eq!(game.level.oni, vec![Position { row: 0, col: 5 }]);
+
+    // Catching the player kills them
+    game.update_oni(&mut player);
+    assert_eq!(game.deaths, 1);
+    assert_eq!(player.pos, game.get_player_start());
+
+    // With the wind chime, contact cleanses the oni instead
+    player.reset_position(Position { row: 0, col: 4 });
+    player.add_item(ItemType::WindChime);
+    game.update_oni(&mut player);
+    assert!(game.level.oni.is_empty());
+    assert_eq!(game.deaths, 1);
+    assert!(player.has_item(ItemType::DragonSword));
+}
+
+#[test]
+fn test_one_way_tiles_only_enter_along_arrow() {
+    let mut map = vec![vec![TileType::Empty; 3]; 1];
+    map[0][1] = TileType::OneWay(Direction::Right);
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (1, 3),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let one_way = Position { row: 0, col: 1 };
+
+    assert_eq!(game.check_collision(&one_way), CollisionType::None);
+    assert!(!game.enters_against_one_way(&one_way, Direction::Right));
+    assert!(game.enters_against_one_way(&one_way, Direction::Left));
+
+    // Coming from the blocked side cancels the move
+    let mut player = game.init_player();
+    player.reset_position(Position { row: 0, col: 2 });
+    player.move_left();
+    assert!(game.enters_against_one_way(&player.get_pending_move().unwrap(), player.facing));
+    game.handle_bump(&mut player, BlockingType::OneWay);
+    assert_eq!(player.pos, Position { row: 0, col: 2 });
+    assert_eq!(player.get_pending_move(), None);
+}
+
+#[test]
+fn test_directional_collision_matches_plain_collision() {
+    let game = Game::with_config(&Config::scratch());
+    let (rows, cols) = game.level.map_size;
+
+    for row in 0..rows as i16 {
+        for col in 0..cols as i16 {
+            let to = Position { row, col };
+
+            for dir in Direction::ALL {
+                let from = to.offset(dir.opposite());
+                assert_eq!(
+                    game.check_collision_from(&from, &to),
+                    game.check_collision(&to),
+                    "{:?} entered moving {:?}",
+                    to,
+                    dir
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_directional_collision_applies_movement_rules() {
+    let mut map = vec![vec![TileType::Empty; 3]; 2];
+    map[0][1] = TileType::Wall;
+    map[1][0] = TileType::Wall;
+    map[1][2] = TileType::OneWay(Direction::Down);
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (2, 3),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+
+    let corner = Position { row: 1, col: 1 };
+    assert_eq!(game.check_collision(&corner), CollisionType::None);
+    assert_eq!(
+        game.check_collision_from(&Position { row: 0, col: 0 }, &corner),
+        CollisionType::Blocking(BlockingType::Wall)
+    );
+
+    let one_way = Position { row: 1, col: 2 };
+    assert_eq!(
+        game.check_collision_from(&Position { row: 0, col: 2 }, &one_way),
+        CollisionType::None
+    );
+    assert_eq!(
+        game.check_collision_from(&corner, &one_way),
+        CollisionType::Blocking(BlockingType::OneWay)
+    );
+}
+
+#[test]
+fn test_quit_confirmation() {
+    let mut game = Game::with_config(&Config::scratch());
+    let player = game.init_player();
+
+    assert!(!game.request_quit());
+    assert!(game.is_quit_pending());
+    assert!(game
+        .ui
+        .compose_frame(&game.level, &player)
+        .contains("Quit? (y/n)"));
+
+    // Anything but yes resumes the game
+    assert!(!game.answer_quit(false));
+    assert!(!game.is_quit_pending());
+    assert!(!game
+        .ui
+        .compose_frame(&game.level, &player)
+        .contains("Quit?"));
+
+    assert!(!game.request_quit());
+    assert!(game.answer_quit(true));
+
+    let no_confirm = Config {
+        confirm_quit: false,
+        ..Config::scratch()
+    };
+    let mut game = Game::with_config(&no_confirm);
+    assert!(game.request_quit());
+    assert!(!game.is_quit_pending());
+}
+
+#[test]
+fn test_load_level_jumps_to_any_level() {
+    let mut game = Game::with_config(&Config::scratch());
+    assert_eq!(game.best_times().len(), game.max_levels());
+
+    assert!(game.load_level(4));
+    assert_eq!(game.current_level, 4);
+    assert_eq!(game.level.map, Level::load(4).unwrap().map);
+
+    assert!(!game.load_level(game.max_levels() + 1));
+    assert_eq!(game.current_level, 4);
+}
+
+#[test]
+fn test_try_move_resolves_pickups_walls_and_goals() {
+    let mut map = vec![vec![TileType::Empty; 4]; 2];
+    map[0][1] = TileType::Key;
+    map[0][2] = TileType::Goal;
+    map[1][1] = TileType::Wall;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (2, 4),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    game.leaderboard = Leaderboard::load(crate::classes::leaderboard::temp_path("try_move"));
+    game.achievements = Achievements::load(crate::classes::leaderboard::temp_path(
+        "try_move_achievements",
+    ));
+    let mut player = game.init_player();
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Up),
+        MoveResult::Blocked
+    );
+    assert_eq!(player.pos, Position { row: 0, col: 0 });
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Down),
+        MoveResult::Moved
+    );
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Blocked
+    );
+    assert_eq!(player.pos, Position { row: 1, col: 0 });
+
+    assert_eq!(game.try_move(&mut player, Direction::Up), MoveResult::Moved);
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Interacted
+    );
+    assert!(player.has_item(ItemType::Key));
+    assert_eq!(player.pos, Position { row: 0, col: 1 });
+    assert_eq!(game.level_moves, 3);
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::NextLevel
+    );
+    assert_eq!(game.current_level, 2);
+    assert_eq!(player.pos, game.get_player_start());
+}
+
+#[test]
+fn test_swimming_costs_stamina() {
+    use crate::classes::player::MAX_STAMINA;
+
+    let mut map = vec![vec![TileType::ShallowWater; 8]; 1];
+    map[0][0] = TileType::Empty;
+    map[0][1] = TileType::Empty;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 1 },
+        map_size: (1, 8),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let mut player = game.init_player();
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(player.stamina, MAX_STAMINA - 2);
+    assert_eq!(game.level_moves, 4);
+
+    // Back on land, stamina slowly returns
+    player.reset_position(Position { row: 0, col: 1 });
+    assert_eq!(
+        game.try_move(&mut player, Direction::Left),
+        MoveResult::Moved
+    );
+    assert_eq!(player.stamina, MAX_STAMINA - 1);
+
+    // Out of stamina, the water turns the player back
+    player.stamina = 0;
+    player.reset_position(Position { row: 0, col: 3 });
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Blocked
+    );
+    assert_eq!(player.pos, Position { row: 0, col: 3 });
+}
+
+#[test]
+fn test_axe_turns_log_by_water_into_canoe() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, "tttt\ntplw\ntstt\ntttt".parse().unwrap());
+    let mut player = game.init_player();
+    player.add_item(ItemType::Axe);
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Interacted
+    );
+    assert_eq!(
+        game.level.get_tile(&Position { row: 1, col: 3 }),
+        Some(TileType::Canoe)
+    );
+    assert_eq!(
+        game.level.get_tile(&Position { row: 1, col: 2 }),
+        Some(TileType::Empty)
+    );
+    assert!(!player.has_item(ItemType::Axe));
+    assert_eq!(player.pos, Position { row: 1, col: 1 });
+}
+
+#[test]
+fn test_hidden_trap_reveals_then_kills() {
+    let mut map = vec![vec![TileType::Empty; 3]; 1];
+    map[0][1] = TileType::HiddenTrap;
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (1, 3),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let mut player = game.init_player();
+    let trap = Position { row: 0, col: 1 };
+
+    // The first step springs the trap, but the player survives
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Interacted
+    );
+    assert_eq!(player.pos, trap);
+    assert_eq!(game.level.get_tile(&trap), Some(TileType::Trap));
+    assert_eq!(game.deaths, 0);
+
+    // Once revealed, it is deadly
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(
+        game.try_move(&mut player, Direction::Left),
+        MoveResult::Interacted
+    );
+    assert_eq!(game.deaths, 1);
+    assert_eq!(player.pos, game.get_player_start());
+
+    // With deadly traps, even the first step kills
+    let deadly = Config {
+        deadly_traps: true,
+        ..Config::scratch()
+    };
+    let mut game = Game::with_config(&deadly);
+    let mut map = vec![vec![TileType::Empty; 2]; 1];
+    map[0][1] = TileType::HiddenTrap;
+    game.level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (1, 2),
+        header: Default::default(),
+    };
+    let mut player = game.init_player();
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.deaths, 1);
+    assert_eq!(player.pos, game.get_player_start());
+}
+
+#[test]
+fn test_moving_platform_carries_player() {
+    use crate::classes::level::Platform;
+
+    let mut map = vec![vec![TileType::Empty; 4]; 1];
+    map[0][1] = TileType::MovingPlatform;
+    map[0][2] = TileType::MovingPlatform;
+    let start = Position { row: 0, col: 1 };
+    let end = Position { row: 0, col: 2 };
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![Platform::new(start, end)],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (1, 4),
+        header: Default::default(),
+    };
+
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level);
+    let mut player = game.init_player();
+
+    // The far end of the track is empty until the platform gets there
+    assert_eq!(
+        game.check_collision(&end),
+        CollisionType::Blocking(BlockingType::Track)
+    );
+
+    // The platform toggles between the ends of its track
+    game.update_platforms(&mut player);
+    assert_eq!(game.level.platforms[0].pos, end);
+    game.update_platforms(&mut player);
+    assert_eq!(game.level.platforms[0].pos, start);
+
+    // Riding it carries the player across
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    game.update_platforms(&mut player);
+    assert_eq!(player.pos, end);
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(player.pos, Position { row: 0, col: 3 });
+}
+
+#[test]
+fn test_enemies_move_every_few_ticks() {
+    let mut game = Game::with_config(&Config::scratch());
+    let mut player = game.init_player();
+
+    for _ in 0..ENEMY_MOVE_TICKS - 1 {
+        game.tick(&mut player);
+    }
+    assert_eq!(game.enemy_tick, 0);
+
+    game.tick(&mut player);
+    assert_eq!(game.enemy_tick, 1);
+
+    for _ in 0..ENEMY_MOVE_TICKS * 3 {
+        game.tick(&mut player);
+    }
+    assert_eq!(game.enemy_tick, 4);
+}
+
+#[test]
+fn test_levels_without_exit_are_skipped() {
+    let dir = std::env::temp_dir().join(format!("ghostblade_no_exit_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("level_1.txt"), "tttt\ntpgt\ntttt\n").unwrap();
+    std::fs::write(dir.join("level_2.txt"), "tttt\ntpst\ntttt\n").unwrap();
+    std::fs::write(dir.join("level_3.txt"), "ttttt\ntpsgt\nttttt\n").unwrap();
+
+    let config = Config {
+        maps_dir: dir.to_string_lossy().into_owned(),
+        ..Config::scratch()
+    };
+    let mut game = Game::with_config(&config);
+
+    assert!(game.load_level(2));
+    assert_eq!(game.current_level, 3);
+    assert_eq!(
+        game.ui.last_message(),
+        Some("   Skipped level 2: There is no goal or princess ")
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_peaceful_mode_has_no_guards() {
+    let peaceful = Config {
+        peaceful: true,
+        ..Config::scratch()
+    };
+    let mut game = Game::with_config(&peaceful);
+    assert!(game.level.enemies.is_empty());
+
+    game.load_level(2);
+    assert!(game.level.enemies.is_empty());
+
+    // Even guards placed by hand stay put
+    let guard = Position { row: 2, col: 2 };
+    game.level.enemies.push(Enemy::new(guard));
+    let player = game.init_player();
+    for _ in 0..20 {
+        game.update_enemies(&player);
+    }
+    assert_eq!(game.level.enemies, vec![Enemy::new(guard)]);
+}
+
+#[test]
+fn test_peaceful_mode_oni_and_boss_only_block() {
+    let peaceful = Config {
+        peaceful: true,
+        ..Config::scratch()
+    };
+    let mut game = Game::with_config(&peaceful);
+    let _ = std::mem::replace(&mut game.level, "ttttt\nt0pot\nttttt".parse().unwrap());
+    let mut player = game.init_player();
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Interacted
+    );
+    assert_eq!(
+        game.try_move(&mut player, Direction::Left),
+        MoveResult::Interacted
+    );
+    assert_eq!(player.pos, game.get_player_start());
+    assert_eq!(game.deaths, 0);
+
+    game.update_oni(&mut player);
+    assert_eq!(game.level.oni, vec![Position { row: 1, col: 3 }]);
+}
+
+#[test]
+fn test_practice_restart_reloads_without_counting_a_death() {
+    let mut game = Game::with_config(&Config::scratch());
+    let mut player = game.init_player();
+    let start_map = game.level.map.clone();
+
+    game.handle_player_death(&mut player);
+    player.add_item(ItemType::Key);
+    game.level
+        .set_tile(&Position { row: 1, col: 1 }, TileType::Empty);
+    game.count_move();
+    game.hints_remaining = 1;
+
+    game.practice_restart(&mut player);
+
+    assert_eq!(game.deaths, 1);
+    assert_eq!(game.hints_remaining, 1);
+    assert_eq!(game.level_moves, 0);
+    assert_eq!(game.current_level, 1);
+    assert_eq!(game.level.map, start_map);
+    assert_eq!(player.pos, game.get_player_start());
+    assert!(player.inventory.is_empty());
+}
+
+#[test]
+fn test_horizontal_patroller_bounces_between_walls() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntHssst\ntpssst\ntttttt".parse().unwrap(),
+    );
+    let player = game.init_player();
+
+    let mut columns = Vec::new();
+    for _ in 0..8 {
+        game.update_enemies(&player);
+        columns.push(game.level.enemies[0].pos.col);
+    }
+
+    assert_eq!(columns, vec![2, 3, 4, 3, 2, 1, 2, 3]);
+    assert!(game.level.enemies.iter().all(|enemy| enemy.pos.row == 1));
+}
+
+#[test]
+fn test_reaching_goal_within_move_limit() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# moves: 3\ntttttt\ntpssgt\ntttttt".parse().unwrap(),
+    );
+    game.moves_remaining = game.move_budget();
+    game.leaderboard = Leaderboard::load(crate::classes::leaderboard::temp_path("move_limit"));
+    game.achievements = Achievements::load(crate::classes::leaderboard::temp_path(
+        "move_limit_achievements",
+    ));
+    let mut player = game.init_player();
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(game.moves_remaining, Some(1));
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::NextLevel
+    );
+    assert_eq!(game.current_level, 2);
+}
+
+#[test]
+fn test_crossing_mud_spends_more_of_the_move_limit() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# moves: 10\ntttttt\ntpuWst\ntttttt".parse().unwrap(),
+    );
+    game.moves_remaining = game.move_budget();
+    let mut player = game.init_player();
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(game.moves_remaining, Some(7));
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(game.moves_remaining, Some(5));
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert_eq!(game.moves_remaining, Some(4));
+    assert_eq!(game.level_moves, 6);
+}
+
+#[test]
+fn test_running_out_of_moves_restarts_the_level() {
+    let mut game = Game::with_config(&Config::scratch());
+    let mut player = game.init_player();
+    game.moves_remaining = Some(2);
+
+    assert_eq!(game.try_move(&mut player, Direction::Up), MoveResult::Moved);
+    assert_eq!(game.moves_remaining, Some(1));
+    assert_eq!(game.try_move(&mut player, Direction::Up), MoveResult::Moved);
+
+    assert_eq!(player.pos, game.get_player_start());
+    assert_eq!(game.level_moves, 0);
+    assert_eq!(game.current_level, 1);
+    assert_eq!(game.deaths, 0);
+    // The first level has no move limit of its own
+    assert_eq!(game.moves_remaining, None);
+}
+
+#[test]
+fn test_spikes_are_only_deadly_while_raised() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, "ttttt\ntp*st\ntgttt".parse().unwrap());
+    let mut player = game.init_player();
+    let spikes = Position { row: 1, col: 2 };
+
+    assert!(!game.spikes_raised());
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Interacted
+    );
+    assert_eq!(player.pos, spikes);
+    assert_eq!(game.deaths, 0);
+
+    game.try_move(&mut player, Direction::Left);
+    game.clock = SPIKE_PHASE_TICKS;
+    assert!(game.spikes_raised());
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(player.pos, game.get_player_start());
+    assert_eq!(game.deaths, 1);
+
+    // Standing on the spikes as they rise is just as deadly
+    game.clock = 2 * SPIKE_PHASE_TICKS;
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(player.pos, spikes);
+    game.clock = 3 * SPIKE_PHASE_TICKS - 1;
+    game.tick(&mut player);
+    assert_eq!(player.pos, game.get_player_start());
+    assert_eq!(game.deaths, 2);
+}
+
+#[test]
+fn test_custom_map_is_a_single_level_game() {
+    let path = crate::classes::leaderboard::temp_path("custom_map");
+    std::fs::write(&path, "ttttt\ntpsgt\nttttt\n").unwrap();
+    let level = Level::load_path(&path).expect("Failed to load custom map");
+    std::fs::remove_file(&path).unwrap();
+
+    let mut game = Game::with_level(&Config::scratch(), level);
+    game.achievements = Achievements::load(crate::classes::leaderboard::temp_path(
+        "custom_map_achievements",
+    ));
+    let mut player = game.init_player();
+
+    assert_eq!(game.max_levels(), 1);
+    assert_eq!(player.pos, Position { row: 1, col: 1 });
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+
+    // Restarting reloads the custom map, not the first numbered level
+    game.practice_restart(&mut player);
+    assert_eq!(game.level.map_size, (3, 5));
+    assert_eq!(player.pos, Position { row: 1, col: 1 });
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::GameClear
+    );
+}
+
+#[test]
+fn test_explored_percent_counts_open_tiles_visited() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntpsstt\ntsstgt\ntttttt".parse().unwrap(),
+    );
+    game.visited = HashSet::from([game.level.player_start]);
+    let mut player = game.init_player();
+
+    // Six open tiles, counting the goal, and the player starts on one of them
+    assert_eq!(game.explored_percent(), 16);
+
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Down);
+    assert_eq!(game.explored_percent(), 50);
+
+    // Stepping on a tile twice doesn't count it twice
+    game.try_move(&mut player, Direction::Up);
+    assert_eq!(game.explored_percent(), 50);
+
+    // Bumping into a wall doesn't explore anything
+    game.try_move(&mut player, Direction::Up);
+    assert_eq!(game.explored_percent(), 50);
+}
+
+#[test]
+fn test_multi_use_key_opens_every_door() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# keys: multi\ntttttt\ntpddgt\ntttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    player.add_item(ItemType::Key);
+    player.add_item(ItemType::Axe);
+
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Right);
+    // The key stays where it was in the pack
+    assert_eq!(player.inventory, vec![ItemType::Key, ItemType::Axe]);
+
+    assert_eq!(
+        game.level.get_tile(&Position { row: 1, col: 2 }),
+        Some(TileType::DoorOpen)
+    );
+    assert_eq!(
+        game.level.get_tile(&Position { row: 1, col: 3 }),
+        Some(TileType::DoorOpen)
+    );
+    assert!(player.has_item(ItemType::Key));
+}
+
+#[test]
+fn test_single_use_key_opens_one_door() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, "tttttt\ntpddgt\ntttttt".parse().unwrap());
+    let mut player = game.init_player();
+    player.add_item(ItemType::Key);
+
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Right);
+
+    assert_eq!(
+        game.level.get_tile(&Position { row: 1, col: 2 }),
+        Some(TileType::DoorOpen)
+    );
+    assert_eq!(
+        game.level.get_tile(&Position { row: 1, col: 3 }),
+        Some(TileType::Door)
+    );
+    assert_eq!(player.pos, Position { row: 1, col: 2 });
+    assert!(!player.has_item(ItemType::Key));
+}
+
+#[test]
+fn test_closing_door_locks_behind_the_player() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# doors: closing\nttttt\ntpdst\ntgttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    player.add_item(ItemType::Key);
+    let door = Position { row: 1, col: 2 };
+
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.level.get_tile(&door), Some(TileType::DoorOpen));
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(player.pos, Position { row: 1, col: 3 });
+    assert_eq!(game.level.get_tile(&door), Some(TileType::Door));
+}
+
+#[test]
+fn test_skip_level_moves_on_after_confirmation() {
+    let mut game = Game::with_config(&Config::scratch());
+    let mut player = game.init_player();
+
+    // Normal players can't skip
+    assert!(!game.request_skip());
+    assert!(!game.is_skip_pending());
+
+    let mut game = Game::with_config(&Config {
+        allow_skip: true,
+        ..Config::scratch()
+    });
+    assert!(game.request_skip());
+    assert!(game
+        .ui
+        .compose_frame(&game.level, &player)
+        .contains("Skip this level? (y/n)"));
+    assert_eq!(game.answer_skip(false, &mut player), None);
+    assert_eq!(game.current_level, 1);
+
+    game.request_skip();
+    assert_eq!(
+        game.answer_skip(true, &mut player),
+        Some(GoalOutcome::NextLevel)
+    );
+    assert_eq!(game.current_level, 2);
+    assert_eq!(player.pos, game.get_player_start());
+
+    // There's nothing to skip to after the last level
+    game.load_level(game.max_levels());
+    assert_eq!(game.skip_level(&mut player), GoalOutcome::GameClear);
+}
+
+#[test]
+fn test_gate_opens_only_while_its_plate_is_weighted() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntpPsGt\ntsPsGt\ntttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    let first_gate = Position { row: 1, col: 4 };
+    let second_gate = Position { row: 2, col: 4 };
+
+    assert_eq!(game.level.get_tile(&first_gate), Some(TileType::Gate(0)));
+    assert_eq!(game.level.get_tile(&second_gate), Some(TileType::Gate(1)));
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(
+        game.level.get_tile(&first_gate),
+        Some(TileType::GateOpen(0))
+    );
+    assert_eq!(game.level.get_tile(&second_gate), Some(TileType::Gate(1)));
+
+    // Stepping from one plate to the other swaps which gate is open
+    game.try_move(&mut player, Direction::Down);
+    assert_eq!(game.level.get_tile(&first_gate), Some(TileType::Gate(0)));
+    assert_eq!(
+        game.level.get_tile(&second_gate),
+        Some(TileType::GateOpen(1))
+    );
+
+    game.try_move(&mut player, Direction::Left);
+    assert_eq!(game.level.get_tile(&second_gate), Some(TileType::Gate(1)));
+    assert_eq!(
+        game.check_collision(&second_gate),
+        CollisionType::Blocking(BlockingType::Gate)
+    );
+
+    // A guard standing on the plate holds the gate open too
+    game.level
+        .enemies
+        .push(Enemy::new(Position { row: 1, col: 2 }));
+    game.update_gates(&player);
+    assert_eq!(
+        game.level.get_tile(&first_gate),
+        Some(TileType::GateOpen(0))
+    );
+}
+
+#[test]
+fn test_reset_starts_the_run_over() {
+    let mut game = Game::with_config(&Config {
+        give: vec![ItemType::Axe],
+        ..Config::scratch()
+    });
+    game.leaderboard = Leaderboard::load(crate::classes::leaderboard::temp_path("reset"));
+    game.achievements =
+        Achievements::load(crate::classes::leaderboard::temp_path("reset_achievements"));
+    let mut player = game.init_player();
+
+    game.handle_player_death(&mut player);
+    game.record_kill();
+    game.boss_health = 1;
+    player.add_item(ItemType::Key);
+    game.level_inventory = player.inventory.clone();
+    game.load_level(2);
+    game.count_move();
+    game.tick(&mut player);
+
+    game.reset();
+    let player = game.init_player();
+
+    assert_eq!(game.current_level, 1);
+    assert_eq!(game.deaths, 0);
+    assert_eq!(game.kills, 0);
+    assert_eq!(game.level_moves, 0);
+    assert_eq!(game.boss_health, BOSS_HEALTH);
+    assert_eq!(game.clock, 0);
+    assert_eq!(player.pos, game.get_player_start());
+    assert_eq!(player.inventory, vec![ItemType::Axe]);
+}
+
+#[test]
+fn test_hook_pulls_player_across_water_gap() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttttttt\ntp1www2sgt\ntttttttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    player.add_item(ItemType::Hook);
+
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Interacted
+    );
+    assert_eq!(player.pos, Position { row: 1, col: 5 });
+    assert_eq!(game.level_moves, 1);
+    assert!(!player.has_item(ItemType::Hook));
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(player.pos, Position { row: 1, col: 6 });
+}
+
+#[test]
+fn test_hook_rejects_anchor_out_of_line() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntp1sst\ntwwwst\ntss2gt\ntttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    player.add_item(ItemType::Hook);
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(player.pos, game.get_player_start());
+    assert!(player.has_item(ItemType::Hook));
+    assert_eq!(
+        game.level.get_tile(&Position { row: 1, col: 2 }),
+        Some(TileType::HookStart)
+    );
+    assert!(game.ui.last_message().unwrap().contains("isn't in line"));
+}
+
+#[test]
+fn test_new_game_plus_carries_items_with_quicker_guards() {
+    let mut game = Game::with_config(&Config::scratch());
+    game.load_level(3);
+    assert_eq!(game.enemy_move_ticks(), ENEMY_MOVE_TICKS);
+
+    game.start_new_game_plus(vec![ItemType::Hook, ItemType::WindChime]);
+    let player = game.init_player();
+
+    assert_eq!(game.current_level, 1);
+    assert_eq!(player.pos, game.get_player_start());
+    assert_eq!(player.inventory, vec![ItemType::Hook, ItemType::WindChime]);
+    assert!(game.enemy_move_ticks() < ENEMY_MOVE_TICKS);
+
+    // Starting over again stays in New Game+
+    game.reset();
+    assert_eq!(game.enemy_move_ticks(), NEW_GAME_PLUS_ENEMY_MOVE_TICKS);
+    assert_eq!(game.init_player().inventory.len(), 2);
+}
+
+#[test]
+fn test_guard_timing_scales_with_speed() {
+    let level = "ttttttt\ntpssHst\ntgttttt";
+    let normal_interval = crate::classes::timing::TICK_DURATION * ENEMY_MOVE_TICKS as u32;
+
+    for (speed, interval) in [
+        (1.0, normal_interval),
+        (0.5, normal_interval * 2),
+        (2.0, normal_interval / 2),
+    ] {
+        let mut game = Game::with_config(&Config {
+            speed,
+            ..Config::scratch()
+        });
+        let _ = std::mem::replace(&mut game.level, level.parse().unwrap());
+        let mut player = game.init_player();
+        let guard = game.level.enemies[0].pos;
+
+        // The guard keeps the same pace in ticks, and the ticks stretch or squeeze with the speed
+        assert_eq!(
+            game.tick_interval() * game.enemy_move_ticks() as u32,
+            interval
+        );
+        for _ in 1..game.enemy_move_ticks() {
+            game.tick(&mut player);
+        }
+        assert_eq!(game.level.enemies[0].pos, guard);
+        game.tick(&mut player);
+        assert_ne!(game.level.enemies[0].pos, guard);
+        assert_eq!(game.clock(), game.enemy_move_ticks());
+    }
+}
+
+#[test]
+fn test_wait_lets_guards_move_while_player_stays() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "ttttttt\ntpssHst\ntgttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    let guard = game.level.enemies[0].pos;
+
+    game.wait(&mut player);
+
+    assert_eq!(game.clock, ENEMY_MOVE_TICKS);
+    assert_eq!(player.pos, game.get_player_start());
+    assert_eq!(game.level_moves, 0);
+    assert_ne!(game.level.enemies[0].pos, guard);
+
+    // Waiting again runs the clock to the following step, not just a single tick
+    game.wait(&mut player);
+    assert_eq!(game.clock, 2 * ENEMY_MOVE_TICKS);
+}
+
+#[test]
+fn test_danger_zone_is_the_guards_open_neighbors() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntpssst\nts!est\ntssrtt\ntgtttt".parse().unwrap(),
+    );
+    let guard = game.level.enemies[0].pos;
+
+    // The rock and the hidden trap keep the guard off, as they do when it moves
+    let expected: HashSet<Position> =
+        [guard.offset(Direction::Up), guard.offset(Direction::Right)].into();
+    assert_eq!(game.danger_zone(), expected);
+
+    game.stunned_enemies.push((guard, u32::MAX));
+    assert!(game.danger_zone().is_empty());
+}
+
+#[test]
+fn test_companion_follows_and_absorbs_one_hit() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntpFset\ntsssst\ntgtttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    let start = player.pos;
+
+    // Walking up to the companion recruits it where it stands
+    player.move_right();
+    assert_eq!(game.resolve_move(&mut player), MoveResult::Interacted);
+    assert_eq!(player.pos, start);
+    assert_eq!(game.companion, Some(start.offset(Direction::Right)));
+    assert_eq!(
+        game.level.get_tile(&start.offset(Direction::Right)),
+        Some(TileType::Empty)
+    );
+
+    // It then takes the tile the player just left
+    for _ in 0..2 {
+        let previous = player.pos;
+        player.move_right();
+        assert_eq!(game.resolve_move(&mut player), MoveResult::Moved);
+        assert_eq!(game.companion, Some(previous));
+    }
+
+    // The first guard the player runs into is held off by the companion
+    let guard = player.pos.offset(Direction::Right);
+    player.move_right();
+    game.resolve_move(&mut player);
+    assert_eq!(game.deaths, 0);
+    assert_eq!(game.companion, None);
+    assert!(game.level.has_enemy_at(&guard));
+    assert!(game.is_stunned(&guard));
+
+    // The second one isn't
+    player.move_right();
+    game.resolve_move(&mut player);
+    assert_eq!(game.deaths, 1);
+    assert_eq!(player.pos, start);
+}
+
+#[test]
+fn test_lantern_light_reveals_more_of_the_fog() {
+    let mut rows = vec!["s".repeat(15); 9];
+    rows[4] = "sspsssssssssOss".to_string();
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, rows.join("\n").parse().unwrap());
+    let mut player = game.init_player();
+
+    let far = game.revealed_tiles(&player);
+    assert_eq!(far.len(), 25);
+
+    // Next to the lantern, the player sees farther
+    let lantern = Position { row: 4, col: 12 };
+    player.reset_position(lantern.offset(Direction::Left));
+    let near = game.revealed_tiles(&player);
+    assert!(near.len() > far.len());
+    assert!(near.contains(&Position { row: 0, col: 7 }));
+
+    // The light they took lasts a while after they walk away, then burns out
+    game.tick(&mut player);
+    player.reset_position(game.get_player_start());
+    assert_eq!(game.vision_radius(&player), LANTERN_VISION_RADIUS);
+    for _ in 0..LANTERN_LIGHT_TICKS {
+        game.tick(&mut player);
+    }
+    assert_eq!(game.revealed_tiles(&player), far);
+}
+
+#[test]
+fn test_try_new_reports_a_missing_first_level() {
+    let dir = std::env::temp_dir().join(format!("ghostblade_no_levels_{}", std::process::id()));
+    let config = Config {
+        maps_dir: dir.to_string_lossy().into_owned(),
+        ..Config::scratch()
+    };
+
+    assert_eq!(
+        Game::try_with_config(&config).err(),
+        Some(GameError::Level(1, LevelError::NotFound))
+    );
+    assert!(Game::try_with_config(&Config::scratch()).is_ok());
+}
+
+#[test]
+fn test_try_with_config_plays_a_map_pack() {
+    let dir = std::env::temp_dir().join(format!("ghostblade_packs_{}", std::process::id()));
+    let forest = dir.join("forest");
+    std::fs::create_dir_all(&forest).unwrap();
+    for number in 1..=2 {
+        std::fs::write(Level::path(&forest, number), "tttt\ntpgt\ntttt").unwrap();
+    }
+    let config = Config {
+        maps_dir: dir.to_string_lossy().into_owned(),
+        pack: Some("forest".to_string()),
+        ..Config::scratch()
+    };
+
+    let game = Game::try_with_config(&config).unwrap();
+    assert_eq!(
+        Level::path(&game.maps_dir, 2),
+        dir.join("forest").join("level_2.txt")
+    );
+    assert_eq!(game.max_levels(), 2);
+    assert_eq!(Level::packs(&dir), vec!["forest".to_string()]);
+
+    let config = Config {
+        pack: Some("desert".to_string()),
+        ..config
+    };
+    let error = Game::try_with_config(&config).err().unwrap();
+    assert_eq!(error, GameError::MissingPack("desert".to_string()));
+    assert_eq!(error.to_string(), "There is no map pack named desert");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_hunter_gives_up_beyond_its_leash() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttttttttt\ntpssssssJsst\ntttttttttttt".parse().unwrap(),
+    );
+    game.leash = 3;
+    let home = Position { row: 1, col: 8 };
+    let mut player = game.init_player();
+
+    // Close to its home, the hunter closes in
+    player.reset_position(Position { row: 1, col: 5 });
+    game.update_enemies(&player);
+    assert_eq!(game.level.enemies[0].pos, Position { row: 1, col: 7 });
+    game.update_enemies(&player);
+    assert_eq!(game.level.enemies[0].pos, Position { row: 1, col: 6 });
+
+    // Once the player slips beyond the leash, it heads back home and stays there
+    player.reset_position(Position { row: 1, col: 4 });
+    game.update_enemies(&player);
+    assert_eq!(game.level.enemies[0].pos, Position { row: 1, col: 7 });
+    for _ in 0..3 {
+        game.update_enemies(&player);
+    }
+    assert_eq!(game.level.enemies[0].pos, home);
+}
+
+#[test]
+fn test_stepping_on_a_sign_shows_its_text() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# sign: Beware the guards ahead\n# sign: Almost there\ntttttt\ntpSsSt\ntttttt"
+            .parse()
+            .unwrap(),
+    );
+    let mut player = game.init_player();
+
+    // Signs are walked over like open ground
+    player.move_right();
+    assert_eq!(game.resolve_move(&mut player), MoveResult::Moved);
+    assert_eq!(
+        game.ui.last_message(),
+        Some("   🪧 Beware the guards ahead ")
+    );
+
+    player.move_right();
+    game.resolve_move(&mut player);
+    player.move_right();
+    game.resolve_move(&mut player);
+    assert_eq!(game.ui.last_message(), Some("   🪧 Almost there "));
+}
+
+#[test]
+fn test_cracked_wall_breaks_with_a_bomb_or_an_axe() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "ttttt\ntpTst\ntTsst\nttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+    let start = player.pos;
+    let east = start.offset(Direction::Right);
+    let south = start.offset(Direction::Down);
+
+    // Without a tool it bumps like any other wall
+    player.move_right();
+    assert_eq!(game.resolve_move(&mut player), MoveResult::Interacted);
+    assert_eq!(player.pos, start);
+    assert_eq!(game.level.get_tile(&east), Some(TileType::CrackedWall));
+    assert_eq!(game.ui.last_message(), Some(BlockingType::Wall.message()));
+
+    player.add_item(ItemType::Bomb);
+    player.move_right();
+    game.resolve_move(&mut player);
+    assert_eq!(game.level.get_tile(&east), Some(TileType::Empty));
+    assert!(!player.has_item(ItemType::Bomb));
+
+    player.add_item(ItemType::Axe);
+    player.move_down();
+    game.resolve_move(&mut player);
+    assert_eq!(game.level.get_tile(&south), Some(TileType::Empty));
+    assert!(!player.has_item(ItemType::Axe));
+}
+
+#[test]
+fn test_goal_needs_the_required_items() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# requires: key, windchime\ntttt\ntpgt\ntttt"
+            .parse()
+            .unwrap(),
+    );
+    game.leaderboard = Leaderboard::load(crate::classes::leaderboard::temp_path("required"));
+    game.achievements = Achievements::load(crate::classes::leaderboard::temp_path(
+        "required_achievements",
+    ));
+    let mut player = game.init_player();
+    let start = player.pos;
+
+    player.add_item(ItemType::Key);
+    player.move_right();
+    assert_eq!(game.reach_goal(&mut player), GoalOutcome::Blocked);
+    assert_eq!(player.pos, start);
+    assert_eq!(
+        game.ui.last_message(),
+        Some("   The gate won't open — you're missing something ")
+    );
+
+    player.add_item(ItemType::WindChime);
+    player.move_right();
+    assert_ne!(game.reach_goal(&mut player), GoalOutcome::Blocked);
+}
+
+#[test]
+fn test_slain_guards_respawn_up_to_the_cap() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# respawn: 10\n# max enemies: 1\ntttttt\ntpsHst\ntsHsst\ntttttt"
+            .parse()
+            .unwrap(),
+    );
+    let mut player = game.init_player();
+    let homes: Vec<Position> = game.level.enemies.iter().map(|enemy| enemy.pos).collect();
+
+    for home in &homes {
+        game.remove_enemy(home);
+    }
+    assert!(game.level.enemies.is_empty());
+
+    for _ in 0..9 {
+        game.tick(&mut player);
+    }
+    assert!(game.level.enemies.is_empty());
+
+    // The first guard slain is back at its post on time
+    game.tick(&mut player);
+    assert_eq!(game.level.enemies.len(), 1);
+    assert_eq!(game.level.enemies[0].pos, homes[0]);
+
+    // The other one waits for room under the cap
+    for _ in 0..30 {
+        game.tick(&mut player);
+    }
+    assert_eq!(game.level.enemies.len(), 1);
+}
+
+#[test]
+fn test_huge_respawn_delay_never_brings_a_guard_back() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        format!("# respawn: {}\ntttt\ntpHt\ntttt", u64::MAX)
+            .parse()
+            .unwrap(),
+    );
+    let mut player = game.init_player();
+    game.clock = 5;
+
+    let home = game.level.enemies[0].pos;
+    game.remove_enemy(&home);
+    for _ in 0..10 {
+        game.tick(&mut player);
+    }
+    assert!(game.level.enemies.is_empty());
+}
+
+#[test]
+fn test_available_interactions_need_the_right_item() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, "tttt\ntpdt\ntTtt\ntttt".parse().unwrap());
+    let mut player = game.init_player();
+
+    // The cracked wall below is left out, so it stays a secret
+    let door = Interaction {
+        dir: Direction::Right,
+        kind: InteractiveType::Door,
+        item: Some(ItemType::Key),
+        available: false,
+    };
+    assert_eq!(game.available_interactions(&player), vec![door]);
+
+    player.add_item(ItemType::Key);
+    assert_eq!(
+        game.available_interactions(&player),
+        vec![Interaction {
+            available: true,
+            ..door
+        }]
+    );
+}
+
+#[test]
+fn test_walking_on_snow_leaves_a_trail_of_recent_footprints() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttttttttt\ntpyyyyyyyyst\ntttttttttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+
+    // Leaving plain ground leaves no print
+    game.try_move(&mut player, Direction::Right);
+    assert!(game.footprints.is_empty());
+
+    for _ in 0..3 {
+        game.try_move(&mut player, Direction::Right);
+    }
+    let trail = |cols: std::ops::Range<i16>| -> Vec<Position> {
+        cols.map(|col| Position { row: 1, col }).collect()
+    };
+    assert_eq!(Vec::from(game.footprints.clone()), trail(2..5));
+
+    // Only the most recent prints stay
+    for _ in 0..6 {
+        game.try_move(&mut player, Direction::Right);
+    }
+    assert_eq!(player.pos, Position { row: 1, col: 10 });
+    assert_eq!(
+        Vec::from(game.footprints.clone()),
+        trail(10 - FOOTPRINT_TRAIL_LENGTH as i16..10)
+    );
+}
+
+#[test]
+fn test_conveyor_carries_the_player_until_a_wall() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, "tttttt\ntpRRRt\ntttgtt".parse().unwrap());
+    let mut player = game.init_player();
+
+    // Plain ground stays put
+    game.tick(&mut player);
+    assert_eq!(player.pos, Position { row: 1, col: 1 });
+
+    game.try_move(&mut player, Direction::Right);
+    for col in 3..=4 {
+        game.tick(&mut player);
+        assert_eq!(player.pos, Position { row: 1, col });
+    }
+
+    // The forest at the end of the belt stops the drift
+    game.tick(&mut player);
+    assert_eq!(player.pos, Position { row: 1, col: 4 });
+    assert_eq!(game.level_moves, 1);
+}
+
+#[test]
+fn test_conveyor_ride_counts_as_a_step_past_a_closing_door() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# doors: closing\nttttttt\ntpdRRRt\ntgttttt"
+            .parse()
+            .unwrap(),
+    );
+    let mut player = game.init_player();
+    player.add_item(ItemType::Key);
+    let door = Position { row: 1, col: 2 };
+
+    for _ in 0..3 {
+        game.try_move(&mut player, Direction::Right);
+    }
+    assert_eq!(player.pos, Position { row: 1, col: 3 });
+    assert_eq!(game.level.get_tile(&door), Some(TileType::Door));
+
+    // The belt carries the player on, and the tiles it passes count as explored
+    for col in 4..=5 {
+        game.tick(&mut player);
+        assert_eq!(player.pos, Position { row: 1, col });
+        assert!(game.visited.contains(&player.pos));
+    }
+    assert_eq!(game.level.get_tile(&door), Some(TileType::Door));
+}
+
+#[test]
+fn test_run_summary_counts_the_whole_run() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, "tttttt\ntpkust\ntttttt".parse().unwrap());
+    let mut player = game.init_player();
+    game.deaths = 1;
+
+    for _ in 0..3 {
+        game.try_move(&mut player, Direction::Right);
+    }
+
+    let summary = game.run_summary();
+    assert_eq!(summary.level, 1);
+    assert_eq!(summary.moves, 5);
+    assert_eq!(summary.deaths, 1);
+    assert_eq!(summary.kills, 0);
+    assert_eq!(summary.items, 1);
+
+    // Starting over clears the run
+    game.reset();
+    assert_eq!(game.run_summary().moves, 0);
+    assert_eq!(game.run_summary().items, 0);
+}
+
+#[test]
+fn test_dash_moves_two_clear_tiles_and_stops_at_an_obstacle() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "ttttttt\ntpsssrt\nttttttt".parse().unwrap(),
+    );
+    let mut player = game.init_player();
+
+    assert_eq!(game.dash(&mut player, Direction::Right), MoveResult::Moved);
+    assert_eq!(player.pos, Position { row: 1, col: 3 });
+
+    // Only one tile is clear before the rock
+    assert_eq!(game.dash(&mut player, Direction::Right), MoveResult::Moved);
+    assert_eq!(player.pos, Position { row: 1, col: 4 });
+
+    assert_eq!(
+        game.dash(&mut player, Direction::Right),
+        MoveResult::Interacted
+    );
+    assert_eq!(player.pos, Position { row: 1, col: 4 });
+    assert_eq!(game.level_moves, 3);
+}
+
+#[test]
+fn test_stepping_on_a_switch_toggles_its_walls() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# switch: 1,4 2,4\nttttttt\ntpKstgt\ntssssst\nttttttt"
+            .parse()
+            .unwrap(),
+    );
+    let mut player = game.init_player();
+    let wall = Position { row: 1, col: 4 };
+    let floor = Position { row: 2, col: 4 };
+    assert!(!game.is_switch_flipped(0));
+
+    // Stepping on the switch opens its wall and raises one where there was open ground
+    assert_eq!(
+        game.try_move(&mut player, Direction::Right),
+        MoveResult::Moved
+    );
+    assert!(game.is_switch_flipped(0));
+    assert_eq!(game.level.get_tile(&wall), Some(TileType::Empty));
+    assert_eq!(game.level.get_tile(&floor), Some(TileType::Wall));
+
+    // Standing on it or walking off leaves it flipped
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.level.get_tile(&wall), Some(TileType::Empty));
+
+    // Stepping on it again flips everything back
+    game.try_move(&mut player, Direction::Left);
+    assert!(!game.is_switch_flipped(0));
+    assert_eq!(game.level.get_tile(&wall), Some(TileType::Wall));
+    assert_eq!(game.level.get_tile(&floor), Some(TileType::Empty));
+    assert!(matches!(
+        game.check_collision(&wall),
+        CollisionType::Blocking(_)
+    ));
+}
+
+#[test]
+fn test_click_steps_toward_the_clicked_tile() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "tttttt\ntpssst\ntttsst\ntsssgt\ntttttt".parse().unwrap(),
+    );
+    let player = game.init_player();
+    let from = player.pos;
+
+    // The longer way to go comes first
+    assert_eq!(
+        game.greedy_step(&from, &Position { row: 1, col: 4 }),
+        Some(Direction::Right)
+    );
+    assert_eq!(
+        game.greedy_step(&from, &Position { row: 3, col: 2 }),
+        Some(Direction::Right)
+    );
+    // Straight down is walled off, so a bump explains it
+    assert_eq!(
+        game.greedy_step(&from, &Position { row: 3, col: 1 }),
+        Some(Direction::Down)
+    );
+    assert_eq!(game.greedy_step(&from, &from), None);
+
+    // Each tile is two terminal columns wide, so column 6 of row 1 is the tile at (1, 3)
+    assert_eq!(game.click_step(&player, 6, 1), Some(Direction::Right));
+    assert_eq!(game.click_step(&player, 2, 1), None);
+    assert_eq!(game.click_step(&player, 40, 1), None);
+
+    // With pathfinding, the click leads around the wall instead
+    assert_eq!(game.click_step(&player, 3, 3), Some(Direction::Down));
+    game.mouse_pathfind = true;
+    assert_eq!(game.click_step(&player, 3, 3), Some(Direction::Right));
+    assert_eq!(
+        game.shortest_path_to(&from, &Position { row: 3, col: 1 })
+            .map(|path| path.len()),
+        Some(6)
+    );
+}
+
+#[test]
+fn test_switch_restores_its_tiles_after_one_was_occupied() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# switch: 1,4 2,4\nttttttt\ntpKstgt\ntssssst\nttttttt"
+            .parse()
+            .unwrap(),
+    );
+    let mut player = game.init_player();
+    let wall = Position { row: 1, col: 4 };
+    let floor = Position { row: 2, col: 4 };
+    game.level.enemies.push(Enemy::new(floor));
+
+    // No wall rises on the guard
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.level.get_tile(&wall), Some(TileType::Empty));
+    assert_eq!(game.level.get_tile(&floor), Some(TileType::Empty));
+
+    // Flipping back still restores the layout the level started with
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Left);
+    assert!(!game.is_switch_flipped(0));
+    assert_eq!(game.level.get_tile(&wall), Some(TileType::Wall));
+    assert_eq!(game.level.get_tile(&floor), Some(TileType::Empty));
+
+    // A pending wall rises once the tile is free
+    game.try_move(&mut player, Direction::Right);
+    game.try_move(&mut player, Direction::Left);
+    assert_eq!(game.level.get_tile(&floor), Some(TileType::Empty));
+    game.level.enemies.clear();
+    game.tick(&mut player);
+    assert_eq!(game.level.get_tile(&floor), Some(TileType::Wall));
+}
+
+#[test]
+fn test_cleared_run_is_logged_before_new_game_plus() {
+    let path = crate::classes::leaderboard::temp_path("run_log_clear");
+    let _ = std::fs::remove_file(&path);
+    let mut game = Game::with_config(&Config::scratch());
+    game.run_log = path.clone();
+    let mut player = game.init_player();
+
+    game.load_level(game.max_levels());
+    assert_eq!(game.skip_level(&mut player), GoalOutcome::GameClear);
+    game.start_new_game_plus(Vec::new());
+    let log = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(log.lines().count(), 1);
+    assert!(log.starts_with("ended="));
+    assert!(log.contains(&format!(" level={} ", game.max_levels())));
+
+    // Quitting the New Game+ run logs it as a run of its own
+    game.log_run().unwrap();
+    game.log_run().unwrap();
+    let log = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(log.lines().count(), 2);
+    assert!(log.lines().nth(1).unwrap().contains(" level=1 "));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_slain_guards_respawn_on_spawn_tiles() {
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(
+        &mut game.level,
+        "# respawn: 2\nttttttt\ntpsHsEt\ntsEssst\nttttttt"
+            .parse()
+            .unwrap(),
+    );
+    let mut player = game.init_player();
+    let spawns = game.level.spawns.clone();
+    assert_eq!(
+        spawns,
+        vec![Position { row: 1, col: 5 }, Position { row: 2, col: 2 }]
+    );
+
+    let home = game.level.enemies[0].pos;
+    game.remove_enemy(&home);
+    game.tick(&mut player);
+    game.tick(&mut player);
+    assert_eq!(game.level.enemies.len(), 1);
+    assert_eq!(game.level.enemies[0].pos, spawns[0]);
+
+    // With the first spawn tile taken, the next guard comes back on the second
+    let pos = game.level.enemies[0].pos;
+    game.level
+        .enemies
+        .push(Enemy::patrolling(home, Patrol::Horizontal));
+    game.remove_enemy(&home);
+    game.tick(&mut player);
+    game.tick(&mut player);
+    assert_eq!(game.level.enemies[0].pos, pos);
+    assert_eq!(game.level.enemies[1].pos, spawns[1]);
+}
+
+#[test]
+fn test_lantern_only_gives_light_in_the_fog() {
+    let level = "ttttt\ntpOgt\nttttt";
+    let mut game = Game::with_config(&Config::scratch());
+    let _ = std::mem::replace(&mut game.level, level.parse().unwrap());
+    let mut player = game.init_player();
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(
+        game.ui.last_message(),
+        Some(BlockingType::Lantern.message())
+    );
+    assert_eq!(game.light_ticks, 0);
+
+    let mut game = Game::with_config(&Config {
+        fog: true,
+        ..Config::scratch()
+    });
+    let _ = std::mem::replace(&mut game.level, level.parse().unwrap());
+    let mut player = game.init_player();
+
+    game.try_move(&mut player, Direction::Right);
+    assert_eq!(game.light_ticks, LANTERN_LIGHT_TICKS);
 }
@@ -0,0 +1,277 @@
+//! The `InputQueue` struct buffers key presses between frames.
+//!
+//! Every pass of the game loop reads all pending key events at once and applies
+//! them in the order they were pressed, so a quick burst of keys isn't lost while
+//! a frame is being drawn. Each queued move is applied on its own, one after the
+//! other, exactly as if the keys had arrived separately.
+//!
+//! Only presses count. Terminals that report key releases send one for every
+//! press, and terminals with key repeat send repeat events while a key is held;
+//! releases are ignored, and repeats of the key last pressed are collapsed into
+//! that press, so a single keypress never moves the player twice.
+//!
+//! The queue holds at most `MAX_QUEUED_KEYS` presses. Once it is full, further
+//! presses are dropped until the queue is drained, so a stalled frame can't
+//! build up a long run of moves that play out after the player let go.
+//!
+//! Left clicks are queued separately, as the terminal cells they landed on, and are
+//! only reported while the mouse is captured with `--mouse`. They count against the
+//! same `MAX_QUEUED_KEYS` limit.
+//!
+//! A `DashDetector` watches the directions pressed for a combo: the same direction
+//! twice within `DASH_WINDOW` makes the second press a dash.
+//!
+//! # Fields
+//!
+//! - `keys` (`VecDeque<(KeyEvent, Instant)>`): The buffered key presses with the time each was read, oldest first.
+//! - `last_pressed` (`Option<KeyCode>`): The key pressed most recently, whose repeats are ignored.
+//! - `clicks` (`VecDeque<(u16, u16)>`): The buffered left clicks, as terminal columns and rows, oldest first.
+//!
+//! # Methods
+//!
+//! - `new`: Creates an empty queue.
+//! - `push`: Adds a key press to the back of the queue, stamped with the time it was read, returning `false`
+//!   if it was dropped because the queue was full, or because it was a release or a repeat.
+//! - `pop`: Takes the oldest key press from the queue with its time, so combos are timed by when keys were
+//!   pressed rather than when a busy frame got around to them.
+//! - `push_click` / `pop_click`: Add a left click to the back of the queue of clicks, or take the oldest one.
+//! - `clear`: Drops every buffered key press and click.
+//! - `read_pending`: Moves every key event and left click waiting in the terminal into the queue without blocking.
+//! - `DashDetector::press`: Records a direction pressed at a given time, returning whether it completes a double tap.
+//!   A dash starts the watch over, so a third tap is a plain move again.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut input = InputQueue::new();
+//! input.read_pending()?;
+//! while let Some((key_event, pressed_at)) = input.pop() {
+//!     handle(key_event, pressed_at);
+//! }
+//! ```
+
+use crate::classes::types::Direction;
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+// The most key presses buffered between two passes of the game loop
+pub const MAX_QUEUED_KEYS: usize = 16;
+// Two presses of the same direction at most this far apart make a dash
+pub const DASH_WINDOW: Duration = Duration::from_millis(250);
+
+#[derive(Default)]
+pub struct InputQueue {
+    keys: VecDeque<(KeyEvent, Instant)>,
+    last_pressed: Option<KeyCode>,
+    clicks: VecDeque<(u16, u16)>,
+}
+
+impl InputQueue {
+    pub fn new() -> Self {
+        Self {
+            keys: VecDeque::with_capacity(MAX_QUEUED_KEYS),
+            last_pressed: None,
+            clicks: VecDeque::new(),
+        }
+    }
+
+    pub fn push(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.kind {
+            KeyEventKind::Release => return false,
+            KeyEventKind::Repeat if self.last_pressed == Some(key_event.code) => return false,
+            _ => self.last_pressed = Some(key_event.code),
+        }
+
+        if self.keys.len() >= MAX_QUEUED_KEYS {
+            return false;
+        }
+
+        self.keys.push_back((key_event, Instant::now()));
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<(KeyEvent, Instant)> {
+        self.keys.pop_front()
+    }
+
+    pub fn push_click(&mut self, column: u16, row: u16) -> bool {
+        if self.clicks.len() >= MAX_QUEUED_KEYS {
+            return false;
+        }
+
+        self.clicks.push_back((column, row));
+        true
+    }
+
+    pub fn pop_click(&mut self) -> Option<(u16, u16)> {
+        self.clicks.pop_front()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.clicks.clear();
+    }
+
+    pub fn read_pending(&mut self) -> io::Result<()> {
+        while event::poll(Duration::ZERO)? {
+            match event::read()? {
+                Event::Key(key_event) => {
+                    self.push(key_event);
+                }
+                Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::Down(MouseButton::Left),
+                    column,
+                    row,
+                    ..
+                }) => {
+                    self.push_click(column, row);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct DashDetector {
+    last: Option<(Direction, Instant)>,
+}
+
+impl DashDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn press(&mut self, dir: Direction, at: Instant) -> bool {
+        let dash = matches!(
+            self.last,
+            Some((last_dir, last_at)) if last_dir == dir && at.duration_since(last_at) <= DASH_WINDOW
+        );
+        self.last = if dash { None } else { Some((dir, at)) };
+        dash
+    }
+}
+
+#[test]
+fn test_queued_moves_come_out_in_order() {
+    use crossterm::event::KeyModifiers;
+
+    let pressed = [KeyCode::Right, KeyCode::Down, KeyCode::Left];
+    let mut input = InputQueue::new();
+    for code in pressed {
+        assert!(input.push(KeyEvent::new(code, KeyModifiers::NONE)));
+    }
+
+    let popped: Vec<(KeyCode, Instant)> = std::iter::from_fn(|| input.pop())
+        .map(|(key_event, at)| (key_event.code, at))
+        .collect();
+    let codes: Vec<KeyCode> = popped.iter().map(|(code, _)| *code).collect();
+    assert_eq!(codes, pressed);
+    assert!(popped.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert_eq!(input.pop(), None);
+}
+
+#[test]
+fn test_full_queue_drops_extra_keys() {
+    use crossterm::event::KeyCode;
+
+    let mut input = InputQueue::new();
+    for _ in 0..MAX_QUEUED_KEYS {
+        assert!(input.push(KeyCode::Up.into()));
+    }
+    assert!(!input.push(KeyCode::Down.into()));
+
+    assert_eq!(std::iter::from_fn(|| input.pop()).count(), MAX_QUEUED_KEYS);
+    assert!(input.push(KeyCode::Down.into()));
+    input.clear();
+    assert_eq!(input.pop(), None);
+}
+
+#[test]
+fn test_repeats_and_releases_collapse_into_one_press() {
+    use crossterm::event::{KeyEventState, KeyModifiers};
+
+    let key = |code, kind| KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+        kind,
+        state: KeyEventState::NONE,
+    };
+    let mut input = InputQueue::new();
+
+    assert!(input.push(key(KeyCode::Right, KeyEventKind::Press)));
+    assert!(!input.push(key(KeyCode::Right, KeyEventKind::Repeat)));
+    assert!(!input.push(key(KeyCode::Right, KeyEventKind::Repeat)));
+    assert!(!input.push(key(KeyCode::Right, KeyEventKind::Release)));
+    // A repeat of another key than the last one pressed still counts
+    assert!(input.push(key(KeyCode::Down, KeyEventKind::Repeat)));
+    assert!(input.push(key(KeyCode::Right, KeyEventKind::Press)));
+
+    let codes: Vec<KeyCode> = std::iter::from_fn(|| input.pop())
+        .map(|(key_event, _)| key_event.code)
+        .collect();
+    assert_eq!(codes, vec![KeyCode::Right, KeyCode::Down, KeyCode::Right]);
+}
+
+#[test]
+fn test_double_tap_within_the_window_dashes() {
+    let mut dash = DashDetector::new();
+    let start = Instant::now();
+
+    assert!(!dash.press(Direction::Right, start));
+    assert!(dash.press(Direction::Right, start + DASH_WINDOW));
+    // The dash used up the first tap
+    assert!(!dash.press(Direction::Right, start + DASH_WINDOW));
+
+    // Too slow, or another direction in between
+    assert!(!dash.press(Direction::Right, start + DASH_WINDOW * 3));
+    assert!(!dash.press(Direction::Up, start + DASH_WINDOW * 3));
+    assert!(!dash.press(Direction::Right, start + DASH_WINDOW * 3));
+}
+
+#[test]
+fn test_keys_are_timed_when_read_rather_than_when_handled() {
+    let mut input = InputQueue::new();
+    assert!(input.push(KeyCode::Right.into()));
+    assert!(input.push(KeyCode::Right.into()));
+    let read = Instant::now();
+
+    let (_, first) = input.pop().unwrap();
+    let (_, second) = input.pop().unwrap();
+    assert!(second <= read);
+
+    // Handled by a slow frame past the dash window, the second press would no longer count
+    let mut late = DashDetector::new();
+    assert!(!late.press(Direction::Right, first));
+    assert!(!late.press(Direction::Right, read + DASH_WINDOW * 2));
+
+    // Timed by when it was read, it still completes the double tap
+    let mut dash = DashDetector::new();
+    assert!(!dash.press(Direction::Right, first));
+    assert!(dash.press(Direction::Right, second));
+}
+
+#[test]
+fn test_clicks_are_queued_apart_from_keys() {
+    use crossterm::event::KeyModifiers;
+
+    let mut input = InputQueue::new();
+    assert!(input.push(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE)));
+    assert!(input.push_click(4, 2));
+    assert!(input.push_click(8, 3));
+
+    assert_eq!(input.pop_click(), Some((4, 2)));
+    assert_eq!(
+        input.pop().map(|(key_event, _)| key_event.code),
+        Some(KeyCode::Right)
+    );
+
+    input.clear();
+    assert_eq!(input.pop_click(), None);
+}
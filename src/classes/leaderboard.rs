@@ -0,0 +1,228 @@
+//! The `Leaderboard` struct keeps the best completion time and move count for each level.
+//!
+//! Records are stored in a small text file, one level per line:
+//!
+//! ```text
+//! <level number> <time in milliseconds> <moves>
+//! ```
+//!
+//! A missing or corrupt file is treated as an empty leaderboard, so a broken
+//! file never prevents the game from starting. It is simply overwritten the
+//! next time a level is completed.
+//!
+//! Map packs number their levels from 1 just like the built-in levels, so each
+//! pack keeps its records in a file of its own, named after the pack.
+//!
+//! # Fields
+//!
+//! - `path` (`PathBuf`): The file the leaderboard is read from and written to.
+//! - `records` (`BTreeMap<usize, Record>`): The best record for each level number.
+//!
+//! # Methods
+//!
+//! - `load`: Reads the leaderboard from a file, starting fresh if it is missing or corrupt.
+//! - `pack_path`: The file of a map pack's leaderboard, next to the built-in one: `leaderboard_forest.txt` for the
+//!   `forest` pack.
+//! - `best`: Returns the best record for a level, if any.
+//! - `record`: Stores a completion if it beats the current best time, returning whether it did.
+//! - `save`: Writes all records back to the file.
+//! - `format_time`: Formats a duration as `MM:SS` for display.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut leaderboard = Leaderboard::load("leaderboard.txt");
+//!
+//! if leaderboard.record(1, Duration::from_secs(42), 30) {
+//!     leaderboard.save()?;
+//! }
+//!
+//! println!("Best: {}", Leaderboard::format_time(leaderboard.best(1).unwrap().time));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// File the best time of each level is saved to
+pub const LEADERBOARD_FILE: &str = "leaderboard.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Record {
+    pub time: Duration,
+    pub moves: u32,
+}
+
+pub struct Leaderboard {
+    path: PathBuf,
+    records: BTreeMap<usize, Record>,
+}
+
+impl Leaderboard {
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let records = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| Self::parse(&contents))
+            .unwrap_or_default();
+
+        Self { path, records }
+    }
+
+    pub fn pack_path<P: AsRef<Path>>(path: P, pack: &str) -> PathBuf {
+        let path = path.as_ref();
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match path.extension() {
+            Some(extension) => format!("{}_{}.{}", stem, pack, extension.to_string_lossy()),
+            None => format!("{}_{}", stem, pack),
+        };
+        path.with_file_name(name)
+    }
+
+    fn parse(contents: &str) -> Option<BTreeMap<usize, Record>> {
+        let mut records = BTreeMap::new();
+
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.split_whitespace();
+            let level = fields.next()?.parse().ok()?;
+            let millis = fields.next()?.parse().ok()?;
+            let moves = fields.next()?.parse().ok()?;
+
+            records.insert(
+                level,
+                Record {
+                    time: Duration::from_millis(millis),
+                    moves,
+                },
+            );
+        }
+
+        Some(records)
+    }
+
+    pub fn best(&self, level: usize) -> Option<&Record> {
+        self.records.get(&level)
+    }
+
+    pub fn record(&mut self, level: usize, time: Duration, moves: u32) -> bool {
+        let is_best = self.records.get(&level).is_none_or(|best| time < best.time);
+
+        if is_best {
+            self.records.insert(level, Record { time, moves });
+        }
+
+        is_best
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents: String = self
+            .records
+            .iter()
+            .map(|(level, record)| {
+                format!("{} {} {}\n", level, record.time.as_millis(), record.moves)
+            })
+            .collect();
+
+        fs::write(&self.path, contents)
+    }
+
+    pub fn format_time(time: Duration) -> String {
+        let seconds = time.as_secs();
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ghostblade_{}_{}.txt", name, std::process::id()))
+}
+
+#[test]
+fn test_leaderboard_round_trip() {
+    let path = temp_path("leaderboard_round_trip");
+    let _ = fs::remove_file(&path);
+
+    let mut leaderboard = Leaderboard::load(&path);
+    assert!(leaderboard.best(1).is_none());
+
+    assert!(leaderboard.record(1, Duration::from_secs(42), 30));
+    leaderboard.save().unwrap();
+
+    let mut reloaded = Leaderboard::load(&path);
+    assert_eq!(
+        reloaded.best(1),
+        Some(&Record {
+            time: Duration::from_secs(42),
+            moves: 30
+        })
+    );
+
+    // Slower runs don't replace the record, faster ones do
+    assert!(!reloaded.record(1, Duration::from_secs(50), 20));
+    assert_eq!(reloaded.best(1).unwrap().time, Duration::from_secs(42));
+    assert!(reloaded.record(1, Duration::from_secs(40), 35));
+    assert_eq!(reloaded.best(1).unwrap().moves, 35);
+
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_leaderboard_corrupt_file_starts_fresh() {
+    let path = temp_path("leaderboard_corrupt");
+    fs::write(&path, "1 42000 30\nnot a record\n").unwrap();
+
+    let leaderboard = Leaderboard::load(&path);
+    assert!(leaderboard.best(1).is_none());
+
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn test_leaderboard_format_time() {
+    assert_eq!(Leaderboard::format_time(Duration::from_secs(42)), "00:42");
+    assert_eq!(
+        Leaderboard::format_time(Duration::from_millis(125_900)),
+        "02:05"
+    );
+}
+
+#[test]
+fn test_pack_records_are_kept_apart_from_built_in_ones() {
+    let path = temp_path("leaderboard_built_in");
+    let pack_path = Leaderboard::pack_path(&path, "forest");
+    assert_eq!(
+        pack_path.file_name().unwrap().to_string_lossy(),
+        format!(
+            "ghostblade_leaderboard_built_in_{}_forest.txt",
+            std::process::id()
+        )
+    );
+    assert_eq!(
+        Leaderboard::pack_path("leaderboard.txt", "forest"),
+        PathBuf::from("leaderboard_forest.txt")
+    );
+
+    let mut built_in = Leaderboard::load(&path);
+    built_in.record(1, Duration::from_secs(42), 30);
+    built_in.save().unwrap();
+
+    // A faster clear of the pack's level 1 is a record of the pack's alone
+    let mut pack = Leaderboard::load(&pack_path);
+    assert!(pack.best(1).is_none());
+    assert!(pack.record(1, Duration::from_secs(10), 8));
+    pack.save().unwrap();
+
+    assert_eq!(
+        Leaderboard::load(&path).best(1).unwrap().time,
+        Duration::from_secs(42)
+    );
+    assert_eq!(
+        Leaderboard::load(&pack_path).best(1).unwrap().time,
+        Duration::from_secs(10)
+    );
+
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(&pack_path);
+}
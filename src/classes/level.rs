@@ -6,9 +6,14 @@
 //!
 //! # Fields
 //! - `map`: A 2D vector of `TileType` that represents the physical layout of the level.
-//! - `enemies`: A vector of `Position` structs representing the positions of enemies in the level.
+//! - `enemies`: The patrol guards of the level. Each `Enemy` has a position, the home it started at, and a `Patrol` telling
+//!   how it moves: wandering at random, marching back and forth along a row or column, or hunting the player near its home.
+//! - `platforms`: The moving platforms, each shuttling back and forth along its own straight track.
+//! - `oni`: The positions of the oni, which chase a nearby player and can only be cleansed with the Wind Chime.
+//! - `spawns`: The spawn tiles (`'E'`) where slain guards come back on a level with `# respawn:`; open ground otherwise.
 //! - `player_start`: A `Position` indicating the starting position of the player.
 //! - `map_size`: A tuple `(u8, u8)` that specifies the number of rows and columns in the level map.
+//! - `header`: The `LevelHeader` settings read from the `#` lines at the top of the map file, such as a move limit.
 //!
 //! # Methods
 //!
@@ -22,6 +27,84 @@
 //! - `Some(Level)`: If the file is successfully read and parsed.
 //! - `None`: If the file is not found or there is a read error.
 //!
+//! ## `load_from`
+//! Same as `load`, but reads `level_<level_number>.txt` from the given directory.
+//!
+//! ## `load_path`
+//! Same as `load`, but reads the map file at the given path, for playing a standalone custom map.
+//!
+//! ## `read`
+//! Same as `load_path`, but tells why the map couldn't be loaded: `LevelError::NotFound` for a missing file,
+//! `LevelError::InvalidUtf8` for a file saved in another encoding, `LevelError::Io` for any other read error,
+//! or the parse error of a malformed map.
+//!
+//! ## `count`
+//! Counts the level files in a directory, from `level_1.txt` up to the first missing number.
+//!
+//! ## `packs`
+//! Lists the map packs installed in a maps directory: the names of its subdirectories, sorted.
+//!
+//! ## `read_contents`
+//! Reads the text of a map file, failing with the same file errors as `read`.
+//!
+//! ## `from_str`
+//! Levels implement `FromStr`, so map text can be parsed directly with `contents.parse::<Level>()`.
+//! Parsing fails with a `LevelError` when the text has no tiles, or when it doesn't have
+//! exactly one player start (`'p'`). A map with more pressure plates (`'P'`), gates (`'G'`),
+//! signs (`'S'`), or switches (`'K'`) than their `u8` index can count fails with `LevelError::TooMany`.
+//!
+//! ## `LevelHeader::parse`
+//! Reads the header lines at the top of a map file. Every header line starts with `#`,
+//! and any line other than these settings is a comment:
+//!
+//! - `# name: TEXT`: The name of the level, shown by `--list-levels`.
+//! - `# par: SECONDS`: The par time of the level, a time worth beating, shown by `--list-levels`.
+//! - `# moves: N`: Limits the level to `N` moves.
+//! - `# keys: single|multi`: Whether a key is used up by the door it opens (the default), or opens every door.
+//! - `# doors: persistent|closing`: Whether an opened door stays open (the default), or locks again behind the player.
+//! - `# respawn: TICKS`: Slain guards come back to where they started after this many ticks, for survival levels.
+//! - `# max enemies: N`: Guards only respawn while fewer than `N` are on the level.
+//! - `# requires: ITEM,ITEM,...`: Items the player must carry for the goal to let them through, named as for `--give`.
+//! - `# sign: TEXT`: The text of a sign. Signs and texts are linked in reading order: the first sign (`'S'`) in the map
+//!   shows the first text, the second sign the second text, and so on.
+//! - `# switch: ROW,COL ROW,COL ...`: The tiles a switch flips between wall and open ground, counting rows and
+//!   columns of the map from 0 at the top left. Switches and these lines are linked in reading order, like signs.
+//! - `# [charmap]`: Starts a section of `# X = y` lines, each reading the character `X` in this file
+//!   as the built-in map character `y`, such as `# █ = t` for walls drawn with box-drawing characters.
+//!   The section runs until the next `# [...]` line; characters it doesn't mention keep their usual meaning.
+//!
+//! A known key with a value that doesn't parse fails with `LevelError::InvalidHeader`.
+//!
+//! ## `check_exit`
+//! Fails with `LevelError::NoExit` when the map has no goal or princess, so the level could
+//! never be finished. A boss alone doesn't count, since defeating it doesn't end the level.
+//!
+//! ## `nearest_exit`
+//! Returns the goal or princess closest to a position, counting cardinal steps.
+//!
+//! ## `is_goal_reachable`
+//! Checks whether a goal or princess can be reached from the player start, assuming every
+//! item puzzle along the way is solved. Only terrain that can never be cleared blocks the path,
+//! so a wall a switch flips open doesn't, and a hook start counts as connected to its hook end.
+//!
+//! ## `bounding_box_of`
+//! Returns the top-left and bottom-right corners of the smallest rectangle covering every tile
+//! of a type, such as all the tiles of a large boss, or `None` when the map has none.
+//!
+//! ## `has_enemy_at`
+//! Checks whether a patrol guard stands at a position.
+//!
+//! ## `Enemy::turn_around`
+//! Reverses the heading of a patrolling guard, used when something blocks its way.
+//!
+//! ## `Platform::advance`
+//! Moves a platform one tile along its track, turning around at either end.
+//!
+//! ## `lint`
+//! Checks map text for common authoring mistakes and returns a description of each problem:
+//! rows of different lengths, a missing or repeated player start, no goal or princess,
+//! or a goal that can't be reached.
+//!
 //! ## `set_tile`
 //! Sets a specified tile in the map to a new `TileType`.
 //!
@@ -42,108 +125,715 @@
 //! - `Some(TileType)`: If the position is within the map boundaries.
 //! - `None`: If the position is out of bounds.
 //!
+//! ## `connected`
+//! Returns every position reachable from a position through neighbors of the same tile type,
+//! including the position itself, such as all the tiles of a boss spanning several tiles.
+//! An out of bounds position has no connected tiles.
+//!
+//! ## `neighbors`
+//! Iterates over the tiles one step away from a position in the four cardinal directions,
+//! yielding the direction, position, and tile of each. Neighbors outside the map are skipped,
+//! so a corner has two and an edge has three.
+//!
 //! # Example Tile Mapping Description
 //! The `load` method interprets characters in a `.txt` file to initialize the level,
 //! with each character corresponding to a `TileType`.
 //! For example:
 //! - `'t'`: Wall
+//! - `'T'`: Cracked wall, drawn like a wall but broken through with a bomb or an axe, for secret passages
 //! - `'p'`: Player starting position
-//! - `'e'`: Enemy
+//! - `'e'`: Enemy that wanders at random
+//! - `'H'`: Enemy that patrols left and right, starting to the right
+//! - `'I'`: Enemy that patrols up and down, starting downward
+//! - `'J'`: Enemy that hunts down a nearby player, but only within its leash of where it started
 //! - `'s'`: Empty space
+//! - `'f'`: Checkpoint
+//! - `'0'`: Boss. A block of adjacent boss tiles, such as 2x2 or 3x3, is a single larger boss
+//! - `'W'`: Shallow water the player can swim across while they have stamina
+//! - `'u'`: Mud, which takes three moves to cross on levels with a move limit
+//! - `'y'`: Snow, where the player leaves a trail of footprints
+//! - `'!'`: A hidden trap, drawn as empty ground until someone steps on it
+//! - `'P'`, `'G'`: A pressure plate and a gate. Plates and gates are linked in reading order: the first
+//!   plate opens the first gate, the second plate the second gate, and so on
+//! - `'*'`: Spikes that rise and sink on a fixed rhythm, deadly while raised
+//! - `'='`: The track of a moving platform. Each straight run of track carries one platform,
+//!   which starts at the top or left end
+//! - `'S'`: A sign, showing the matching `# sign:` text of the header when stepped on
+//! - `'K'`: A switch, flipping the tiles of the matching `# switch:` line of the header each time it is stepped on
+//! - `'F'`: A companion, who follows the player once they walk up to it and holds off one guard in their place
+//! - `'^'`, `'V'`, `'<'`, `'>'`: One-way paths that can only be entered moving up, down, left, or right
+//! - `'U'`, `'Y'`, `'L'`, `'R'`: Conveyors that carry the player up, down, left, or right, a tile every tick
 //!
 //! Additional characters map to their respective `TileType` as defined in the `match` block.
 //!
 
-use crate::classes::types::{Position, TileType};
+use crate::classes::types::{Direction, ItemType, Position, TileType};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Patrol {
+    Wander,
+    Horizontal,
+    Vertical,
+    Hunt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Enemy {
+    pub pos: Position,
+    pub home: Position,
+    pub patrol: Patrol,
+    pub heading: Direction,
+}
+
+impl Enemy {
+    pub fn new(pos: Position) -> Self {
+        Self::patrolling(pos, Patrol::Wander)
+    }
+
+    pub fn patrolling(pos: Position, patrol: Patrol) -> Self {
+        let heading = match patrol {
+            Patrol::Vertical => Direction::Down,
+            Patrol::Wander | Patrol::Horizontal | Patrol::Hunt => Direction::Right,
+        };
+
+        Self {
+            pos,
+            home: pos,
+            patrol,
+            heading,
+        }
+    }
+
+    pub fn turn_around(&mut self) {
+        self.heading = self.heading.opposite();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Platform {
+    pub pos: Position,
+    pub start: Position,
+    pub end: Position,
+    heading_to_end: bool,
+}
 
+impl Platform {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self {
+            pos: start,
+            start,
+            end,
+            heading_to_end: true,
+        }
+    }
+
+    pub fn advance(&mut self) {
+        let target = if self.heading_to_end {
+            self.end
+        } else {
+            self.start
+        };
+
+        self.pos = Position {
+            row: self.pos.row + (target.row - self.pos.row).signum(),
+            col: self.pos.col + (target.col - self.pos.col).signum(),
+        };
+
+        if self.pos == target {
+            self.heading_to_end = !self.heading_to_end;
+        }
+    }
+
+    fn find_all(map: &[Vec<TileType>]) -> Vec<Platform> {
+        let is_track = |row: usize, col: usize| {
+            map.get(row).and_then(|tiles| tiles.get(col)) == Some(&TileType::MovingPlatform)
+        };
+
+        let mut platforms = Vec::new();
+        for (row, tiles) in map.iter().enumerate() {
+            for col in 0..tiles.len() {
+                // Only start from the top or left end of a run of track
+                let continues_left = col > 0 && is_track(row, col - 1);
+                let continues_up = row > 0 && is_track(row - 1, col);
+                if !is_track(row, col) || continues_left || continues_up {
+                    continue;
+                }
+
+                let (mut end_row, mut end_col) = (row, col);
+                if is_track(row, col + 1) {
+                    while is_track(row, end_col + 1) {
+                        end_col += 1;
+                    }
+                } else {
+                    while is_track(end_row + 1, col) {
+                        end_row += 1;
+                    }
+                }
+
+                platforms.push(Platform::new(
+                    Position {
+                        row: row as i16,
+                        col: col as i16,
+                    },
+                    Position {
+                        row: end_row as i16,
+                        col: end_col as i16,
+                    },
+                ));
+            }
+        }
+        platforms
+    }
+}
+
+// Directory the numbered level files are read from by default
+pub const DEFAULT_MAPS_DIR: &str = "maps";
+
+#[derive(Debug, PartialEq)]
+pub enum LevelError {
+    Empty,
+    PlayerStarts(usize),
+    NoExit,
+    InvalidHeader(String),
+    NotFound,
+    InvalidUtf8,
+    Io(String),
+    TooMany(char),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum KeyUse {
+    #[default]
+    Single,
+    Multi,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DoorMode {
+    #[default]
+    Persistent,
+    Closing,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LevelHeader {
+    pub move_limit: Option<u32>,
+    pub keys: KeyUse,
+    pub doors: DoorMode,
+    pub charmap: HashMap<char, char>,
+    pub signs: Vec<String>,
+    pub switches: Vec<Vec<Position>>,
+    pub required_items: Vec<ItemType>,
+    pub respawn_ticks: Option<u64>,
+    pub max_enemies: Option<usize>,
+    pub name: Option<String>,
+    pub par: Option<Duration>,
+}
+
+impl LevelHeader {
+    pub fn parse(lines: &[&str]) -> Result<Self, LevelError> {
+        let mut header = LevelHeader::default();
+        let mut in_charmap = false;
+
+        for line in lines {
+            let text = line.trim_start_matches(HEADER_PREFIX).trim();
+            if text.starts_with('[') && text.ends_with(']') {
+                in_charmap = text == "[charmap]";
+                continue;
+            }
+            if in_charmap {
+                if let Some((from, to)) = parse_char_mapping(text) {
+                    let Some(to) = to else {
+                        return Err(LevelError::InvalidHeader(line.to_string()));
+                    };
+                    header.charmap.insert(from, to);
+                    continue;
+                }
+            }
+
+            let Some((key, value)) = line.trim_start_matches(HEADER_PREFIX).split_once(':') else {
+                continue;
+            };
+
+            let invalid = || LevelError::InvalidHeader(line.to_string());
+            match (key.trim(), value.trim()) {
+                ("moves", value) => {
+                    header.move_limit = match value.parse::<u32>() {
+                        Ok(limit) if limit > 0 => Some(limit),
+                        _ => return Err(invalid()),
+                    };
+                }
+                ("keys", "single") => header.keys = KeyUse::Single,
+                ("keys", "multi") => header.keys = KeyUse::Multi,
+                ("doors", "persistent") => header.doors = DoorMode::Persistent,
+                ("doors", "closing") => header.doors = DoorMode::Closing,
+                ("sign", text) => header.signs.push(text.to_string()),
+                ("switch", tiles) => {
+                    let tiles = tiles
+                        .split_whitespace()
+                        .map(parse_position)
+                        .collect::<Option<Vec<_>>>()
+                        .filter(|tiles| !tiles.is_empty())
+                        .ok_or_else(invalid)?;
+                    header.switches.push(tiles);
+                }
+                ("name", text) => header.name = Some(text.to_string()),
+                ("par", value) => {
+                    header.par = match value.parse::<u64>() {
+                        Ok(seconds) if seconds > 0 => Some(Duration::from_secs(seconds)),
+                        _ => return Err(invalid()),
+                    };
+                }
+                ("respawn", value) => {
+                    header.respawn_ticks = match value.parse::<u64>() {
+                        Ok(ticks) if ticks > 0 => Some(ticks),
+                        _ => return Err(invalid()),
+                    };
+                }
+                ("max enemies", value) => {
+                    header.max_enemies = Some(value.parse::<usize>().map_err(|_| invalid())?);
+                }
+                ("requires", names) => {
+                    for name in names.split(',') {
+                        let item = ItemType::from_name(name.trim()).ok_or_else(invalid)?;
+                        header.required_items.push(item);
+                    }
+                }
+                ("keys" | "doors", _) => return Err(invalid()),
+                _ => {}
+            }
+        }
+
+        Ok(header)
+    }
+}
+
+// Reads a `ROW,COL` position of a `# switch:` line
+fn parse_position(text: &str) -> Option<Position> {
+    let (row, col) = text.split_once(',')?;
+    Some(Position {
+        row: row.trim().parse().ok()?,
+        col: col.trim().parse().ok()?,
+    })
+}
+
+// Header lines at the top of a map file start with this character
+const HEADER_PREFIX: char = '#';
+
+// Reads a `X = y` charmap line, with `None` in place of a target that isn't a single character
+fn parse_char_mapping(text: &str) -> Option<(char, Option<char>)> {
+    let mut chars = text.chars();
+    let from = chars.next()?;
+    let target = chars.as_str().trim_start().strip_prefix('=')?.trim();
+
+    let mut target_chars = target.chars();
+    let to = match (target_chars.next(), target_chars.next()) {
+        (Some(to), None) => Some(to),
+        _ => None,
+    };
+    Some((from, to))
+}
+
+// Splits map text into its header lines and the rows of tiles below them
+fn split_header(contents: &str) -> (Vec<&str>, Vec<&str>) {
+    let lines: Vec<&str> = contents.lines().collect();
+    let header_len = lines
+        .iter()
+        .take_while(|line| line.starts_with(HEADER_PREFIX))
+        .count();
+    let (header, rows) = lines.split_at(header_len);
+    (header.to_vec(), rows.to_vec())
+}
+
+// The index linking the `count`-th tile of its kind to its header line, as long as it fits in a `u8`
+fn link_index(count: usize, c: char) -> Result<u8, LevelError> {
+    u8::try_from(count).map_err(|_| LevelError::TooMany(c))
+}
+
+#[derive(Clone)]
 pub struct Level {
     pub map: Vec<Vec<TileType>>,
-    pub enemies: Vec<Position>,
+    pub enemies: Vec<Enemy>,
+    pub oni: Vec<Position>,
+    pub spawns: Vec<Position>,
+    pub platforms: Vec<Platform>,
     pub player_start: Position,
     pub map_size: (u8, u8),
+    pub header: LevelHeader,
 }
 
-impl Level {
-    pub fn load(level_number: usize) -> Option<Self> {
-        let filename = format!("maps/level_{}.txt", level_number);
-        if let Ok(contents) = fs::read_to_string(&filename) {
-            let mut map = Vec::new();
-            let mut enemies = Vec::new();
-            let mut player_start = Position { row: 0, col: 0 };
-
-            for (row, line) in contents.lines().enumerate() {
-                let mut map_row = Vec::new();
-                for (col, c) in line.chars().enumerate() {
-                    match c {
-                        't' => map_row.push(TileType::Wall),
-                        'b' => map_row.push(TileType::Bamboo),
-                        'm' => map_row.push(TileType::Mountain),
-                        'v' => map_row.push(TileType::Volcano),
-                        'n' => map_row.push(TileType::SnowMountain),
-                        'h' => map_row.push(TileType::Cottage),
-                        's' => map_row.push(TileType::Empty),
-                        'w' => map_row.push(TileType::Water),
-                        'z' => map_row.push(TileType::Lava),
-                        'a' => map_row.push(TileType::Axe),
-                        'l' => map_row.push(TileType::WoodLog),
-                        'c' => map_row.push(TileType::Canoe),
-                        '+' => map_row.push(TileType::Sword),
-                        'k' => map_row.push(TileType::Key),
-                        'd' => map_row.push(TileType::Door),
-                        'D' => map_row.push(TileType::DoorOpen),
-                        'r' => map_row.push(TileType::Rock),
-                        '@' => map_row.push(TileType::Bomb),
-                        'j' => map_row.push(TileType::Hook),
-                        '1' => map_row.push(TileType::HookStart),
-                        '2' => map_row.push(TileType::HookEnd),
-                        '-' => map_row.push(TileType::Link),
-                        'A' => map_row.push(TileType::CrystalA),
-                        'B' => map_row.push(TileType::CrystalB),
-                        'C' => map_row.push(TileType::CrystalC),
-                        '3' => map_row.push(TileType::FlameA),
-                        '4' => map_row.push(TileType::FlameB),
-                        '5' => map_row.push(TileType::FlameC),
-                        'i' => map_row.push(TileType::WindChime),
-                        'x' => map_row.push(TileType::DragonSword),
-                        'O' => map_row.push(TileType::Lantern),
-                        'o' => map_row.push(TileType::Oni),
-                        '0' => map_row.push(TileType::Boss),
-                        '$' => map_row.push(TileType::Princess),
-                        'p' => {
-                            map_row.push(TileType::Empty);
-                            player_start = Position {
-                                row: row as i16,
-                                col: col as i16,
-                            };
-                        }
-                        'e' => {
-                            map_row.push(TileType::Empty);
-                            enemies.push(Position {
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::Empty => write!(f, "The map has no tiles"),
+            LevelError::PlayerStarts(count) => {
+                write!(f, "Found {} player starts, expected exactly one", count)
+            }
+            LevelError::NoExit => write!(f, "There is no goal or princess"),
+            LevelError::InvalidHeader(line) => write!(f, "Invalid header line: {}", line),
+            LevelError::NotFound => write!(f, "The file doesn't exist"),
+            LevelError::InvalidUtf8 => {
+                write!(f, "The file isn't valid UTF-8; save it with UTF-8 encoding")
+            }
+            LevelError::Io(error) => write!(f, "Couldn't read the file: {}", error),
+            LevelError::TooMany(c) => {
+                write!(f, "More than {} '{}' tiles", u8::MAX as usize + 1, c)
+            }
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = LevelError;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut map = Vec::new();
+        let mut enemies = Vec::new();
+        let mut oni = Vec::new();
+        let mut spawns = Vec::new();
+        let mut player_start = Position { row: 0, col: 0 };
+        let mut player_starts = 0;
+        let mut plates: usize = 0;
+        let mut gates: usize = 0;
+        let mut signs: usize = 0;
+        let mut switches: usize = 0;
+
+        let (header_lines, rows) = split_header(contents);
+        let header = LevelHeader::parse(&header_lines)?;
+
+        for (row, line) in rows.iter().enumerate() {
+            let mut map_row = Vec::new();
+            for (col, c) in line.chars().enumerate() {
+                let c = header.charmap.get(&c).copied().unwrap_or(c);
+                match c {
+                    't' => map_row.push(TileType::Wall),
+                    'T' => map_row.push(TileType::CrackedWall),
+                    'b' => map_row.push(TileType::Bamboo),
+                    'm' => map_row.push(TileType::Mountain),
+                    'v' => map_row.push(TileType::Volcano),
+                    'n' => map_row.push(TileType::SnowMountain),
+                    'h' => map_row.push(TileType::Cottage),
+                    's' => map_row.push(TileType::Empty),
+                    'w' => map_row.push(TileType::Water),
+                    'W' => map_row.push(TileType::ShallowWater),
+                    'u' => map_row.push(TileType::Mud),
+                    'y' => map_row.push(TileType::Snow),
+                    '!' => map_row.push(TileType::HiddenTrap),
+                    '*' => map_row.push(TileType::Spikes),
+                    '=' => map_row.push(TileType::MovingPlatform),
+                    'z' => map_row.push(TileType::Lava),
+                    'a' => map_row.push(TileType::Axe),
+                    'l' => map_row.push(TileType::WoodLog),
+                    'c' => map_row.push(TileType::Canoe),
+                    '+' => map_row.push(TileType::Sword),
+                    'k' => map_row.push(TileType::Key),
+                    'd' => map_row.push(TileType::Door),
+                    'D' => map_row.push(TileType::DoorOpen),
+                    'r' => map_row.push(TileType::Rock),
+                    '@' => map_row.push(TileType::Bomb),
+                    'j' => map_row.push(TileType::Hook),
+                    '1' => map_row.push(TileType::HookStart),
+                    '2' => map_row.push(TileType::HookEnd),
+                    '-' => map_row.push(TileType::Link),
+                    'A' => map_row.push(TileType::CrystalA),
+                    'B' => map_row.push(TileType::CrystalB),
+                    'C' => map_row.push(TileType::CrystalC),
+                    '3' => map_row.push(TileType::FlameA),
+                    '4' => map_row.push(TileType::FlameB),
+                    '5' => map_row.push(TileType::FlameC),
+                    'i' => map_row.push(TileType::WindChime),
+                    'x' => map_row.push(TileType::DragonSword),
+                    'O' => map_row.push(TileType::Lantern),
+                    'P' => {
+                        map_row.push(TileType::Pressure(link_index(plates, c)?));
+                        plates += 1;
+                    }
+                    'G' => {
+                        map_row.push(TileType::Gate(link_index(gates, c)?));
+                        gates += 1;
+                    }
+                    'S' => {
+                        map_row.push(TileType::Sign(link_index(signs, c)?));
+                        signs += 1;
+                    }
+                    'K' => {
+                        map_row.push(TileType::Switch(link_index(switches, c)?));
+                        switches += 1;
+                    }
+                    'o' => {
+                        map_row.push(TileType::Empty);
+                        oni.push(Position {
+                            row: row as i16,
+                            col: col as i16,
+                        });
+                    }
+                    'E' => {
+                        map_row.push(TileType::Empty);
+                        spawns.push(Position {
+                            row: row as i16,
+                            col: col as i16,
+                        });
+                    }
+                    '0' => map_row.push(TileType::Boss),
+                    '$' => map_row.push(TileType::Princess),
+                    'F' => map_row.push(TileType::Companion),
+                    'f' => map_row.push(TileType::Checkpoint),
+                    '^' => map_row.push(TileType::OneWay(Direction::Up)),
+                    'V' => map_row.push(TileType::OneWay(Direction::Down)),
+                    '<' => map_row.push(TileType::OneWay(Direction::Left)),
+                    '>' => map_row.push(TileType::OneWay(Direction::Right)),
+                    'U' => map_row.push(TileType::Conveyor(Direction::Up)),
+                    'Y' => map_row.push(TileType::Conveyor(Direction::Down)),
+                    'L' => map_row.push(TileType::Conveyor(Direction::Left)),
+                    'R' => map_row.push(TileType::Conveyor(Direction::Right)),
+                    'p' => {
+                        map_row.push(TileType::Empty);
+                        player_starts += 1;
+                        player_start = Position {
+                            row: row as i16,
+                            col: col as i16,
+                        };
+                    }
+                    'e' | 'H' | 'I' | 'J' => {
+                        let patrol = match c {
+                            'H' => Patrol::Horizontal,
+                            'I' => Patrol::Vertical,
+                            'J' => Patrol::Hunt,
+                            _ => Patrol::Wander,
+                        };
+                        map_row.push(TileType::Empty);
+                        enemies.push(Enemy::patrolling(
+                            Position {
                                 row: row as i16,
                                 col: col as i16,
-                            });
-                        }
-                        'g' => {
-                            map_row.push(TileType::Goal);
-                        }
-                        _ => map_row.push(TileType::Empty),
+                            },
+                            patrol,
+                        ));
+                    }
+                    'g' => {
+                        map_row.push(TileType::Goal);
                     }
+                    _ => map_row.push(TileType::Empty),
                 }
-                map.push(map_row);
             }
+            map.push(map_row);
+        }
+
+        if map.is_empty() {
+            return Err(LevelError::Empty);
+        }
 
-            let map_size = (map.len() as u8, map[0].len() as u8);
+        if player_starts != 1 {
+            return Err(LevelError::PlayerStarts(player_starts));
+        }
 
-            Some(Level {
-                map,
-                enemies,
-                player_start,
-                map_size,
-            })
+        let map_size = (map.len() as u8, map[0].len() as u8);
+        let platforms = Platform::find_all(&map);
+
+        Ok(Level {
+            map,
+            enemies,
+            oni,
+            spawns,
+            platforms,
+            player_start,
+            map_size,
+            header,
+        })
+    }
+}
+
+impl Level {
+    pub fn load(level_number: usize) -> Option<Self> {
+        Self::load_from(DEFAULT_MAPS_DIR, level_number)
+    }
+
+    pub fn load_from<P: AsRef<Path>>(dir: P, level_number: usize) -> Option<Self> {
+        Self::load_path(Self::path(dir, level_number))
+    }
+
+    pub fn load_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        Self::read(path).ok()
+    }
+
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, LevelError> {
+        Self::read_contents(path)?.parse()
+    }
+
+    pub fn read_contents<P: AsRef<Path>>(path: P) -> Result<String, LevelError> {
+        let bytes = fs::read(path).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => LevelError::NotFound,
+            _ => LevelError::Io(error.to_string()),
+        })?;
+        String::from_utf8(bytes).map_err(|_| LevelError::InvalidUtf8)
+    }
+
+    pub fn path<P: AsRef<Path>>(dir: P, level_number: usize) -> PathBuf {
+        dir.as_ref().join(format!("level_{}.txt", level_number))
+    }
+
+    pub fn count<P: AsRef<Path>>(dir: P) -> usize {
+        (1..)
+            .take_while(|number| Self::path(&dir, *number).is_file())
+            .count()
+    }
+
+    pub fn packs<P: AsRef<Path>>(maps_dir: P) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(maps_dir) else {
+            return Vec::new();
+        };
+        let mut packs: Vec<String> = entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        packs.sort();
+        packs
+    }
+
+    pub fn check_exit(&self) -> Result<(), LevelError> {
+        let has_exit = self
+            .map
+            .iter()
+            .flatten()
+            .any(|tile| matches!(tile, TileType::Goal | TileType::Princess));
+
+        if has_exit {
+            Ok(())
         } else {
-            None
+            Err(LevelError::NoExit)
+        }
+    }
+
+    pub fn nearest_exit(&self, from: &Position) -> Option<Position> {
+        self.find_all(TileType::Goal)
+            .into_iter()
+            .chain(self.find_all(TileType::Princess))
+            .min_by_key(|exit| exit.manhattan(from))
+    }
+
+    pub fn is_goal_reachable(&self) -> bool {
+        let (rows, cols) = (self.map_size.0 as usize, self.map_size.1 as usize);
+        let mut visited = vec![vec![false; cols]; rows];
+        let mut stack = vec![self.player_start];
+
+        while let Some(pos) = stack.pop() {
+            let tile = match self.get_tile(&pos) {
+                Some(tile) => tile,
+                None => continue,
+            };
+            if visited[pos.row as usize][pos.col as usize] {
+                continue;
+            }
+            visited[pos.row as usize][pos.col as usize] = true;
+
+            match tile {
+                TileType::Goal | TileType::Princess => return true,
+                // Terrain that no item or puzzle can ever clear
+                TileType::Wall
+                | TileType::Bamboo
+                | TileType::Mountain
+                | TileType::Volcano
+                | TileType::SnowMountain
+                | TileType::Lantern
+                    if pos != self.player_start && !self.is_switch_linked(&pos) =>
+                {
+                    continue;
+                }
+                // The grappling hook links its start point to the hook end
+                TileType::HookStart => stack.extend(self.find_all(TileType::HookEnd)),
+                _ => {}
+            }
+
+            stack.extend(self.neighbors(&pos).map(|(_, next, _)| next));
+        }
+
+        false
+    }
+
+    // Whether a switch flips this tile between wall and open ground
+    fn is_switch_linked(&self, pos: &Position) -> bool {
+        self.header
+            .switches
+            .iter()
+            .flatten()
+            .any(|linked| linked == pos)
+    }
+
+    pub fn bounding_box_of(&self, tile_type: TileType) -> Option<(Position, Position)> {
+        let found = self.find_all(tile_type);
+        let top = found.iter().map(|pos| pos.row).min()?;
+        let bottom = found.iter().map(|pos| pos.row).max()?;
+        let left = found.iter().map(|pos| pos.col).min()?;
+        let right = found.iter().map(|pos| pos.col).max()?;
+
+        Some((
+            Position {
+                row: top,
+                col: left,
+            },
+            Position {
+                row: bottom,
+                col: right,
+            },
+        ))
+    }
+
+    fn find_all(&self, tile_type: TileType) -> Vec<Position> {
+        let mut found = Vec::new();
+        for (row, tiles) in self.map.iter().enumerate() {
+            for (col, &tile) in tiles.iter().enumerate() {
+                if tile == tile_type {
+                    found.push(Position {
+                        row: row as i16,
+                        col: col as i16,
+                    });
+                }
+            }
+        }
+        found
+    }
+
+    pub fn lint(contents: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let (_, rows) = split_header(contents);
+        let widths: Vec<usize> = rows.iter().map(|line| line.chars().count()).collect();
+        if let Some(&width) = widths.first() {
+            for (row, &row_width) in widths.iter().enumerate() {
+                if row_width != width {
+                    problems.push(format!(
+                        "Row {} has {} tiles, expected {}",
+                        row + 1,
+                        row_width,
+                        width
+                    ));
+                }
+            }
         }
+
+        let level: Level = match contents.parse() {
+            Ok(level) => level,
+            Err(err) => {
+                problems.push(err.to_string());
+                return problems;
+            }
+        };
+
+        if let Err(err) = level.check_exit() {
+            problems.push(err.to_string());
+        } else if problems.is_empty() && !level.is_goal_reachable() {
+            problems.push("The goal can't be reached from the player start".to_string());
+        }
+
+        problems
     }
 
     pub fn set_tile(&mut self, pos: &Position, tile_type: TileType) {
@@ -156,6 +846,42 @@ impl Level {
         }
     }
 
+    pub fn connected(&self, pos: &Position) -> Vec<Position> {
+        let Some(tile) = self.get_tile(pos) else {
+            return Vec::new();
+        };
+        let mut found = vec![*pos];
+        let mut index = 0;
+
+        while index < found.len() {
+            let next: Vec<Position> = self
+                .neighbors(&found[index])
+                .filter(|(_, next, other)| *other == tile && !found.contains(next))
+                .map(|(_, next, _)| next)
+                .collect();
+            found.extend(next);
+            index += 1;
+        }
+
+        found
+    }
+
+    pub fn has_enemy_at(&self, pos: &Position) -> bool {
+        self.enemies.iter().any(|enemy| enemy.pos == *pos)
+    }
+
+    pub fn neighbors(
+        &self,
+        pos: &Position,
+    ) -> impl Iterator<Item = (Direction, Position, TileType)> + '_ {
+        let pos = *pos;
+
+        Direction::ALL.into_iter().filter_map(move |dir| {
+            let next = pos.offset(dir);
+            self.get_tile(&next).map(|tile| (dir, next, tile))
+        })
+    }
+
     pub fn get_tile(&self, pos: &Position) -> Option<TileType> {
         if pos.row >= 0
             && pos.row < self.map_size.0 as i16
@@ -259,3 +985,307 @@ fn test_get_tile_returns_none_for_invalid_positions() {
         panic!("Could not load level 1 for testing");
     }
 }
+
+#[test]
+fn test_lint_reports_problems() {
+    assert!(Level::lint("ttt\ntpg\nttt").is_empty());
+
+    let problems = Level::lint("tttt\ntpg\nttt");
+    assert!(problems.iter().any(|p| p.contains("Row 2 has 3 tiles")));
+
+    let problems = Level::lint("ttt\ntsg\nttt");
+    assert!(problems.iter().any(|p| p.contains("Found 0 player starts")));
+
+    let problems = Level::lint("ttt\ntps\nttt");
+    assert!(problems.iter().any(|p| p.contains("no goal")));
+
+    let problems = Level::lint("ttttt\ntptgt\nttttt");
+    assert!(problems.iter().any(|p| p.contains("can't be reached")));
+
+    assert_eq!("".parse::<Level>().err(), Some(LevelError::Empty));
+}
+
+#[test]
+fn test_parse_rejects_missing_player_start() {
+    let result = "ttttt\ntssgt\nttttt".parse::<Level>();
+
+    assert_eq!(result.err(), Some(LevelError::PlayerStarts(0)));
+}
+
+#[test]
+fn test_parse_rejects_multiple_player_starts() {
+    let result = "ttttt\ntpsgt\ntspst\nttttt".parse::<Level>();
+
+    assert_eq!(result.err(), Some(LevelError::PlayerStarts(2)));
+}
+
+#[test]
+fn test_platform_tracks_are_parsed() {
+    let level: Level = "tttttt\ntp==st\nttss=t\ntgss=t\ntttttt".parse().unwrap();
+
+    assert_eq!(
+        level.platforms,
+        vec![
+            Platform::new(Position { row: 1, col: 2 }, Position { row: 1, col: 3 }),
+            Platform::new(Position { row: 2, col: 4 }, Position { row: 3, col: 4 }),
+        ]
+    );
+}
+
+#[test]
+fn test_neighbors_stay_inside_the_map() {
+    let level: Level = "pse\nsgs\ntst".parse().unwrap();
+    let directions = |pos| {
+        level
+            .neighbors(&pos)
+            .map(|(dir, _, _)| dir)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        directions(Position { row: 0, col: 0 }),
+        vec![Direction::Down, Direction::Right]
+    );
+    assert_eq!(
+        directions(Position { row: 2, col: 1 }),
+        vec![Direction::Up, Direction::Left, Direction::Right]
+    );
+    assert_eq!(directions(Position { row: 1, col: 1 }).len(), 4);
+
+    let (dir, pos, tile) = level
+        .neighbors(&Position { row: 0, col: 1 })
+        .find(|(_, _, tile)| *tile == TileType::Goal)
+        .unwrap();
+    assert_eq!(dir, Direction::Down);
+    assert_eq!(pos, Position { row: 1, col: 1 });
+    assert_eq!(tile, TileType::Goal);
+}
+
+#[test]
+fn test_check_exit_rejects_maps_without_goal() {
+    let level: Level = "tttt\ntp0t\ntttt".parse().unwrap();
+    assert_eq!(level.check_exit(), Err(LevelError::NoExit));
+
+    let level: Level = "tttt\ntpgt\ntttt".parse().unwrap();
+    assert_eq!(level.check_exit(), Ok(()));
+}
+
+#[test]
+fn test_patrol_enemies_are_parsed() {
+    let level: Level = "ttttt\ntpeJt\ntHIst\nttttt".parse().unwrap();
+    let patrols: Vec<Patrol> = level.enemies.iter().map(|enemy| enemy.patrol).collect();
+
+    assert_eq!(
+        patrols,
+        vec![
+            Patrol::Wander,
+            Patrol::Hunt,
+            Patrol::Horizontal,
+            Patrol::Vertical
+        ]
+    );
+    assert!(level.enemies.iter().all(|enemy| enemy.home == enemy.pos));
+    assert!(level.has_enemy_at(&Position { row: 2, col: 1 }));
+    assert_eq!(
+        level.get_tile(&Position { row: 2, col: 1 }),
+        Some(TileType::Empty)
+    );
+}
+
+#[test]
+fn test_header_sets_move_limit() {
+    let level: Level = "# A short puzzle\n# moves: 12\ntttt\ntpgt\ntttt"
+        .parse()
+        .unwrap();
+    assert_eq!(level.header.move_limit, Some(12));
+    assert_eq!(level.map_size, (3, 4));
+    assert_eq!(level.player_start, Position { row: 1, col: 1 });
+
+    let level: Level = "tttt\ntpgt\ntttt".parse().unwrap();
+    assert_eq!(level.header.move_limit, None);
+
+    let result = "# moves: many\ntttt\ntpgt\ntttt".parse::<Level>();
+    assert_eq!(
+        result.err(),
+        Some(LevelError::InvalidHeader("# moves: many".to_string()))
+    );
+}
+
+#[test]
+fn test_header_sets_door_modes() {
+    let level: Level = "# keys: multi\n# doors: closing\ntttt\ntpgt\ntttt"
+        .parse()
+        .unwrap();
+    assert_eq!(level.header.keys, KeyUse::Multi);
+    assert_eq!(level.header.doors, DoorMode::Closing);
+
+    let level: Level = "tttt\ntpgt\ntttt".parse().unwrap();
+    assert_eq!(level.header.keys, KeyUse::Single);
+    assert_eq!(level.header.doors, DoorMode::Persistent);
+
+    assert!("# doors: revolving\ntttt\ntpgt\ntttt"
+        .parse::<Level>()
+        .is_err());
+}
+
+#[test]
+fn test_bounding_box_covers_scattered_tiles() {
+    let level: Level = "tttttt\ntpskst\ntssssk\ntksgst\ntttttt".parse().unwrap();
+
+    assert_eq!(
+        level.bounding_box_of(TileType::Key),
+        Some((Position { row: 1, col: 1 }, Position { row: 3, col: 5 }))
+    );
+    assert_eq!(
+        level.bounding_box_of(TileType::Goal),
+        Some((Position { row: 3, col: 3 }, Position { row: 3, col: 3 }))
+    );
+    assert_eq!(level.bounding_box_of(TileType::Boss), None);
+}
+
+#[test]
+fn test_read_tells_file_errors_apart() {
+    let path = crate::classes::leaderboard::temp_path("read_errors");
+    let _ = fs::remove_file(&path);
+    assert_eq!(Level::read(&path).err(), Some(LevelError::NotFound));
+
+    // "tpgt" saved in Latin-1 with an accented character
+    fs::write(&path, b"tpgt\n\xe9\n").unwrap();
+    assert_eq!(Level::read(&path).err(), Some(LevelError::InvalidUtf8));
+    assert!(Level::load_path(&path).is_none());
+
+    fs::write(&path, "tpgt\ntsst\n").unwrap();
+    assert!(Level::read(&path).is_ok());
+
+    fs::write(&path, "tsgt\n").unwrap();
+    assert_eq!(Level::read(&path).err(), Some(LevelError::PlayerStarts(0)));
+
+    let dir = std::env::temp_dir();
+    assert!(matches!(Level::read(&dir), Err(LevelError::Io(_))));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_header_charmap_remaps_characters() {
+    let level: Level = "# [charmap]\n# █ = t\n# · = s\n# s = w\n████\n█p·█\n█sg█\n████"
+        .parse()
+        .unwrap();
+    assert_eq!(level.header.charmap.get(&'█'), Some(&'t'));
+    assert_eq!(level.map_size, (4, 4));
+    assert_eq!(
+        level.get_tile(&Position { row: 0, col: 0 }),
+        Some(TileType::Wall)
+    );
+    assert_eq!(
+        level.get_tile(&Position { row: 1, col: 2 }),
+        Some(TileType::Empty)
+    );
+
+    // A remapped built-in character takes on its new meaning
+    assert_eq!(
+        level.get_tile(&Position { row: 2, col: 1 }),
+        Some(TileType::Water)
+    );
+
+    let result = "# [charmap]\n# █ = tt\ntttt\ntpgt\ntttt".parse::<Level>();
+    assert_eq!(
+        result.err(),
+        Some(LevelError::InvalidHeader("# █ = tt".to_string()))
+    );
+}
+
+#[test]
+fn test_header_lists_required_items() {
+    let level: Level = "# requires: key, bomb\ntttt\ntpgt\ntttt".parse().unwrap();
+    assert_eq!(
+        level.header.required_items,
+        vec![ItemType::Key, ItemType::Bomb]
+    );
+
+    let result = "# requires: key, lightsaber\ntttt\ntpgt\ntttt".parse::<Level>();
+    assert_eq!(
+        result.err(),
+        Some(LevelError::InvalidHeader(
+            "# requires: key, lightsaber".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_header_names_the_level_and_its_par_time() {
+    let level: Level = "# name: The Bamboo Grove\n# par: 45\ntttt\ntpgt\ntttt"
+        .parse()
+        .unwrap();
+    assert_eq!(level.header.name.as_deref(), Some("The Bamboo Grove"));
+    assert_eq!(level.header.par, Some(Duration::from_secs(45)));
+
+    let result = "# par: soon\ntttt\ntpgt\ntttt".parse::<Level>();
+    assert_eq!(
+        result.err(),
+        Some(LevelError::InvalidHeader("# par: soon".to_string()))
+    );
+}
+
+#[test]
+fn test_header_links_switches_to_their_tiles() {
+    let level: Level = "# switch: 1,3 2,3\n# switch: 0,1\ntttt\ntpKt\ntKgt\ntttt"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        level.get_tile(&Position { row: 1, col: 2 }),
+        Some(TileType::Switch(0))
+    );
+    assert_eq!(
+        level.get_tile(&Position { row: 2, col: 1 }),
+        Some(TileType::Switch(1))
+    );
+    assert_eq!(
+        level.header.switches,
+        vec![
+            vec![Position { row: 1, col: 3 }, Position { row: 2, col: 3 }],
+            vec![Position { row: 0, col: 1 }],
+        ]
+    );
+
+    let result = "# switch: 1;3\ntttt\ntpgt\ntttt".parse::<Level>();
+    assert_eq!(
+        result.err(),
+        Some(LevelError::InvalidHeader("# switch: 1;3".to_string()))
+    );
+}
+
+#[test]
+fn test_too_many_switches_fail_to_parse() {
+    let row = "K".repeat(u8::MAX as usize + 2);
+    let result = format!("tpgt\n{}", row).parse::<Level>();
+    assert_eq!(result.err(), Some(LevelError::TooMany('K')));
+
+    let row = "K".repeat(u8::MAX as usize + 1);
+    assert!(format!("tpgt\n{}", row).parse::<Level>().is_ok());
+}
+
+#[test]
+fn test_a_wall_a_switch_opens_doesnt_block_the_goal() {
+    let level: Level = "# switch: 1,3\nttttt\ntpKtg\nttttt".parse().unwrap();
+    assert!(level.is_goal_reachable());
+
+    let level: Level = "ttttt\ntpKtg\nttttt".parse().unwrap();
+    assert!(!level.is_goal_reachable());
+}
+
+#[test]
+fn test_too_many_plates_or_gates_fail_to_parse() {
+    for c in ['P', 'G'] {
+        let row = c.to_string().repeat(u8::MAX as usize + 2);
+        let result = format!("tpgt\n{}", row).parse::<Level>();
+        assert_eq!(result.err(), Some(LevelError::TooMany(c)));
+    }
+}
+
+#[test]
+fn test_too_many_signs_fail_to_parse() {
+    let row = "S".repeat(u8::MAX as usize + 2);
+    let result = format!("tpgt\n{}", row).parse::<Level>();
+    assert_eq!(result.err(), Some(LevelError::TooMany('S')));
+}
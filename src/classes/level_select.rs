@@ -0,0 +1,123 @@
+//! The `LevelSelect` struct is a small menu for picking which level to start on.
+//!
+//! It lists every level, marks the ones already completed along with their best
+//! time, and keeps a cursor the player moves with the movement keys.
+//!
+//! # Fields
+//!
+//! - `best_times` (`Vec<Option<Duration>>`): The best time of each level, or `None` if it hasn't been completed.
+//! - `cursor` (`usize`): The index of the highlighted level.
+//! - `ascii` (`bool`): Draws the completion mark and cursor with plain ASCII characters.
+//!
+//! # Methods
+//!
+//! - `new`: Creates a menu from the best time of each level, with the cursor on level 1.
+//! - `move_up` / `move_down`: Moves the cursor, stopping at the first and last level.
+//! - `selected`: Returns the number of the highlighted level.
+//! - `compose`: Builds the text of the menu.
+//! - `render`: Clears the terminal and draws the menu, returning any I/O error.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut select = LevelSelect::new(best_times, config.ascii);
+//! select.move_down();
+//! game.load_level(select.selected());
+//! ```
+
+use crate::classes::leaderboard::Leaderboard;
+use crossterm::{
+    cursor::{Hide, MoveTo},
+    terminal::{Clear, ClearType},
+    ExecutableCommand,
+};
+use std::io::{self, stdout, Write};
+use std::time::Duration;
+
+pub struct LevelSelect {
+    best_times: Vec<Option<Duration>>,
+    cursor: usize,
+    ascii: bool,
+}
+
+impl LevelSelect {
+    pub fn new(best_times: Vec<Option<Duration>>, ascii: bool) -> Self {
+        Self {
+            best_times,
+            cursor: 0,
+            ascii,
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < self.best_times.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.cursor + 1
+    }
+
+    pub fn compose(&self) -> String {
+        let mut frame = String::from(" Select a level\r\n\r\n");
+
+        for (index, best) in self.best_times.iter().enumerate() {
+            let cursor = if index == self.cursor { ">" } else { " " };
+            let mark = match (best, self.ascii) {
+                (Some(_), true) => "[x]",
+                (Some(_), false) => "✅",
+                (None, true) => "[ ]",
+                (None, false) => "⬜",
+            };
+            frame.push_str(&format!(" {} {} Level {:>2}", cursor, mark, index + 1));
+            if let Some(time) = best {
+                frame.push_str(&format!("  Best: {}", Leaderboard::format_time(*time)));
+            }
+            frame.push_str("\r\n");
+        }
+
+        frame.push_str("\r\n w/s: Move | Enter: Start | q: Quit");
+        frame
+    }
+
+    pub fn render(&self) -> io::Result<()> {
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(MoveTo(0, 0))?;
+        stdout.execute(Hide)?;
+
+        write!(stdout, "{}", self.compose())?;
+        stdout.flush()
+    }
+}
+
+#[test]
+fn test_level_select_cursor_clamps_at_ends() {
+    let mut select = LevelSelect::new(vec![None; 3], false);
+    assert_eq!(select.selected(), 1);
+
+    select.move_up();
+    assert_eq!(select.selected(), 1);
+
+    select.move_down();
+    select.move_down();
+    select.move_down();
+    assert_eq!(select.selected(), 3);
+
+    select.move_up();
+    assert_eq!(select.selected(), 2);
+}
+
+#[test]
+fn test_level_select_marks_completed_levels() {
+    let select = LevelSelect::new(vec![Some(Duration::from_secs(42)), None], true);
+    let frame = select.compose();
+
+    assert!(frame.contains("> [x] Level  1  Best: 00:42"));
+    assert!(frame.contains("  [ ] Level  2\r\n"));
+}
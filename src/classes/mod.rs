@@ -1,5 +1,22 @@
+pub mod achievements;
+pub mod carry_select;
+pub mod config;
+pub mod demo;
 pub mod game;
+pub mod input;
+pub mod leaderboard;
 pub mod level;
+pub mod level_select;
+pub mod options;
 pub mod player;
+pub mod recipes;
+pub mod run_log;
+pub mod settings;
+#[cfg(feature = "svg")]
+pub mod svg;
+pub mod theme;
+pub mod timing;
 pub mod types;
 pub mod ui;
+pub mod validate;
+pub mod width;
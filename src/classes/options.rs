@@ -0,0 +1,166 @@
+//! The `OptionsMenu` struct is the in-game options screen.
+//!
+//! It lists the runtime `Settings` with their current values and keeps a cursor
+//! the player moves with the movement keys. Toggling an on/off option flips it,
+//! and toggling the difficulty cycles through easy, normal, and hard.
+//!
+//! # Fields
+//!
+//! - `settings` (`Settings`): The settings being edited.
+//! - `cursor` (`usize`): The index of the highlighted option.
+//!
+//! # Methods
+//!
+//! - `new`: Creates a menu for the given settings, with the cursor on the first option.
+//! - `move_up` / `move_down`: Moves the cursor, stopping at the first and last option.
+//! - `toggle`: Changes the highlighted option.
+//! - `settings`: Returns the edited settings.
+//! - `compose`: Builds the text of the menu.
+//! - `render`: Clears the terminal and draws the menu, returning any I/O error.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut menu = OptionsMenu::new(settings);
+//! menu.move_down();
+//! menu.toggle();
+//! game.apply_settings(&menu.settings());
+//! ```
+
+use crate::classes::config::Difficulty;
+use crate::classes::settings::Settings;
+use crossterm::{
+    cursor::{Hide, MoveTo},
+    terminal::{Clear, ClearType},
+    ExecutableCommand,
+};
+use std::io::{self, stdout, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OptionItem {
+    Color,
+    Ascii,
+    Sound,
+    Difficulty,
+}
+
+const ITEMS: [OptionItem; 4] = [
+    OptionItem::Color,
+    OptionItem::Ascii,
+    OptionItem::Sound,
+    OptionItem::Difficulty,
+];
+
+pub struct OptionsMenu {
+    settings: Settings,
+    cursor: usize,
+}
+
+impl OptionsMenu {
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            settings,
+            cursor: 0,
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < ITEMS.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        let settings = &mut self.settings;
+
+        match ITEMS[self.cursor] {
+            OptionItem::Color => settings.color = !settings.color,
+            OptionItem::Ascii => settings.ascii = !settings.ascii,
+            OptionItem::Sound => settings.sound = !settings.sound,
+            OptionItem::Difficulty => {
+                settings.difficulty = match settings.difficulty {
+                    Difficulty::Easy => Difficulty::Normal,
+                    Difficulty::Normal => Difficulty::Hard,
+                    Difficulty::Hard => Difficulty::Easy,
+                }
+            }
+        }
+    }
+
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
+    pub fn compose(&self) -> String {
+        let mut frame = String::from(" Options\r\n\r\n");
+
+        for (index, item) in ITEMS.iter().enumerate() {
+            let cursor = if index == self.cursor { ">" } else { " " };
+            let (label, value) = match item {
+                OptionItem::Color => ("Color (ASCII)", on_off(self.settings.color)),
+                OptionItem::Ascii => ("ASCII", on_off(self.settings.ascii)),
+                OptionItem::Sound => ("Sound", on_off(self.settings.sound)),
+                OptionItem::Difficulty => ("Difficulty", self.settings.difficulty.name()),
+            };
+            frame.push_str(&format!(" {} {:<14} {}\r\n", cursor, label, value));
+        }
+
+        frame.push_str("\r\n Difficulty applies from the next level");
+        frame.push_str("\r\n w/s: Move | Enter: Change | o: Back");
+        frame
+    }
+
+    pub fn render(&self) -> io::Result<()> {
+        let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(MoveTo(0, 0))?;
+        stdout.execute(Hide)?;
+
+        write!(stdout, "{}", self.compose())?;
+        stdout.flush()
+    }
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+#[test]
+fn test_options_toggle_highlighted_setting() {
+    let start = Settings {
+        color: false,
+        ascii: false,
+        sound: true,
+        difficulty: Difficulty::Normal,
+    };
+    let mut menu = OptionsMenu::new(start);
+
+    menu.toggle();
+    assert!(menu.settings().color);
+    menu.toggle();
+    assert!(!menu.settings().color);
+
+    menu.move_down();
+    menu.move_down();
+    menu.toggle();
+    assert!(!menu.settings().sound);
+
+    // The cursor stops at the last option, and difficulty wraps around
+    menu.move_down();
+    menu.move_down();
+    menu.toggle();
+    assert_eq!(menu.settings().difficulty, Difficulty::Hard);
+    menu.toggle();
+    assert_eq!(menu.settings().difficulty, Difficulty::Easy);
+
+    assert!(menu.compose().contains("> Difficulty     easy"));
+    assert!(menu.compose().contains("  Sound          Off"));
+}
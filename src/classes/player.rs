@@ -1,30 +1,45 @@
 //! Module containing the `Player` struct and its associated methods.
 //!
 //! The `Player` struct is used to represent a player, including their position,
-//! inventory, and movement functionality within a game. The struct provides
+//! facing direction, inventory, and movement functionality within a game. The struct provides
 //! methods to manipulate the player's state, such as moving, managing an inventory,
 //! and resetting or committing movement.
 //!
 //! ## Structs
 //!
-//! - `Player`: The main struct representing a player in the game.
+//! - `Player`: The main struct representing a player in the game. Besides the position and inventory,
+//!   it tracks `stamina`, which is spent swimming through shallow water and recovered on land.
+//!
+//! ## Enums
+//!
+//! - `MoveResult`: What happened when the player tried to take a step: they moved, were blocked,
+//!   interacted with something, reached the next level, or cleared the game.
 //!
 //! ## Methods
 //!
 //! ### `Player`
 //!
 //! - `new`: Creates a new `Player` instance with default values.
+//! - `with_items`: Creates a new `Player` that starts out carrying the given items.
 //! - `reset_position`: Resets the player's position to a specified value.
-//! - `move_up`: Sets a pending move to one row up.
-//! - `move_down`: Sets a pending move to one row down.
-//! - `move_left`: Sets a pending move to one column left.
-//! - `move_right`: Sets a pending move to one column right.
+//! - `move_up`: Sets a pending move to one row up and faces up.
+//! - `move_down`: Sets a pending move to one row down and faces down.
+//! - `move_left`: Sets a pending move to one column left and faces left.
+//! - `move_right`: Sets a pending move to one column right and faces right.
+//! - `move_up_left`, `move_up_right`, `move_down_left`, `move_down_right`: Set a pending diagonal move.
+//! - `move_in`: Sets a pending move one step in the given direction and faces that way.
 //! - `get_pending_move`: Retrieves the pending move, if any.
 //! - `commit_move`: Commits the pending move and updates the player's position.
 //! - `cancel_move`: Cancels the pending move.
+//! - `swim`: Spends one point of stamina on a stroke through shallow water, if the player has any left.
+//! - `rest`: Recovers one point of stamina, up to `MAX_STAMINA`.
 //! - `add_item`: Adds an item to the player's inventory.
 //! - `has_item`: Checks if the player has a specific item in their inventory.
+//! - `inventory_contains_all` / `inventory_contains_any`: Check for several items at once: every one of them, or at least one.
+//!   No items at all are trivially all there, but none of them is.
 //! - `remove_item`: Removes an item from the player's inventory if it exists.
+//! - `sorted_inventory`: The inventory in display order: tools, then keys, then quest items,
+//!   no matter the order they were picked up in.
 //!
 //! ## Usage
 //!
@@ -54,12 +69,26 @@
 //! player.remove_item(ItemType::Sword);
 //! ```
 
-use crate::classes::types::{ItemType, Position};
+use crate::classes::types::{Direction, ItemType, Position};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Moved,
+    Blocked,
+    Interacted,
+    NextLevel,
+    GameClear,
+}
+
+// Number of strokes the player can swim before they need to rest on land
+pub const MAX_STAMINA: u8 = 5;
 
 pub struct Player {
     pub pos: Position,
+    pub facing: Direction,
     pending_move: Option<Position>,
     pub inventory: Vec<ItemType>,
+    pub stamina: u8,
 }
 
 impl Default for Player {
@@ -72,8 +101,17 @@ impl Player {
     pub fn new() -> Self {
         Self {
             pos: Position { row: 0, col: 0 },
+            facing: Direction::Down,
             pending_move: None,
             inventory: Vec::new(),
+            stamina: MAX_STAMINA,
+        }
+    }
+
+    pub fn with_items(items: Vec<ItemType>) -> Self {
+        Self {
+            inventory: items,
+            ..Self::new()
         }
     }
 
@@ -83,31 +121,40 @@ impl Player {
     }
 
     pub fn move_up(&mut self) {
-        self.pending_move = Some(Position {
-            row: self.pos.row - 1,
-            col: self.pos.col,
-        });
+        self.move_in(Direction::Up);
     }
 
     pub fn move_down(&mut self) {
-        self.pending_move = Some(Position {
-            row: self.pos.row + 1,
-            col: self.pos.col,
-        });
+        self.move_in(Direction::Down);
     }
 
     pub fn move_left(&mut self) {
-        self.pending_move = Some(Position {
-            row: self.pos.row,
-            col: self.pos.col - 1,
-        });
+        self.move_in(Direction::Left);
     }
 
     pub fn move_right(&mut self) {
-        self.pending_move = Some(Position {
-            row: self.pos.row,
-            col: self.pos.col + 1,
-        });
+        self.move_in(Direction::Right);
+    }
+
+    pub fn move_up_left(&mut self) {
+        self.move_in(Direction::UpLeft);
+    }
+
+    pub fn move_up_right(&mut self) {
+        self.move_in(Direction::UpRight);
+    }
+
+    pub fn move_down_left(&mut self) {
+        self.move_in(Direction::DownLeft);
+    }
+
+    pub fn move_down_right(&mut self) {
+        self.move_in(Direction::DownRight);
+    }
+
+    pub fn move_in(&mut self, dir: Direction) {
+        self.facing = dir;
+        self.pending_move = Some(self.pos.offset(dir));
     }
 
     pub fn get_pending_move(&self) -> Option<Position> {
@@ -125,6 +172,18 @@ impl Player {
         self.pending_move = None;
     }
 
+    pub fn swim(&mut self) -> bool {
+        if self.stamina == 0 {
+            return false;
+        }
+        self.stamina -= 1;
+        true
+    }
+
+    pub fn rest(&mut self) {
+        self.stamina = (self.stamina + 1).min(MAX_STAMINA);
+    }
+
     pub fn add_item(&mut self, item: ItemType) {
         self.inventory.push(item);
     }
@@ -133,11 +192,25 @@ impl Player {
         self.inventory.contains(&item)
     }
 
+    pub fn inventory_contains_all(&self, items: &[ItemType]) -> bool {
+        items.iter().all(|item| self.has_item(*item))
+    }
+
+    pub fn inventory_contains_any(&self, items: &[ItemType]) -> bool {
+        items.iter().any(|item| self.has_item(*item))
+    }
+
     pub fn remove_item(&mut self, item: ItemType) {
         if let Some(index) = self.inventory.iter().position(|&i| i == item) {
             self.inventory.remove(index);
         }
     }
+
+    pub fn sorted_inventory(&self) -> Vec<ItemType> {
+        let mut items = self.inventory.clone();
+        items.sort_by_key(|item| (item.category(), *item));
+        items
+    }
 }
 
 #[test]
@@ -239,3 +312,109 @@ fn test_player_inventory_empty_check() {
     player.remove_item(ItemType::Key);
     assert!(player.inventory.is_empty());
 }
+
+#[test]
+fn test_player_facing_follows_movement() {
+    let mut player = Player::new();
+    player.reset_position(Position { row: 5, col: 5 });
+    assert_eq!(player.facing, Direction::Down);
+
+    player.move_left();
+    assert_eq!(player.facing, Direction::Left);
+    player.commit_move();
+
+    player.move_up();
+    assert_eq!(player.facing, Direction::Up);
+
+    // Facing changes even when the move is blocked
+    player.cancel_move();
+    assert_eq!(player.facing, Direction::Up);
+}
+
+#[test]
+fn test_player_diagonal_move() {
+    let mut player = Player::new();
+    player.reset_position(Position { row: 5, col: 5 });
+
+    player.move_up_left();
+    assert_eq!(player.get_pending_move(), Some(Position { row: 4, col: 4 }));
+    assert_eq!(player.facing, Direction::UpLeft);
+
+    player.move_down_right();
+    assert_eq!(player.get_pending_move(), Some(Position { row: 6, col: 6 }));
+
+    player.move_up_right();
+    player.commit_move();
+    assert_eq!(player.pos, Position { row: 4, col: 6 });
+
+    player.move_down_left();
+    player.commit_move();
+    assert_eq!(player.pos, Position { row: 5, col: 5 });
+}
+
+#[test]
+fn test_player_stamina() {
+    let mut player = Player::new();
+    assert_eq!(player.stamina, MAX_STAMINA);
+
+    for _ in 0..MAX_STAMINA {
+        assert!(player.swim());
+    }
+    assert_eq!(player.stamina, 0);
+    assert!(!player.swim());
+
+    player.rest();
+    assert_eq!(player.stamina, 1);
+
+    for _ in 0..MAX_STAMINA {
+        player.rest();
+    }
+    assert_eq!(player.stamina, MAX_STAMINA);
+}
+
+#[test]
+fn test_sorted_inventory_ignores_pickup_order() {
+    let picked_up = [
+        ItemType::DragonSword,
+        ItemType::Key,
+        ItemType::Hook,
+        ItemType::Axe,
+        ItemType::WindChime,
+        ItemType::Key,
+    ];
+    let forward = Player::with_items(picked_up.to_vec());
+    let backward = Player::with_items(picked_up.iter().rev().copied().collect());
+
+    let expected = vec![
+        ItemType::Axe,
+        ItemType::Hook,
+        ItemType::Key,
+        ItemType::Key,
+        ItemType::WindChime,
+        ItemType::DragonSword,
+    ];
+    assert_eq!(forward.sorted_inventory(), expected);
+    assert_eq!(backward.sorted_inventory(), expected);
+    // The pickup order itself is left alone
+    assert_eq!(forward.inventory, picked_up.to_vec());
+}
+
+#[test]
+fn test_inventory_contains_all_and_any() {
+    let mut player = Player::new();
+    let wanted = [ItemType::Key, ItemType::Bomb];
+
+    assert!(!player.inventory_contains_all(&wanted));
+    assert!(!player.inventory_contains_any(&wanted));
+    assert!(player.inventory_contains_all(&[]));
+    assert!(!player.inventory_contains_any(&[]));
+
+    player.add_item(ItemType::Axe);
+    player.add_item(ItemType::Key);
+    assert!(!player.inventory_contains_all(&wanted));
+    assert!(player.inventory_contains_any(&wanted));
+
+    player.add_item(ItemType::Bomb);
+    assert!(player.inventory_contains_all(&wanted));
+    assert!(player.inventory_contains_any(&wanted));
+}
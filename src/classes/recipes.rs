@@ -0,0 +1,143 @@
+//! The crafting recipes: what happens when the player walks into a tile while holding an item.
+//!
+//! Each `Recipe` pairs a held item with the tile it is used on, optionally
+//! requiring a particular tile next to it, and describes the outcome. Adding
+//! a new combination only takes a new entry in `RECIPES`.
+//!
+//! # Fields
+//!
+//! - `item` (`ItemType`): The item the player must hold. It is used up by the recipe.
+//! - `target` (`TileType`): The tile the player walks into.
+//! - `beside` (`Option<(Direction, TileType)>`): A tile that must lie in the given direction from the target.
+//! - `result` (`TileType`): What the target tile turns into.
+//! - `beside_result` (`Option<TileType>`): What the tile beside the target turns into, if anything.
+//! - `passes` (`bool`): Whether the player steps onto the target afterwards.
+//! - `message` (`&str`): The message shown when the recipe is used.
+//!
+//! # Functions
+//!
+//! - `find`: Returns the first recipe that applies when the player walks into the given position.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! if let Some(recipe) = recipes::find(&game.level, &player, &pos) {
+//!     println!("{}", recipe.message);
+//! }
+//! ```
+
+use crate::classes::level::Level;
+use crate::classes::player::Player;
+use crate::classes::types::{Direction, ItemType, Position, TileType};
+
+#[derive(Debug, PartialEq)]
+pub struct Recipe {
+    pub item: ItemType,
+    pub target: TileType,
+    pub beside: Option<(Direction, TileType)>,
+    pub result: TileType,
+    pub beside_result: Option<TileType>,
+    pub passes: bool,
+    pub message: &'static str,
+}
+
+// Checked in order, so more specific recipes come first
+pub const RECIPES: &[Recipe] = &[
+    // A log by the water becomes a canoe on it
+    Recipe {
+        item: ItemType::Axe,
+        target: TileType::WoodLog,
+        beside: Some((Direction::Right, TileType::Water)),
+        result: TileType::Empty,
+        beside_result: Some(TileType::Canoe),
+        passes: false,
+        message: "   You crafted a canoe ",
+    },
+    Recipe {
+        item: ItemType::Axe,
+        target: TileType::WoodLog,
+        beside: None,
+        result: TileType::Empty,
+        beside_result: None,
+        passes: true,
+        message: "   You chopped the log ",
+    },
+    Recipe {
+        item: ItemType::Key,
+        target: TileType::Door,
+        beside: None,
+        result: TileType::DoorOpen,
+        beside_result: None,
+        passes: false,
+        message: "   You opened the door ",
+    },
+    Recipe {
+        item: ItemType::Bomb,
+        target: TileType::Rock,
+        beside: None,
+        result: TileType::Empty,
+        beside_result: None,
+        passes: false,
+        message: "  💥 The rock crumbles to dust 💥",
+    },
+    // Cracked walls hide secret passages
+    Recipe {
+        item: ItemType::Bomb,
+        target: TileType::CrackedWall,
+        beside: None,
+        result: TileType::Empty,
+        beside_result: None,
+        passes: false,
+        message: "  💥 The wall gives way to a hidden passage 💥",
+    },
+    Recipe {
+        item: ItemType::Axe,
+        target: TileType::CrackedWall,
+        beside: None,
+        result: TileType::Empty,
+        beside_result: None,
+        passes: false,
+        message: "   You hack through the cracked wall to a hidden passage ",
+    },
+];
+
+pub fn find(level: &Level, player: &Player, pos: &Position) -> Option<&'static Recipe> {
+    let target = level.get_tile(pos)?;
+
+    RECIPES.iter().find(|recipe| {
+        recipe.target == target
+            && player.has_item(recipe.item)
+            && recipe.beside.is_none_or(|beside| {
+                level
+                    .neighbors(pos)
+                    .any(|(dir, _, tile)| (dir, tile) == beside)
+            })
+    })
+}
+
+#[test]
+fn test_canoe_recipe_takes_precedence_by_the_water() {
+    let mut level: Level = "tttt\ntplw\ntslt\ntttt".parse().unwrap();
+    let mut player = Player::new();
+    let by_water = Position { row: 1, col: 2 };
+    let inland = Position { row: 2, col: 2 };
+
+    assert_eq!(find(&level, &player, &by_water), None);
+
+    player.add_item(ItemType::Axe);
+    assert_eq!(
+        find(&level, &player, &by_water).unwrap().result,
+        TileType::Empty
+    );
+    assert_eq!(
+        find(&level, &player, &by_water).unwrap().beside_result,
+        Some(TileType::Canoe)
+    );
+    assert!(find(&level, &player, &inland).unwrap().passes);
+
+    level.set_tile(&by_water.offset(Direction::Right), TileType::Empty);
+    assert_eq!(
+        find(&level, &player, &by_water).unwrap().beside_result,
+        None
+    );
+}
@@ -0,0 +1,117 @@
+//! The `RunSummary` struct sums up a whole run of the game, appended to a log file on exit.
+//!
+//! Every run adds one line to the log, so the file grows into a history of past
+//! runs for looking back on. Each line is a list of `key=value` fields:
+//!
+//! ```text
+//! ended=<unix time in seconds> level=<level reached> moves=<moves> deaths=<deaths> kills=<kills> time=<seconds> items=<items collected>
+//! ```
+//!
+//! # Fields
+//!
+//! - `ended` (`u64`): When the run ended, in seconds since the Unix epoch.
+//! - `level` (`usize`): The level the player had reached.
+//! - `moves` (`u32`): The moves made over the whole run, counted as for the move limit.
+//! - `deaths` (`u32`): The times the player died.
+//! - `kills` (`u32`): The enemies, oni, and bosses slain.
+//! - `time` (`Duration`): How long the run lasted.
+//! - `items` (`u32`): The items the player picked up, found, or was given along the way.
+//!
+//! # Methods
+//!
+//! - `to_line`: Formats the summary as a line of the log, ending in a newline.
+//! - `append`: Adds the summary to the end of a log file, creating the file if needed.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! game.run_summary().append("runs.txt")?;
+//! ```
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+// File a summary of every run is appended to when the game exits
+pub const RUN_LOG_FILE: &str = "runs.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSummary {
+    pub ended: u64,
+    pub level: usize,
+    pub moves: u32,
+    pub deaths: u32,
+    pub kills: u32,
+    pub time: Duration,
+    pub items: u32,
+}
+
+impl RunSummary {
+    pub fn to_line(&self) -> String {
+        format!(
+            "ended={} level={} moves={} deaths={} kills={} time={} items={}\n",
+            self.ended,
+            self.level,
+            self.moves,
+            self.deaths,
+            self.kills,
+            self.time.as_secs(),
+            self.items
+        )
+    }
+
+    pub fn append<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(self.to_line().as_bytes())
+    }
+}
+
+#[test]
+fn test_run_summary_line_lists_every_field() {
+    let summary = RunSummary {
+        ended: 1_700_000_000,
+        level: 4,
+        moves: 312,
+        deaths: 2,
+        kills: 5,
+        time: Duration::from_millis(754_900),
+        items: 7,
+    };
+
+    assert_eq!(
+        summary.to_line(),
+        "ended=1700000000 level=4 moves=312 deaths=2 kills=5 time=754 items=7\n"
+    );
+}
+
+#[test]
+fn test_run_summaries_are_appended() {
+    let path = crate::classes::leaderboard::temp_path("run_log");
+    let _ = std::fs::remove_file(&path);
+    let summary = RunSummary {
+        ended: 0,
+        level: 1,
+        moves: 10,
+        deaths: 0,
+        kills: 0,
+        time: Duration::from_secs(20),
+        items: 1,
+    };
+
+    summary.append(&path).unwrap();
+    RunSummary {
+        level: 2,
+        ..summary
+    }
+    .append(&path)
+    .unwrap();
+
+    let log = std::fs::read_to_string(&path).unwrap();
+    let levels: Vec<&str> = log
+        .lines()
+        .map(|line| line.split(' ').nth(1).unwrap())
+        .collect();
+    assert_eq!(levels, vec!["level=1", "level=2"]);
+    let _ = std::fs::remove_file(&path);
+}
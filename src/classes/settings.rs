@@ -0,0 +1,196 @@
+//! The `Settings` struct holds the display and gameplay options that can be changed while playing.
+//!
+//! Settings start out from the command line options and can then be changed on
+//! the options screen. The options changed there are saved to a small text file, one
+//! option per line, and used as the defaults the next time the game starts:
+//!
+//! ```text
+//! color off
+//! ascii on
+//! sound on
+//! difficulty normal
+//! ```
+//!
+//! A missing or corrupt file is ignored, so a broken file never prevents the game
+//! from starting. It is simply overwritten the next time the settings change.
+//!
+//! Command line options only last for the run they are given on, so `--ascii` once
+//! doesn't turn ASCII mode on for good; `--no-ascii`, `--no-color`, and `--sound`
+//! override saved settings the other way.
+//!
+//! # Fields
+//!
+//! - `color` (`bool`): Colors the map glyphs in ASCII mode.
+//! - `ascii` (`bool`): Renders the game with plain ASCII characters instead of emoji.
+//! - `sound` (`bool`): Rings the terminal bell on pickups, deaths, and level clears.
+//! - `difficulty` (`Difficulty`): Scales the number of enemies on each level.
+//!
+//! # Methods
+//!
+//! - `from_config`: Takes the settings from a `Config`.
+//! - `apply_to`: Copies the settings onto a `Config`.
+//! - `with_changes`: Takes over the options that differ between two other settings, such as before and
+//!   after the options screen, leaving the rest as they were. This keeps one-off command line options out
+//!   of the saved file.
+//! - `load`: Reads settings from a file, returning `None` if it is missing or corrupt.
+//! - `save`: Writes the settings to a file.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut config = Config::default();
+//! if let Some(settings) = Settings::load(&config.settings_file) {
+//!     settings.apply_to(&mut config);
+//! }
+//!
+//! Settings::from_config(&config).save(&config.settings_file)?;
+//! ```
+
+use crate::classes::config::{Config, Difficulty};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// File the settings chosen on the options screen are saved to
+pub const SETTINGS_FILE: &str = "settings.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub color: bool,
+    pub ascii: bool,
+    pub sound: bool,
+    pub difficulty: Difficulty,
+}
+
+impl Settings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            color: config.color,
+            ascii: config.ascii,
+            sound: config.sound,
+            difficulty: config.difficulty,
+        }
+    }
+
+    pub fn apply_to(&self, config: &mut Config) {
+        config.color = self.color;
+        config.ascii = self.ascii;
+        config.sound = self.sound;
+        config.difficulty = self.difficulty;
+    }
+
+    pub fn with_changes(&self, before: &Settings, after: &Settings) -> Self {
+        let pick = |saved, before, after| if before != after { after } else { saved };
+        Self {
+            color: pick(self.color, before.color, after.color),
+            ascii: pick(self.ascii, before.ascii, after.ascii),
+            sound: pick(self.sound, before.sound, after.sound),
+            difficulty: if before.difficulty != after.difficulty {
+                after.difficulty
+            } else {
+                self.difficulty
+            },
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        Self::parse(&contents)
+    }
+
+    fn parse(contents: &str) -> Option<Self> {
+        let mut settings = Self::from_config(&Config::default());
+
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.split_whitespace();
+            let key = fields.next()?;
+            let value = fields.next()?;
+
+            match key {
+                "color" => settings.color = parse_switch(value)?,
+                "ascii" => settings.ascii = parse_switch(value)?,
+                "sound" => settings.sound = parse_switch(value)?,
+                "difficulty" => settings.difficulty = Difficulty::parse(value)?,
+                _ => return None,
+            }
+        }
+
+        Some(settings)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let contents = format!(
+            "color {}\nascii {}\nsound {}\ndifficulty {}\n",
+            switch_name(self.color),
+            switch_name(self.ascii),
+            switch_name(self.sound),
+            self.difficulty.name()
+        );
+
+        fs::write(path, contents)
+    }
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn switch_name(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+#[test]
+fn test_settings_round_trip() {
+    let path = crate::classes::leaderboard::temp_path("settings_round_trip");
+    let _ = fs::remove_file(&path);
+    assert_eq!(Settings::load(&path), None);
+
+    let settings = Settings {
+        color: true,
+        ascii: true,
+        sound: false,
+        difficulty: Difficulty::Hard,
+    };
+    settings.save(&path).unwrap();
+    assert_eq!(Settings::load(&path), Some(settings));
+
+    let mut config = Config::default();
+    settings.apply_to(&mut config);
+    assert_eq!(Settings::from_config(&config), settings);
+
+    fs::write(&path, "ascii maybe\n").unwrap();
+    assert_eq!(Settings::load(&path), None);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_with_changes_only_takes_the_options_changed() {
+    let saved = Settings::from_config(&Config::default());
+    // `--ascii --no-sound` for this run only, then color turned on in the options screen
+    let before = Settings {
+        ascii: true,
+        sound: false,
+        ..saved
+    };
+    let after = Settings {
+        color: true,
+        ..before
+    };
+
+    assert_eq!(
+        saved.with_changes(&before, &after),
+        Settings {
+            color: true,
+            ..saved
+        }
+    );
+}
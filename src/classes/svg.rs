@@ -0,0 +1,137 @@
+//! Renders levels as SVG images, for level thumbnails in a gallery.
+//!
+//! Only built with the `svg` feature. Every tile is drawn as a square filled
+//! with a flat color for its kind of terrain, and the player start and the
+//! guards as dots on top, so the picture needs no emoji font and looks the
+//! same everywhere. It shares nothing with the terminal renderer in `ui`.
+//!
+//! # Constants
+//!
+//! - `SVG_CELL_SIZE`: The width and height of a tile in the image, in pixels.
+//!
+//! # Methods
+//!
+//! - `Level::render_svg`: Returns the whole map as an SVG document.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let level = Level::load(3).unwrap();
+//! std::fs::write("level_3.svg", level.render_svg())?;
+//! ```
+
+use crate::classes::level::Level;
+use crate::classes::types::{Position, TileType};
+use std::fmt::Write;
+
+pub const SVG_CELL_SIZE: usize = 16;
+
+impl Level {
+    pub fn render_svg(&self) -> String {
+        let (rows, cols) = (self.map_size.0 as usize, self.map_size.1 as usize);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+            cols * SVG_CELL_SIZE,
+            rows * SVG_CELL_SIZE,
+            cols * SVG_CELL_SIZE,
+            rows * SVG_CELL_SIZE
+        );
+
+        for (row, tiles) in self.map.iter().enumerate() {
+            for (col, tile) in tiles.iter().enumerate() {
+                writeln!(
+                    svg,
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    col * SVG_CELL_SIZE,
+                    row * SVG_CELL_SIZE,
+                    SVG_CELL_SIZE,
+                    SVG_CELL_SIZE,
+                    tile_fill(*tile)
+                )
+                .unwrap();
+            }
+        }
+
+        // The characters are dots, so they never hide the tile they stand on
+        let characters = std::iter::once((self.player_start, "#00bcd4"))
+            .chain(self.enemies.iter().map(|enemy| (enemy.pos, "#e53935")))
+            .chain(self.oni.iter().map(|pos| (*pos, "#8e24aa")));
+        for (pos, fill) in characters {
+            write_dot(&mut svg, &pos, fill);
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn write_dot(svg: &mut String, pos: &Position, fill: &str) {
+    let half = SVG_CELL_SIZE / 2;
+    writeln!(
+        svg,
+        "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+        pos.col as usize * SVG_CELL_SIZE + half,
+        pos.row as usize * SVG_CELL_SIZE + half,
+        half - 2,
+        fill
+    )
+    .unwrap();
+}
+
+fn tile_fill(tile: TileType) -> &'static str {
+    match tile {
+        // A hidden trap stays hidden in thumbnails too
+        TileType::Empty | TileType::HiddenTrap | TileType::Checkpoint => "#e8e0c8",
+        TileType::Wall | TileType::CrackedWall | TileType::Bamboo => "#2e7d32",
+        TileType::Mountain | TileType::SnowMountain | TileType::Volcano => "#795548",
+        TileType::Mud => "#6d4c41",
+        TileType::Snow => "#fafafa",
+        TileType::Water | TileType::ShallowWater => "#1e88e5",
+        TileType::Lava | TileType::FlameA | TileType::FlameB | TileType::FlameC => "#f4511e",
+        TileType::Goal | TileType::Princess => "#fdd835",
+        TileType::Door | TileType::Gate(_) | TileType::Rock | TileType::Lantern => "#5d4037",
+        TileType::Trap | TileType::Spikes => "#b71c1c",
+        TileType::Boss => "#6a1b9a",
+        TileType::DoorOpen
+        | TileType::GateOpen(_)
+        | TileType::Tomb
+        | TileType::Canoe
+        | TileType::Alembic
+        | TileType::Link
+        | TileType::OneWay(_)
+        | TileType::Conveyor(_)
+        | TileType::Pressure(_)
+        | TileType::Switch(_)
+        | TileType::MovingPlatform
+        | TileType::HookStart
+        | TileType::HookEnd
+        | TileType::Cottage
+        | TileType::Sign(_) => "#bcaaa4",
+        // Whatever the player can pick up or recruit
+        TileType::Axe
+        | TileType::WoodLog
+        | TileType::Sword
+        | TileType::Key
+        | TileType::Bomb
+        | TileType::Hook
+        | TileType::CrystalA
+        | TileType::CrystalB
+        | TileType::CrystalC
+        | TileType::WindChime
+        | TileType::DragonSword
+        | TileType::Companion => "#ffb300",
+    }
+}
+
+#[test]
+fn test_render_svg_draws_a_rect_per_tile() {
+    let level: Level = "tttt\ntpgt\ntest\ntttt".parse().unwrap();
+    let svg = level.render_svg();
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"64\" height=\"64\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<rect ").count(), 16);
+
+    // The player start and the guard are drawn over their tiles
+    assert_eq!(svg.matches("<circle ").count(), 2);
+}
@@ -0,0 +1,235 @@
+//! The `Theme` struct is the palette the map is colored with in colored ASCII mode.
+//!
+//! A theme gives a color to each category of things on the map. Three themes are
+//! built in, `classic`, `highcontrast`, and `solarized`, and `--theme` picks one of
+//! them by name or reads a theme file, one category per line:
+//!
+//! ```text
+//! # Walls in a darker shade
+//! base solarized
+//! wall dark_green
+//! water #268bd2
+//! ```
+//!
+//! Colors are crossterm's color names (`red`, `dark_red`, `grey`, `dark_grey`, ...)
+//! or `#rrggbb` values. The `base` line names the built-in theme the file starts
+//! from, `classic` if it has none, and any category the file leaves out keeps the
+//! color of its base. Blank lines and lines starting with `#` are skipped.
+//!
+//! # Fields
+//!
+//! - `player` (`Color`): The player.
+//! - `enemy` (`Color`): Patrol guards, and the danger zone they could step onto next.
+//! - `oni` (`Color`): The oni.
+//! - `platform` (`Color`): Moving platforms.
+//! - `ally` (`Color`): The princess and the companion.
+//! - `hint` (`Color`): The hinted next step toward the goal.
+//! - `goal` (`Color`): The goal and checkpoints.
+//! - `wall` (`Color`): Walls, cracked walls, and bamboo.
+//! - `water` (`Color`): Deep and shallow water.
+//! - `hazard` (`Color`): Lava, sprung traps, and spikes.
+//!
+//! # Constants
+//!
+//! - `THEMES`: The names of the built-in themes.
+//!
+//! # Methods
+//!
+//! - `named`: Returns the built-in theme with the given name.
+//! - `load`: Reads a theme file.
+//! - `resolve`: Returns the built-in theme with the given name, or else reads the theme file at that path.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let theme = Theme::resolve("solarized")?;
+//! let glyph = "@ ".with(theme.player);
+//! ```
+
+use crossterm::style::Color;
+use std::fs;
+use std::path::Path;
+
+pub const THEMES: [&str; 3] = ["classic", "highcontrast", "solarized"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub player: Color,
+    pub enemy: Color,
+    pub oni: Color,
+    pub platform: Color,
+    pub ally: Color,
+    pub hint: Color,
+    pub goal: Color,
+    pub wall: Color,
+    pub water: Color,
+    pub hazard: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            player: Color::Cyan,
+            enemy: Color::Red,
+            oni: Color::Magenta,
+            platform: Color::DarkYellow,
+            ally: Color::Cyan,
+            hint: Color::Green,
+            goal: Color::Yellow,
+            wall: Color::DarkGreen,
+            water: Color::Blue,
+            hazard: Color::Red,
+        }
+    }
+}
+
+impl Theme {
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "classic" => Some(Self::default()),
+            // Bright colors only, with white walls that stand out on a dark background
+            "highcontrast" => Some(Self {
+                platform: Color::Yellow,
+                wall: Color::White,
+                ..Self::default()
+            }),
+            "solarized" => Some(Self {
+                player: rgb(0x2a, 0xa1, 0x98),
+                enemy: rgb(0xdc, 0x32, 0x2f),
+                oni: rgb(0xd3, 0x36, 0x82),
+                platform: rgb(0xcb, 0x4b, 0x16),
+                ally: rgb(0x6c, 0x71, 0xc4),
+                hint: rgb(0x85, 0x99, 0x00),
+                goal: rgb(0xb5, 0x89, 0x00),
+                wall: rgb(0x58, 0x6e, 0x75),
+                water: rgb(0x26, 0x8b, 0xd2),
+                hazard: rgb(0xcb, 0x4b, 0x16),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read theme {}: {}", path.display(), error))?;
+        Self::parse(&contents)
+    }
+
+    pub fn resolve(value: &str) -> Result<Self, String> {
+        match Self::named(value) {
+            Some(theme) => Ok(theme),
+            None => Self::load(value),
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let lines = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let mut theme = Self::default();
+        for (index, line) in lines.enumerate() {
+            let (key, value) = line
+                .split_once(char::is_whitespace)
+                .map(|(key, value)| (key, value.trim()))
+                .ok_or(format!("Theme line needs a category and a color: {}", line))?;
+
+            // The base comes first, so it never overwrites the colors after it
+            if key == "base" {
+                if index > 0 {
+                    return Err("The base of a theme must be its first line".to_string());
+                }
+                theme = Self::named(value).ok_or(format!("Unknown theme: {}", value))?;
+                continue;
+            }
+
+            let color = parse_color(value).ok_or(format!("Unknown color: {}", value))?;
+            match key {
+                "player" => theme.player = color,
+                "enemy" => theme.enemy = color,
+                "oni" => theme.oni = color,
+                "platform" => theme.platform = color,
+                "ally" => theme.ally = color,
+                "hint" => theme.hint = color,
+                "goal" => theme.goal = color,
+                "wall" => theme.wall = color,
+                "water" => theme.water = color,
+                "hazard" => theme.hazard = color,
+                _ => return Err(format!("Unknown theme category: {}", key)),
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |at: usize| u8::from_str_radix(&hex[at..at + 2], 16).ok();
+        return Some(rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+
+    match value {
+        "black" => Some(Color::Black),
+        "dark_grey" => Some(Color::DarkGrey),
+        "red" => Some(Color::Red),
+        "dark_red" => Some(Color::DarkRed),
+        "green" => Some(Color::Green),
+        "dark_green" => Some(Color::DarkGreen),
+        "yellow" => Some(Color::Yellow),
+        "dark_yellow" => Some(Color::DarkYellow),
+        "blue" => Some(Color::Blue),
+        "dark_blue" => Some(Color::DarkBlue),
+        "magenta" => Some(Color::Magenta),
+        "dark_magenta" => Some(Color::DarkMagenta),
+        "cyan" => Some(Color::Cyan),
+        "dark_cyan" => Some(Color::DarkCyan),
+        "white" => Some(Color::White),
+        "grey" => Some(Color::Grey),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_built_in_themes_resolve_by_name() {
+    for name in THEMES {
+        assert!(Theme::named(name).is_some());
+    }
+    assert_eq!(Theme::resolve("classic"), Ok(Theme::default()));
+    assert_eq!(Theme::named("sepia"), None);
+}
+
+#[test]
+fn test_theme_file_overrides_its_base() {
+    let path = crate::classes::leaderboard::temp_path("theme_file");
+    fs::write(
+        &path,
+        "# Darker walls\nbase solarized\n\nwall dark_green\nwater #102030\n",
+    )
+    .unwrap();
+
+    let theme = Theme::resolve(path.to_str().unwrap()).unwrap();
+    assert_eq!(theme.wall, Color::DarkGreen);
+    assert_eq!(theme.water, rgb(0x10, 0x20, 0x30));
+    // Everything else comes from the base
+    assert_eq!(theme.goal, Theme::named("solarized").unwrap().goal);
+
+    fs::write(&path, "wall plaid\n").unwrap();
+    assert_eq!(Theme::load(&path), Err("Unknown color: plaid".to_string()));
+    fs::write(&path, "roof red\n").unwrap();
+    assert!(Theme::load(&path).is_err());
+    fs::write(&path, "wall red\nbase classic\n").unwrap();
+    assert!(Theme::load(&path).is_err());
+
+    let _ = fs::remove_file(&path);
+    assert!(Theme::load(&path).is_err());
+}
@@ -0,0 +1,179 @@
+//! Helpers for frame pacing in the main loop.
+//!
+//! Rendering happens at a fixed frame rate, while input is polled continuously
+//! in between frames. These helpers compute how long the loop may wait before
+//! the next frame is due.
+//!
+//! The game world itself advances on a separate, fixed clock of `TICK_DURATION`
+//! ticks, so enemies and platforms move at the same pace whatever the frame rate.
+//!
+//! With `--debug-perf`, the main loop also measures how long rendering and
+//! updating the world take, smoothing the numbers with a `MovingAverage` so
+//! the HUD doesn't flicker between wildly different values.
+//!
+//! # Constants
+//!
+//! - `TICK_DURATION`: The time between two ticks of the game clock.
+//! - `PERF_SAMPLES`: The number of samples the performance overlay averages over.
+//! - `MAX_CATCH_UP_TICKS`: The most ticks the main loop runs in one go to catch up. A loop that fell further
+//!   behind, such as after a screen that waited for a key, drops the backlog rather than fast-forwarding the world.
+//!
+//! # Structs
+//!
+//! - `MovingAverage`: The average of the most recent durations recorded, up to a fixed window.
+//! - `PerfStats`: The averaged render, frame, and update times shown by the performance overlay.
+//!
+//! # Functions
+//!
+//! - `frame_duration`: Returns the duration of a single frame for a target FPS.
+//! - `time_until_next_frame`: Returns how long to wait given the time already spent in the current frame.
+//! - `scaled`: Stretches or shrinks a duration by the `--speed` factor; a speed of 0.5 doubles it.
+//! - `tick_duration`: The time between two ticks of the game clock at a given speed.
+//! - `scaled_fps`: The frame rate at a given speed, never below one frame per second.
+//! - `catch_up`: Returns how many ticks came due by now and when the next one is, capped at `MAX_CATCH_UP_TICKS`.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+pub const TICK_DURATION: Duration = Duration::from_millis(100);
+pub const PERF_SAMPLES: usize = 30;
+pub const MAX_CATCH_UP_TICKS: u32 = 5;
+
+pub struct MovingAverage {
+    samples: VecDeque<Duration>,
+    window: usize,
+}
+
+impl MovingAverage {
+    pub fn new(window: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(window),
+            window: window.max(1),
+        }
+    }
+
+    pub fn record(&mut self, sample: Duration) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PerfStats {
+    pub render: Duration,
+    pub frame: Duration,
+    pub update: Duration,
+}
+
+pub fn scaled(duration: Duration, speed: f64) -> Duration {
+    duration.div_f64(speed)
+}
+
+pub fn tick_duration(speed: f64) -> Duration {
+    scaled(TICK_DURATION, speed)
+}
+
+pub fn catch_up(next_tick: Instant, now: Instant, tick: Duration) -> (u32, Instant) {
+    if now < next_tick {
+        return (0, next_tick);
+    }
+
+    let behind = (now - next_tick).as_nanos() / tick.as_nanos().max(1);
+    if behind >= MAX_CATCH_UP_TICKS as u128 {
+        // Too far behind to be a slow frame: pick up the clock from now
+        return (MAX_CATCH_UP_TICKS, now + tick);
+    }
+    let due = behind as u32 + 1;
+    (due, next_tick + tick * due)
+}
+
+pub fn scaled_fps(fps: u32, speed: f64) -> u32 {
+    (fps as f64 * speed).round().max(1.0) as u32
+}
+
+pub fn frame_duration(fps: u32) -> Duration {
+    Duration::from_secs(1) / fps.max(1)
+}
+
+pub fn time_until_next_frame(fps: u32, elapsed: Duration) -> Duration {
+    frame_duration(fps).saturating_sub(elapsed)
+}
+
+#[test]
+fn test_time_until_next_frame() {
+    let frame = frame_duration(10);
+    assert_eq!(frame, Duration::from_millis(100));
+
+    assert_eq!(time_until_next_frame(10, Duration::ZERO), frame);
+    assert_eq!(
+        time_until_next_frame(10, Duration::from_millis(40)),
+        Duration::from_millis(60)
+    );
+
+    // Frames that ran over budget should not wait at all
+    assert_eq!(
+        time_until_next_frame(10, Duration::from_millis(150)),
+        Duration::ZERO
+    );
+
+    // Higher frame rates leave less time per frame
+    assert!(time_until_next_frame(30, Duration::ZERO) < frame);
+}
+
+#[test]
+fn test_speed_scales_durations() {
+    assert_eq!(tick_duration(1.0), TICK_DURATION);
+    assert_eq!(tick_duration(0.5), TICK_DURATION * 2);
+    assert_eq!(tick_duration(2.0), TICK_DURATION / 2);
+    assert_eq!(scaled(Duration::from_secs(3), 0.5), Duration::from_secs(6));
+
+    assert_eq!(scaled_fps(10, 0.5), 5);
+    assert_eq!(scaled_fps(10, 0.01), 1);
+}
+
+#[test]
+fn test_moving_average_keeps_the_latest_samples() {
+    let mut average = MovingAverage::new(3);
+    assert_eq!(average.average(), Duration::ZERO);
+
+    average.record(Duration::from_millis(10));
+    average.record(Duration::from_millis(20));
+    assert_eq!(average.average(), Duration::from_millis(15));
+
+    average.record(Duration::from_millis(30));
+    average.record(Duration::from_millis(40));
+    // The oldest sample has dropped out of the window
+    assert_eq!(average.average(), Duration::from_millis(30));
+}
+
+#[test]
+fn test_catch_up_runs_the_ticks_that_came_due() {
+    let tick = Duration::from_millis(100);
+    let start = Instant::now();
+
+    assert_eq!(catch_up(start + tick, start, tick), (0, start + tick));
+    assert_eq!(catch_up(start, start, tick), (1, start + tick));
+    assert_eq!(
+        catch_up(start, start + tick * 2 + tick / 2, tick),
+        (3, start + tick * 3)
+    );
+}
+
+#[test]
+fn test_catch_up_drops_a_long_backlog() {
+    let tick = Duration::from_millis(100);
+    let start = Instant::now();
+    // Say a victory screen kept the loop waiting for a minute
+    let now = start + Duration::from_secs(60);
+
+    assert_eq!(catch_up(start, now, tick), (MAX_CATCH_UP_TICKS, now + tick));
+}
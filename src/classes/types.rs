@@ -14,10 +14,45 @@
 //! - `row` (i16): The row index of the position.
 //! - `col` (i16): The column index of the position.
 //!
+//! Methods:
+//! - `offset(dir)`: Returns the position one step away in the given direction.
+//! - `neighbor(dir, distance)`: Returns the position `distance` steps away in the given direction.
+//! - `manhattan(other)`: The number of cardinal steps between two positions. The oni use it to
+//!   decide when they see the player and which step brings them closer.
+//! - `chebyshev(other)`: The number of steps between two positions when diagonal steps count as one,
+//!   so positions within a distance form a square. The Wind Chime stuns enemies within such a square.
+//!
 //! Example:
 //! ```rust,ignore
 //! let position = Position { row: 5, col: 3 };
 //! println!("{:?}", position); // Outputs: Position { row: 5, col: 3 }
+//! println!("{:?}", position.offset(Direction::Up)); // Outputs: Position { row: 4, col: 3 }
+//! ```
+//!
+//! ### `Direction`
+//! Represents a direction the player can move or face.
+//!
+//! Variants:
+//! - `Up`, `Down`, `Left`, `Right`
+//! - `UpLeft`, `UpRight`, `DownLeft`, `DownRight`: Diagonals, only used when diagonal movement is enabled.
+//!
+//! Methods:
+//! - `delta()`: Returns the `(row, col)` change of a single step in this direction.
+//! - `opposite()`: Returns the direction pointing the other way.
+//! - `is_diagonal()`: Checks whether the direction is one of the diagonals.
+//! - `between(from, to)`: The direction of a single step from one position to an adjacent one, if any.
+//! - `toward(from, to)`: The compass direction from one position to any other. It is diagonal only
+//!   when neither the row nor the column difference is more than twice the other.
+//!
+//! Constants:
+//! - `Direction::ALL`: The four cardinal directions, useful for iterating over neighbors.
+//! - `Direction::DIAGONALS`: The four diagonal directions.
+//!
+//! Example:
+//! ```rust,ignore
+//! let facing = Direction::Left;
+//! assert_eq!(facing.delta(), (0, -1));
+//! assert_eq!(facing.opposite(), Direction::Right);
 //! ```
 //!
 //! ### `CollisionType`
@@ -29,6 +64,7 @@
 //! - `Blocking(BlockingType)`: Represents a blocking element.
 //! - `Goal`: Represents the goal position.
 //! - `Princess`: Represents the princess tile.
+//! - `OutOfBounds`: The position lies outside the map.
 //!
 //! Example:
 //! ```rust,ignore
@@ -61,6 +97,9 @@
 //! Variants:
 //! - `Wall`, `Bamboo`, `Mountain`, etc.: Various blocking terrain types.
 //!
+//! Methods:
+//! - `message()`: A short explanation shown when the player bumps into this blocker.
+//!
 //! Example:
 //! ```rust,ignore
 //! let block = BlockingType::Wall;
@@ -76,6 +115,16 @@
 //! Variants:
 //! - `Axe`, `Sword`, `Key`, `Bomb`, etc.: Various collectible or usable items.
 //!
+//! Constants:
+//! - `ALL`: Every item, in declaration order.
+//!
+//! Methods:
+//! - `description()`: A short explanation of what the item does, shown in the inventory overlay.
+//! - `from_name(name)`: Looks up an item by its lowercase name, such as `axe` or `windchime`.
+//! - `tile()`: The tile the item lies on the map as, before it is picked up.
+//! - `glyph(mode)`: The glyph shown for the item in the inventory, the same as its tile's.
+//! - `category()`: The `ItemCategory` the item belongs to, which decides where it's listed in the inventory.
+//!
 //! Example:
 //! ```rust,ignore
 //! let item = ItemType::Key;
@@ -85,12 +134,48 @@
 //! }
 //! ```
 //!
+//! ### `GlyphMode`
+//! Which set of glyphs the map is drawn with.
+//!
+//! Variants:
+//! - `Ascii`: Plain ASCII characters, for terminals without emoji support.
+//! - `Emoji`: Emoji, the default.
+//!
+//! ### `ItemCategory`
+//! Groups items for display, in the order the inventory lists them.
+//!
+//! Variants:
+//! - `Tool`: Gear used on the map, such as the axe, sword, bomb, and hook.
+//! - `KeyItem`: Keys for locked doors.
+//! - `Quest`: Story items, the Wind Chime and the Dragon Sword.
+//!
 //! ### `TileType`
 //! Represents different tile types in the game map.
 //!
 //! Variants:
 //! - Examples include `Empty`, `Wall`, `Goal`, `Princess`, `Cottage`, etc.
 //!
+//! Constants:
+//! - `ALL`: Every tile variant once, in the order the legend overlay lists them. Variants that carry
+//!   data appear with a single representative value.
+//!
+//! Methods:
+//! - `name()`: A short lowercase name for the tile, used when describing the surroundings in words.
+//! - `description()`: A one-line explanation of the tile, shown in the legend overlay.
+//! - `glyph(mode)`: The two-column glyph drawn for the tile in the given `GlyphMode`, with spikes raised.
+//! - `glyph_when(mode, spikes_raised)`: The same glyph with spikes in the given phase. This is the one
+//!   place that maps tiles to glyphs; every renderer goes through it.
+//! - `is_walkable()`: Checks whether the player can step on the tile freely. This covers plain
+//!   terrain and the tiles left behind by interactions, such as an opened door (`DoorOpen`),
+//!   a looted cottage (`Tomb`), a crafted `Canoe`, a hook `Link`, or a used crystal (`Alembic`).
+//!   Checkpoints are walkable too; stepping on one only moves the respawn point. So are pressure plates
+//!   and open gates, which carry the id linking each plate to its gate.
+//! - `movement_cost()`: How many moves stepping onto the tile takes from the move limit of a level.
+//!   Most tiles cost one; wading through shallow water costs two, and slogging through mud three.
+//! - `allows_entry(direction)`: Checks whether the tile can be entered while moving in the given direction.
+//!   Only `OneWay(direction)` tiles restrict this: they must be entered moving along their arrow,
+//!   and a diagonal move counts when one of its components matches.
+//!
 //! Example:
 //! ```rust,ignore
 //! let tile = TileType::Goal;
@@ -99,13 +184,147 @@
 //!     _ => println!("This is a different tile."),
 //! }
 //! ```
+//!
+//! ### `GameEvent`
+//! Something notable that happened during play, reported by the game so other systems,
+//! such as achievements, can react to it.
+//!
+//! Variants:
+//! - `LevelStarted(level)`: A level was loaded and the player is at its start.
+//! - `PlayerDied`: The player died and respawned.
+//! - `EnemySlain`: The player defeated an enemy, the boss, or an oni.
+//! - `LevelCleared { level, time, moves }`: The player reached the goal of a level.
+//!
+//! Example:
+//! ```rust,ignore
+//! match event {
+//!     GameEvent::LevelCleared { time, .. } => println!("Cleared in {:?}", time),
+//!     _ => {}
+//! }
+//! ```
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Position {
     pub row: i16,
     pub col: i16,
 }
 
+impl Position {
+    pub fn offset(&self, dir: Direction) -> Position {
+        self.neighbor(dir, 1)
+    }
+
+    pub fn manhattan(&self, other: &Position) -> i32 {
+        (self.row as i32 - other.row as i32).abs() + (self.col as i32 - other.col as i32).abs()
+    }
+
+    pub fn chebyshev(&self, other: &Position) -> i32 {
+        (self.row as i32 - other.row as i32)
+            .abs()
+            .max((self.col as i32 - other.col as i32).abs())
+    }
+
+    pub fn neighbor(&self, dir: Direction, distance: i16) -> Position {
+        let (dy, dx) = dir.delta();
+        Position {
+            row: self.row + dy * distance,
+            col: self.col + dx * distance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub const DIAGONALS: [Direction; 4] = [
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    pub fn delta(&self) -> (i16, i16) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    pub fn between(from: &Position, to: &Position) -> Option<Direction> {
+        let delta = (to.row - from.row, to.col - from.col);
+        Self::ALL
+            .into_iter()
+            .chain(Self::DIAGONALS)
+            .find(|dir| dir.delta() == delta)
+    }
+
+    pub fn toward(from: &Position, to: &Position) -> Option<Direction> {
+        let (dy, dx) = (to.row - from.row, to.col - from.col);
+        if dy == 0 && dx == 0 {
+            return None;
+        }
+
+        // An axis counts unless the other one clearly dominates
+        let vertical = if dy.abs() * 2 >= dx.abs() {
+            dy.signum()
+        } else {
+            0
+        };
+        let horizontal = if dx.abs() * 2 >= dy.abs() {
+            dx.signum()
+        } else {
+            0
+        };
+
+        Self::ALL
+            .into_iter()
+            .chain(Self::DIAGONALS)
+            .find(|dir| dir.delta() == (vertical, horizontal))
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        Self::DIAGONALS.contains(self)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::DownRight => Direction::UpLeft,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CollisionType {
     None,
@@ -113,6 +332,7 @@ pub enum CollisionType {
     Blocking(BlockingType),
     Goal,
     Princess,
+    OutOfBounds,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -129,6 +349,12 @@ pub enum InteractiveType {
     Enemy,
     Oni,
     Boss,
+    HiddenTrap,
+    Trap,
+    Spikes,
+    Companion,
+    Lantern,
+    CrackedWall,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -144,9 +370,35 @@ pub enum BlockingType {
     FlameB,
     FlameC,
     Lantern,
+    OneWay,
+    Exhausted,
+    Track,
+    Gate,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+impl BlockingType {
+    pub fn message(&self) -> &'static str {
+        match self {
+            BlockingType::Wall => "   The forest is too dense to pass ",
+            BlockingType::Bamboo => "   The bamboo is too thick ",
+            BlockingType::Mountain => "   The mountain is too steep to climb ",
+            BlockingType::Water => "   The water is too deep ",
+            BlockingType::Volcano => "   The volcano is far too hot ",
+            BlockingType::Lava => "   The lava would burn you to a crisp ",
+            BlockingType::SnowMountain => "   The snowy peak is impassable ",
+            BlockingType::FlameA | BlockingType::FlameB | BlockingType::FlameC => {
+                "   A magic flame blocks the way "
+            }
+            BlockingType::Lantern => "   The lantern stands firmly in place ",
+            BlockingType::OneWay => "   The path only runs the other way ",
+            BlockingType::Exhausted => "   You're too tired to swim — rest on land first ",
+            BlockingType::Track => "   Wait for the platform to come by ",
+            BlockingType::Gate => "   The gate is shut — something must weigh down its plate ",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ItemType {
     Axe,
     Sword,
@@ -157,6 +409,81 @@ pub enum ItemType {
     DragonSword,
 }
 
+impl ItemType {
+    pub const ALL: [ItemType; 7] = [
+        ItemType::Axe,
+        ItemType::Sword,
+        ItemType::Key,
+        ItemType::Bomb,
+        ItemType::Hook,
+        ItemType::WindChime,
+        ItemType::DragonSword,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ItemType::Axe => "Axe — chops logs, or turns one into a canoe by the water",
+            ItemType::Sword => "Sword — slays a single patrol guard",
+            ItemType::Key => "Key — opens locked doors",
+            ItemType::Bomb => "Bomb — blasts rocks out of the way",
+            ItemType::Hook => "Hook — spans gaps between anchors",
+            ItemType::WindChime => "Wind Chime — stuns nearby guards and cleanses the oni",
+            ItemType::DragonSword => "Dragon Sword — the only blade that can harm the boss",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "axe" => Some(ItemType::Axe),
+            "sword" => Some(ItemType::Sword),
+            "key" => Some(ItemType::Key),
+            "bomb" => Some(ItemType::Bomb),
+            "hook" => Some(ItemType::Hook),
+            "windchime" | "wind-chime" => Some(ItemType::WindChime),
+            "dragonsword" | "dragon-sword" => Some(ItemType::DragonSword),
+            _ => None,
+        }
+    }
+
+    pub fn tile(&self) -> TileType {
+        match self {
+            ItemType::Axe => TileType::Axe,
+            ItemType::Sword => TileType::Sword,
+            ItemType::Key => TileType::Key,
+            ItemType::Bomb => TileType::Bomb,
+            ItemType::Hook => TileType::Hook,
+            ItemType::WindChime => TileType::WindChime,
+            ItemType::DragonSword => TileType::DragonSword,
+        }
+    }
+
+    pub fn glyph(&self, mode: GlyphMode) -> &'static str {
+        // The same glyph as the item lying on the map, without the padding of a map cell
+        self.tile().glyph(mode).trim_end()
+    }
+
+    pub fn category(&self) -> ItemCategory {
+        match self {
+            ItemType::Axe | ItemType::Sword | ItemType::Bomb | ItemType::Hook => ItemCategory::Tool,
+            ItemType::Key => ItemCategory::KeyItem,
+            ItemType::WindChime | ItemType::DragonSword => ItemCategory::Quest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphMode {
+    Ascii,
+    Emoji,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ItemCategory {
+    Tool,
+    KeyItem,
+    Quest,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TileType {
     Empty,
@@ -193,7 +520,601 @@ pub enum TileType {
     WindChime,
     Lantern,
     DragonSword,
-    Oni,
     Boss,
     Princess,
+    Checkpoint,
+    OneWay(Direction),
+    ShallowWater,
+    HiddenTrap,
+    Trap,
+    Spikes,
+    MovingPlatform,
+    Pressure(u8),
+    Gate(u8),
+    GateOpen(u8),
+    Companion,
+    Sign(u8),
+    CrackedWall,
+    Mud,
+    Snow,
+    Conveyor(Direction),
+    Switch(u8),
+}
+
+impl TileType {
+    pub const ALL: [TileType; 53] = [
+        TileType::Empty,
+        TileType::Wall,
+        TileType::Bamboo,
+        TileType::Mountain,
+        TileType::Water,
+        TileType::Volcano,
+        TileType::Lava,
+        TileType::SnowMountain,
+        TileType::Axe,
+        TileType::WoodLog,
+        TileType::Canoe,
+        TileType::Sword,
+        TileType::Key,
+        TileType::Door,
+        TileType::DoorOpen,
+        TileType::Cottage,
+        TileType::Tomb,
+        TileType::Bomb,
+        TileType::Rock,
+        TileType::Goal,
+        TileType::Hook,
+        TileType::HookStart,
+        TileType::HookEnd,
+        TileType::Link,
+        TileType::CrystalA,
+        TileType::CrystalB,
+        TileType::CrystalC,
+        TileType::FlameA,
+        TileType::FlameB,
+        TileType::FlameC,
+        TileType::Alembic,
+        TileType::WindChime,
+        TileType::Lantern,
+        TileType::DragonSword,
+        TileType::Boss,
+        TileType::Princess,
+        TileType::Checkpoint,
+        TileType::OneWay(Direction::Up),
+        TileType::ShallowWater,
+        TileType::HiddenTrap,
+        TileType::Trap,
+        TileType::Spikes,
+        TileType::MovingPlatform,
+        TileType::Pressure(0),
+        TileType::Gate(0),
+        TileType::GateOpen(0),
+        TileType::Companion,
+        TileType::Sign(0),
+        TileType::CrackedWall,
+        TileType::Mud,
+        TileType::Snow,
+        TileType::Conveyor(Direction::Right),
+        TileType::Switch(0),
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            // A hidden trap passes for empty ground in words too
+            TileType::Empty | TileType::HiddenTrap => "empty",
+            // A cracked wall passes for forest in words too
+            TileType::Wall | TileType::CrackedWall => "forest",
+            TileType::Bamboo => "bamboo",
+            TileType::Mountain => "mountain",
+            TileType::Water => "deep water",
+            TileType::Volcano => "volcano",
+            TileType::Lava => "lava",
+            TileType::SnowMountain => "snowy peak",
+            TileType::Axe => "axe",
+            TileType::WoodLog => "log",
+            TileType::Canoe => "canoe",
+            TileType::Sword => "sword",
+            TileType::Key => "key",
+            TileType::Door => "locked door",
+            TileType::DoorOpen => "open door",
+            TileType::Cottage => "cottage",
+            TileType::Tomb => "tomb",
+            TileType::Bomb => "bomb",
+            TileType::Rock => "rock",
+            TileType::Goal => "goal",
+            TileType::Hook => "hook",
+            TileType::HookStart | TileType::HookEnd => "hook point",
+            TileType::Link => "rope",
+            TileType::CrystalA => "red crystal",
+            TileType::CrystalB => "yellow crystal",
+            TileType::CrystalC => "blue crystal",
+            TileType::FlameA => "red flame",
+            TileType::FlameB => "yellow flame",
+            TileType::FlameC => "blue flame",
+            TileType::Alembic => "alembic",
+            TileType::WindChime => "wind chime",
+            TileType::Lantern => "lantern",
+            TileType::DragonSword => "dragon sword",
+            TileType::Boss => "boss",
+            TileType::Princess => "princess",
+            TileType::Checkpoint => "checkpoint",
+            TileType::OneWay(_) => "one-way path",
+            TileType::ShallowWater => "shallow water",
+            TileType::Trap => "trap",
+            TileType::Spikes => "spikes",
+            TileType::MovingPlatform => "platform track",
+            TileType::Pressure(_) => "pressure plate",
+            TileType::Gate(_) => "gate",
+            TileType::GateOpen(_) => "open gate",
+            TileType::Companion => "companion",
+            TileType::Sign(_) => "sign",
+            TileType::Mud => "mud",
+            TileType::Snow => "snow",
+            TileType::Conveyor(_) => "conveyor",
+            TileType::Switch(_) => "switch",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            TileType::Empty => "Empty ground",
+            TileType::Wall => "Forest, too dense to pass",
+            TileType::Bamboo => "Bamboo, too thick to pass",
+            TileType::Mountain => "Mountain, too steep to climb",
+            TileType::Water => "Deep water; cross it by canoe or hook",
+            TileType::Volcano => "Volcano, far too hot to pass",
+            TileType::Lava => "Lava; only a hook gets you over it",
+            TileType::SnowMountain => "Snowy peak, impassable",
+            TileType::Axe => "Axe, for chopping logs",
+            TileType::WoodLog => "Log; chop it with the axe, or build a canoe by the water",
+            TileType::Canoe => "Canoe, carries you across deep water",
+            TileType::Sword => "Sword, slays a single patrol guard",
+            TileType::Key => "Key, opens a locked door",
+            TileType::Door => "Locked door",
+            TileType::DoorOpen => "Open door",
+            TileType::Cottage => "Cottage; search it for supplies",
+            TileType::Tomb => "Searched cottage, nothing left inside",
+            TileType::Bomb => "Bomb, blasts a rock out of the way",
+            TileType::Rock => "Rock; blast it with a bomb",
+            TileType::Goal => "Goal, clears the level",
+            TileType::Hook => "Hook, spans a gap between hook points",
+            TileType::HookStart => "Hook point; walk into it with a hook to swing across",
+            TileType::HookEnd => "Anchor the hook swings you toward",
+            TileType::Link => "Rope laid by the hook",
+            TileType::CrystalA => "Red crystal, quenches the red flame",
+            TileType::CrystalB => "Yellow crystal, quenches the yellow flame",
+            TileType::CrystalC => "Blue crystal, quenches the blue flame",
+            TileType::FlameA => "Red flame; find the red crystal",
+            TileType::FlameB => "Yellow flame; find the yellow crystal",
+            TileType::FlameC => "Blue flame; find the blue crystal",
+            TileType::Alembic => "Spent crystal",
+            TileType::WindChime => "Wind Chime, stuns nearby guards and cleanses the oni",
+            TileType::Lantern => {
+                "Lantern; in fog of war, its light lets you see farther for a while"
+            }
+            TileType::DragonSword => "Dragon Sword, the only blade that can harm the boss",
+            TileType::Boss => "The boss; strike it with the Dragon Sword",
+            TileType::Princess => "The princess; lead her back to the start",
+            TileType::Checkpoint => "Checkpoint, where you respawn after dying",
+            TileType::OneWay(_) => "One-way path, entered only in the arrow's direction",
+            TileType::ShallowWater => "Shallow water, swim across while you have stamina",
+            TileType::HiddenTrap => "Hidden trap, looks like empty ground until sprung",
+            TileType::Trap => "Sprung trap",
+            TileType::Spikes => "Spikes that rise and sink; cross while they're down",
+            TileType::MovingPlatform => "Platform track; ride the platform across",
+            TileType::Pressure(_) => "Pressure plate, holds its gate open while weighed down",
+            TileType::Gate(_) => "Gate, shut until its plate is weighed down",
+            TileType::GateOpen(_) => "Open gate",
+            TileType::Companion => {
+                "A companion; it follows you and holds off one guard in your place"
+            }
+            TileType::Sign(_) => "Sign; step on it to read it",
+            TileType::Mud => "Mud, slow going: crossing it takes three moves",
+            TileType::Snow => "Snow; your footprints stay in it for a while",
+            TileType::Conveyor(_) => {
+                "Conveyor, carrying whoever stands on it in the arrows' direction"
+            }
+            TileType::Switch(_) => "Switch; stepping on it opens or closes its walls",
+            TileType::CrackedWall => {
+                "Cracked forest; looks like any other, but a bomb or an axe breaks through"
+            }
+        }
+    }
+
+    pub fn glyph(&self, mode: GlyphMode) -> &'static str {
+        self.glyph_when(mode, true)
+    }
+
+    pub fn glyph_when(&self, mode: GlyphMode, spikes_raised: bool) -> &'static str {
+        if mode == GlyphMode::Ascii {
+            return match self {
+                TileType::Empty => ". ",
+                TileType::Wall | TileType::CrackedWall => "# ",
+                TileType::Bamboo => "| ",
+                TileType::Mountain => "M ",
+                TileType::Water => "~ ",
+                TileType::Volcano => "V ",
+                TileType::Lava => "% ",
+                TileType::SnowMountain => "N ",
+                TileType::Goal => "G ",
+                TileType::Axe => "a ",
+                TileType::WoodLog => "l ",
+                TileType::Canoe => "c ",
+                TileType::Sword => "s ",
+                TileType::Key => "k ",
+                TileType::Door => "D ",
+                TileType::DoorOpen => "_ ",
+                TileType::Cottage => "H ",
+                TileType::Tomb => "t ",
+                TileType::Rock => "R ",
+                TileType::Bomb => "@ ",
+                TileType::Hook => "j ",
+                TileType::HookStart => "& ",
+                TileType::HookEnd => "& ",
+                TileType::Link => "- ",
+                TileType::CrystalA => "*1",
+                TileType::CrystalB => "*2",
+                TileType::CrystalC => "*3",
+                TileType::FlameA => "f1",
+                TileType::FlameB => "f2",
+                TileType::FlameC => "f3",
+                TileType::Alembic => "u ",
+                TileType::WindChime => "i ",
+                TileType::DragonSword => "X ",
+                TileType::Lantern => "L ",
+                TileType::Boss => "B ",
+                TileType::Princess => "$ ",
+                TileType::Checkpoint => "F ",
+                TileType::ShallowWater => "= ",
+                TileType::HiddenTrap => ". ",
+                TileType::Trap => "! ",
+                TileType::Spikes if !spikes_raised => ", ",
+                TileType::Spikes => "x ",
+                TileType::MovingPlatform => ": ",
+                TileType::Pressure(_) => "P ",
+                TileType::Gate(_) => "||",
+                TileType::GateOpen(_) => "' ",
+                TileType::Companion => "C ",
+                TileType::Sign(_) => "? ",
+                TileType::Mud => "; ",
+                TileType::Snow => "` ",
+                TileType::Switch(_) => "_/",
+                TileType::OneWay(Direction::Up) => "^-",
+                TileType::OneWay(Direction::Down) => "v-",
+                TileType::OneWay(Direction::Left) => "<-",
+                TileType::OneWay(Direction::Right) => "->",
+                TileType::OneWay(_) => "* ",
+                TileType::Conveyor(Direction::Up) => "^^",
+                TileType::Conveyor(Direction::Down) => "vv",
+                TileType::Conveyor(Direction::Left) => "<<",
+                TileType::Conveyor(Direction::Right) => ">>",
+                TileType::Conveyor(_) => "**",
+            };
+        }
+
+        match self {
+            TileType::Empty => "・",
+            // Secret passages look like any other forest
+            TileType::Wall | TileType::CrackedWall => "🌲",
+            TileType::Bamboo => "🎋",
+            TileType::Mountain => "⛰️\u{200B}",
+            TileType::Water => "🟦",
+            TileType::Volcano => "🌋",
+            TileType::Lava => "🟧",
+            TileType::SnowMountain => "🗻",
+            TileType::Goal => "🏯",
+            TileType::Axe => "🪓",
+            TileType::WoodLog => "🪵",
+            TileType::Canoe => "🛶",
+            TileType::Sword => "🗡\u{200B}",
+            TileType::Key => "🗝️\u{200B}",
+            TileType::Door => "🚪",
+            TileType::DoorOpen => "⛩️\u{200B}",
+            TileType::Cottage => "🏚️\u{200B}",
+            TileType::Tomb => "🪦",
+            TileType::Rock => "🪨",
+            TileType::Bomb => "💣",
+            TileType::Hook => "🪝",
+            TileType::HookStart => "⚓",
+            TileType::HookEnd => "⚓",
+            TileType::Link => "🔗",
+            TileType::CrystalA => "🔴",
+            TileType::CrystalB => "🟡",
+            TileType::CrystalC => "🔵",
+            TileType::FlameA => "🔥",
+            TileType::FlameB => "🕯️\u{200B}",
+            TileType::FlameC => "🪔",
+            TileType::Alembic => "⚗️\u{200B}",
+            TileType::WindChime => "🎐",
+            TileType::DragonSword => "⚔️\u{200B}",
+            TileType::Lantern => "🏮",
+            TileType::Boss => "🎎",
+            TileType::Princess => "🧝‍♀️",
+            TileType::Checkpoint => "🚩",
+            TileType::ShallowWater => "💧",
+            // Looks like any other empty tile until it is sprung
+            TileType::HiddenTrap => "・",
+            TileType::Trap => "📍",
+            TileType::Spikes if !spikes_raised => "🔲",
+            TileType::Spikes => "🔱",
+            TileType::MovingPlatform => "〰️\u{200B}",
+            TileType::Pressure(_) => "🔘",
+            TileType::Gate(_) => "🚧",
+            TileType::GateOpen(_) => "⬜",
+            TileType::Companion => "🐕",
+            TileType::Sign(_) => "🪧",
+            TileType::Mud => "🟤",
+            TileType::Snow => "❄️\u{200B}",
+            TileType::Switch(_) => "🕹️\u{200B}",
+            TileType::OneWay(Direction::Up) => "⬆️\u{200B}",
+            TileType::OneWay(Direction::Down) => "⬇️\u{200B}",
+            TileType::OneWay(Direction::Left) => "⬅️\u{200B}",
+            TileType::OneWay(Direction::Right) => "➡️\u{200B}",
+            TileType::OneWay(_) => "✳️\u{200B}",
+            TileType::Conveyor(Direction::Up) => "⏫",
+            TileType::Conveyor(Direction::Down) => "⏬",
+            TileType::Conveyor(Direction::Left) => "⏪",
+            TileType::Conveyor(Direction::Right) => "⏩",
+            TileType::Conveyor(_) => "🔀",
+        }
+    }
+
+    pub fn is_walkable(&self) -> bool {
+        matches!(
+            self,
+            TileType::Empty
+                | TileType::DoorOpen
+                | TileType::Tomb
+                | TileType::Canoe
+                | TileType::Alembic
+                | TileType::Link
+                | TileType::HookEnd
+                | TileType::Checkpoint
+                | TileType::OneWay(_)
+                | TileType::Pressure(_)
+                | TileType::GateOpen(_)
+                | TileType::Sign(_)
+                | TileType::Mud
+                | TileType::Snow
+                | TileType::Conveyor(_)
+                | TileType::Switch(_)
+        )
+    }
+
+    pub fn movement_cost(&self) -> u32 {
+        match self {
+            TileType::ShallowWater => 2,
+            TileType::Mud => 3,
+            _ => 1,
+        }
+    }
+
+    pub fn allows_entry(&self, dir: Direction) -> bool {
+        match self {
+            TileType::OneWay(allowed) => {
+                let (allowed_dy, allowed_dx) = allowed.delta();
+                let (dy, dx) = dir.delta();
+                (allowed_dy != 0 && dy == allowed_dy) || (allowed_dx != 0 && dx == allowed_dx)
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEvent {
+    LevelStarted(usize),
+    PlayerDied,
+    EnemySlain,
+    LevelCleared {
+        level: usize,
+        time: Duration,
+        moves: u32,
+    },
+}
+
+#[test]
+fn test_direction_offsets() {
+    let origin = Position { row: 5, col: 5 };
+
+    assert_eq!(origin.offset(Direction::Up), Position { row: 4, col: 5 });
+    assert_eq!(origin.offset(Direction::Down), Position { row: 6, col: 5 });
+    assert_eq!(origin.offset(Direction::Left), Position { row: 5, col: 4 });
+    assert_eq!(origin.offset(Direction::Right), Position { row: 5, col: 6 });
+
+    assert_eq!(
+        origin.neighbor(Direction::Right, 3),
+        Position { row: 5, col: 8 }
+    );
+}
+
+#[test]
+fn test_direction_offset_is_reversible() {
+    let origin = Position { row: 0, col: 0 };
+
+    for dir in Direction::ALL.into_iter().chain(Direction::DIAGONALS) {
+        assert_eq!(origin.offset(dir).offset(dir.opposite()), origin);
+        assert_eq!(origin.neighbor(dir, 4).neighbor(dir.opposite(), 4), origin);
+    }
+}
+
+#[test]
+fn test_every_blocker_has_a_message() {
+    let blockers = [
+        BlockingType::Wall,
+        BlockingType::Bamboo,
+        BlockingType::Mountain,
+        BlockingType::Water,
+        BlockingType::Volcano,
+        BlockingType::Lava,
+        BlockingType::SnowMountain,
+        BlockingType::FlameA,
+        BlockingType::FlameB,
+        BlockingType::FlameC,
+        BlockingType::Lantern,
+        BlockingType::OneWay,
+        BlockingType::Exhausted,
+        BlockingType::Track,
+        BlockingType::Gate,
+    ];
+
+    for blocker in blockers {
+        assert!(
+            !blocker.message().trim().is_empty(),
+            "{:?} has no bump message",
+            blocker
+        );
+    }
+}
+
+#[test]
+fn test_every_item_has_a_description() {
+    for item in ItemType::ALL {
+        assert!(
+            !item.description().is_empty(),
+            "{:?} has no description",
+            item
+        );
+    }
+}
+
+#[test]
+fn test_one_way_allows_entry_along_its_arrow() {
+    let tile = TileType::OneWay(Direction::Right);
+
+    assert!(tile.allows_entry(Direction::Right));
+    assert!(tile.allows_entry(Direction::DownRight));
+    assert!(!tile.allows_entry(Direction::Left));
+    assert!(!tile.allows_entry(Direction::Up));
+    assert!(TileType::Empty.allows_entry(Direction::Left));
+}
+
+#[test]
+fn test_direction_between_adjacent_positions() {
+    let from = Position { row: 3, col: 3 };
+
+    for dir in Direction::ALL.into_iter().chain(Direction::DIAGONALS) {
+        assert_eq!(Direction::between(&from, &from.offset(dir)), Some(dir));
+    }
+    assert_eq!(Direction::between(&from, &from), None);
+    assert_eq!(
+        Direction::between(&from, &from.neighbor(Direction::Up, 2)),
+        None
+    );
+}
+
+#[test]
+fn test_distance_metrics() {
+    let origin = Position { row: 0, col: 0 };
+    let far = Position { row: -3, col: 4 };
+
+    assert_eq!(origin.manhattan(&far), 7);
+    assert_eq!(origin.chebyshev(&far), 4);
+    assert_eq!(far.manhattan(&origin), 7);
+    assert_eq!(far.chebyshev(&origin), 4);
+
+    let other = Position { row: -5, col: -1 };
+    assert_eq!(far.manhattan(&other), 7);
+    assert_eq!(far.chebyshev(&other), 5);
+    assert_eq!(other.manhattan(&other), 0);
+}
+
+#[test]
+fn test_direction_toward_follows_the_dominant_axis() {
+    let from = Position { row: 5, col: 5 };
+    let toward = |row, col| Direction::toward(&from, &Position { row, col });
+
+    assert_eq!(toward(5, 5), None);
+    assert_eq!(toward(0, 5), Some(Direction::Up));
+    assert_eq!(toward(5, 12), Some(Direction::Right));
+    assert_eq!(toward(6, 0), Some(Direction::Left));
+    assert_eq!(toward(9, 4), Some(Direction::Down));
+    assert_eq!(toward(2, 9), Some(Direction::UpRight));
+    assert_eq!(toward(8, 1), Some(Direction::DownLeft));
+    assert_eq!(toward(-3, -3), Some(Direction::UpLeft));
+}
+
+#[test]
+fn test_all_tiles_lists_every_variant_once() {
+    // Stops compiling when a variant is added, as a reminder to list it in `TileType::ALL`
+    let variant = |tile: TileType| match tile {
+        TileType::Empty => 0,
+        TileType::Wall => 1,
+        TileType::Bamboo => 2,
+        TileType::Mountain => 3,
+        TileType::Water => 4,
+        TileType::Volcano => 5,
+        TileType::Lava => 6,
+        TileType::SnowMountain => 7,
+        TileType::Axe => 8,
+        TileType::WoodLog => 9,
+        TileType::Canoe => 10,
+        TileType::Sword => 11,
+        TileType::Key => 12,
+        TileType::Door => 13,
+        TileType::DoorOpen => 14,
+        TileType::Cottage => 15,
+        TileType::Tomb => 16,
+        TileType::Bomb => 17,
+        TileType::Rock => 18,
+        TileType::Goal => 19,
+        TileType::Hook => 20,
+        TileType::HookStart => 21,
+        TileType::HookEnd => 22,
+        TileType::Link => 23,
+        TileType::CrystalA => 24,
+        TileType::CrystalB => 25,
+        TileType::CrystalC => 26,
+        TileType::FlameA => 27,
+        TileType::FlameB => 28,
+        TileType::FlameC => 29,
+        TileType::Alembic => 30,
+        TileType::WindChime => 31,
+        TileType::Lantern => 32,
+        TileType::DragonSword => 33,
+        TileType::Boss => 34,
+        TileType::Princess => 35,
+        TileType::Checkpoint => 36,
+        TileType::OneWay(_) => 37,
+        TileType::ShallowWater => 38,
+        TileType::HiddenTrap => 39,
+        TileType::Trap => 40,
+        TileType::Spikes => 41,
+        TileType::MovingPlatform => 42,
+        TileType::Pressure(_) => 43,
+        TileType::Gate(_) => 44,
+        TileType::GateOpen(_) => 45,
+        TileType::Companion => 46,
+        TileType::Sign(_) => 47,
+        TileType::CrackedWall => 48,
+        TileType::Mud => 49,
+        TileType::Snow => 50,
+        TileType::Conveyor(_) => 51,
+        TileType::Switch(_) => 52,
+    };
+
+    let variants: Vec<usize> = TileType::ALL.iter().map(|tile| variant(*tile)).collect();
+    assert_eq!(variants, (0..TileType::ALL.len()).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_every_tile_and_item_has_a_glyph_in_both_modes() {
+    for mode in [GlyphMode::Ascii, GlyphMode::Emoji] {
+        for tile in TileType::ALL {
+            assert!(
+                !tile.glyph(mode).is_empty(),
+                "{:?} has no {:?} glyph",
+                tile,
+                mode
+            );
+        }
+        for item in ItemType::ALL {
+            assert!(
+                !item.glyph(mode).is_empty(),
+                "{:?} has no {:?} glyph",
+                item,
+                mode
+            );
+        }
+    }
 }
@@ -2,51 +2,306 @@
 //! It interacts with the terminal using the `crossterm` library for clear and dynamic updates.
 //!
 //! The UI includes:
-//! - Rendering the game map with various tiles, the player's position, and enemies.
-//! - Displaying the player's inventory below the map.
+//! - Rendering the game map with various tiles, the player's position, enemies, oni, and moving platforms.
+//! - Animating water, lava, and flame tiles across frames (emoji mode only).
+//! - Displaying the player's inventory below the map, sorted by category and color-coded in colored ASCII mode.
+//! - Showing a status line with the level timer, best time, remaining hints, moves left on levels with a move limit,
+//!   stamina while swimming, and active modes, such as god mode.
+//! - Briefly highlighting the tile suggested by a hint.
+//! - Full-screen overlays, drawn in place of the map: the inventory with item descriptions, and a legend
+//!   listing the glyph and meaning of every tile, item, and character in the current ASCII or emoji mode,
+//!   split into pages that fit the terminal height.
+//! - Scrolling the map with the player when the terminal is too small to show all of it, with a compass
+//!   in the status line pointing toward the goal while it is out of view.
 //! - Showing control instructions.
-//! - Providing space for messages like death or victory notifications.
+//! - Keeping a log of the most recent messages, such as item pickups or death notifications.
 //!
 //! ### Fields
-//! - `last_rendered_height`:
-//!   Tracks the last height where content was rendered in the terminal.
-//!   This is useful for positioning messages correctly.
+//! - `messages`:
+//!   A ring buffer of the last `MESSAGE_LOG_SIZE` messages, rendered beneath the controls
+//!   with the newest message at the bottom. A `Message` may be shown only for a while, and may
+//!   be dismissed early by the next key press.
+//! - `diagonal`, `god`, `peaceful`:
+//!   Mirror the matching `Config` options so the HUD can show the right controls and status.
+//! - `level_time`, `best_time`:
+//!   The time spent on the current level and its best recorded time, shown in the status line.
+//! - `hint`, `hints_remaining`:
+//!   The hinted tile with the time it was shown, and the hint counter displayed in the status line.
+//! - `spikes_raised`:
+//!   Whether the spikes are currently raised, so they are drawn in the matching phase.
+//! - `perf`:
+//!   The measured render, frame, and update times shown in the status line with `--debug-perf`.
+//! - `moves_remaining`:
+//!   The moves left on a level with a move limit, shown in the status line.
+//! - `usable_items`:
+//!   The items the player could use by walking into a neighboring tile, with the way to walk,
+//!   shown in the status line.
+//! - `frame_count`:
+//!   Counts rendered frames to drive cosmetic tile animations, such as rippling water.
+//! - `overlay`:
+//!   The overlay currently covering the map, if any.
+//! - `legend_page`, `terminal_rows`:
+//!   The legend page being shown, and the terminal height the legend pages are cut to fit.
+//! - `view`:
+//!   The number of map rows and columns shown when the whole map doesn't fit in the terminal,
+//!   or `None` when the whole map is drawn.
+//! - `camera`:
+//!   The tile the view is centered on in photo mode, panned apart from the player, or `None` outside photo mode.
+//! - `prompt`:
+//!   A yes/no question, such as "Quit? (y/n)", shown in place of the controls line while it awaits an answer.
+//! - `escort`:
+//!   Where the rescued princess is following the player, drawn trailing behind them.
+//! - `fog`:
+//!   The tiles the player can see in fog of war, or `None` when the whole map is shown. The others are drawn blank.
+//! - `footprints`:
+//!   The trail the player left in the snow, oldest first. The older half is drawn fainter in colored ASCII mode.
+//! - `companion`:
+//!   Where the companion is following the player, drawn trailing behind them.
+//! - `flash`, `flash_frames`:
+//!   Whether the screen flashes on deaths and boss clashes (off with `--no-flash`), and how many
+//!   more frames the current flash lasts.
+//! - `sound`:
+//!   Rings the terminal bell for key events unless turned off with `--no-sound`.
+//! - `ascii`:
+//!   Renders plain ASCII characters instead of emoji, for terminals without emoji support.
+//!   In ASCII mode the player is drawn as an arrow pointing in the direction they face.
+//! - `color`:
+//!   Colors the player, enemies, goal, and hazards in ASCII mode. Emoji keep their own colors.
+//! - `theme`:
+//!   The palette the map is colored with, chosen with `--theme`.
+//! - `danger_zone`:
+//!   The tiles the guards could step onto next, highlighted on open ground with `--danger-zone`.
+//! - `speed`:
+//!   The `--speed` factor; timed messages and hints stay up longer at lower speeds.
+//! - `player_skin` / `enemy_skin`:
+//!   Custom glyphs for the player and the patrol guards chosen with `--player-glyph` and `--enemy-glyph`,
+//!   already padded to fill a map cell. They replace the default glyphs in both ASCII and emoji mode.
+//! - `spacious`:
+//!   Low-vision mode that follows every tile with a blank column and every map row with a blank row,
+//!   so the tiles stand apart while staying aligned.
 //!
 //! ### Methods
 //! - `UI::new()`:
 //!   Creates a new `UI` instance with default values.
 //!
+//! - `UI::with_config()`:
+//!   Creates a new `UI` instance using the display options of a `Config`.
+//!
 //! - `UI::render()`:
-//!   Renders the game map, player position, inventory, and controls within the terminal. Clears the terminal first
-//!   to ensure seamless and clean updates.
+//!   Renders the game map, player position, inventory, controls, and message log within the terminal. Clears the terminal first
+//!   to ensure seamless and clean updates. Terminal errors are returned to the caller rather than panicking, so the
+//!   main loop can restore the terminal before exiting.
+//!
+//! - `UI::compose_map()`:
+//!   Builds the text of the map alone, with the player, enemies, and other moving pieces drawn on it.
+//!
+//! - `UI::dump_map()`:
+//!   Writes the map to any writer with plain newlines, for printing a level outside the game.
+//!
+//! - `UI::apply_settings()`:
+//!   Switches ASCII mode, colors, and sound to the chosen `Settings`, starting with the next frame.
+//!
+//! - `UI::describe()`:
+//!   Describes the surroundings of the player in words for screen readers: the tile in each direction,
+//!   then anything worth noting a few tiles further along each direction.
+//!
+//! - `UI::compose_frame()`:
+//!   Builds the text of a single frame (map, inventory, controls, and message log) without touching the terminal.
+//!
+//! - `UI::required_size()`:
+//!   Computes the terminal size (columns, rows) needed to display a map of the given size along with the HUD.
+//!   Spacious mode needs more room; `render` accounts for it.
+//!
+//! - `UI::fits_terminal()`:
+//!   Checks whether a terminal of the given size is large enough for a map. When it is not, `render`
+//!   shows the part of the map around the player instead.
+//!
+//! - `UI::scrolled_view()`:
+//!   Computes how many map rows and columns fit in a terminal of the given size next to the HUD, or `None`
+//!   when not even a small window fits. `render` then shows a resize prompt instead of a garbled frame,
+//!   and resumes normal rendering once the terminal grows.
+//!
+//! - `UI::tile_at()`:
+//!   Finds the map tile drawn at a terminal cell, such as the one a mouse click landed on, taking scrolling
+//!   and spacious mode into account. `None` outside the map, or while an overlay covers it.
+//!
+//! - `UI::open_overlay()` / `UI::close_overlay()`:
+//!   Shows an overlay in place of the map, or closes it. `close_overlay` reports whether one was open;
+//!   the legend turns to its next page instead until the last one has been shown.
+//!
+//! - `UI::show_hint()`:
+//!   Highlights a tile for about a second to show the player where to go next.
+//!
+//! - `UI::set_level_times()`:
+//!   Updates the level timer and best time shown in the status line.
+//!
+//! - `UI::toggle_photo_mode()` / `UI::is_photo_mode()`:
+//!   Enters photo mode with the camera on the player, or leaves it; and tells whether it is on.
+//!
+//! - `UI::pan_camera()` / `UI::recenter_camera()`:
+//!   Moves the photo mode camera a tile in a direction, staying on the map, or back onto the player.
+//!   The view only pans when the map is larger than the terminal.
+//!
+//! - `UI::set_prompt()`:
+//!   Shows a yes/no question in place of the controls line, or hides it again with `None`.
+//!
+//! - `UI::set_danger_zone()`:
+//!   Updates the tiles highlighted as within reach of the guards.
+//!
+//! - `UI::set_escort()`:
+//!   Updates the position of the princess following the player.
+//!
+//! - `UI::set_fog()`:
+//!   Updates the tiles revealed in fog of war.
+//!
+//! - `UI::set_footprints()`:
+//!   Updates the footprint trail drawn on snow tiles.
+//!
+//! - `UI::set_companion()`:
+//!   Updates the position of the companion following the player.
+//!
+//! - `UI::set_hints_remaining()`:
+//!   Updates the hint counter shown in the status line.
+//!
+//! - `UI::set_spikes_raised()`:
+//!   Updates the phase the spikes are drawn in.
+//!
+//! - `UI::set_perf_stats()`:
+//!   Updates the timings shown by the performance overlay.
+//!
+//! - `UI::set_moves_remaining()`:
+//!   Updates the moves left shown in the status line, or hides them on levels without a move limit.
+//!
+//! - `UI::set_usable_items()`:
+//!   Updates the items the status line suggests using on the tiles around the player.
+//!
+//! - `UI::flash()`:
+//!   Flashes the screen, drawing the next `FLASH_FRAMES` frames in inverted colors. Does nothing
+//!   when flashing is off.
+//!
+//! - `UI::beep()` / `UI::beep_to()`:
+//!   Rings the terminal bell, either on the terminal or on any other writer. Does nothing
+//!   when sound is off, and `beep` stays quiet when the output isn't a terminal.
+//!
+//! - `UI::show_message()`:
+//!   Adds a message to the message log, where it stays until newer messages push it out.
+//!
+//! - `UI::show_message_for()`:
+//!   Adds a message that disappears after the given duration, or at the next key press when `dismiss_on_key` is set.
+//!
+//! - `UI::dismiss_messages()`:
+//!   Clears the remaining time of every message that a key press dismisses. The main loop calls it for every key.
 //!
 //! - `UI::show_death_message()`:
-//!   Displays a "You died" message in the terminal.
+//!   Adds a "You died" message to the message log.
+//!
+//! - `UI::compose_victory_screen()` / `UI::compose_defeat_screen()`:
+//!   Builds the full-screen panel shown when the game is cleared, or when the player gives up on a run:
+//!   ASCII art and the run's stats in a frame, drawn with plain ASCII characters in ASCII mode.
 //!
-//! - `UI::show_game_clear_message()`:
-//!   Displays a "Game clear" message in the terminal.
+//! - `UI::show_victory_screen()` / `UI::show_defeat_screen()`:
+//!   Clears the terminal, draws the matching panel, and waits for a key.
 //!
 //! - `UI::show_message()`:
-//!   Displays a custom message at the correct screen position while temporarily pausing
-//!   the program to make the message visible for the user.
+//!   Adds a custom message to the message log without pausing the game. The oldest message
+//!   is dropped once the log is full.
+//!
+//! - `UI::last_message()`:
+//!   Returns the newest message in the message log, if any.
 //!
 //! ### Notes
 //! This struct heavily relies on the ANSI escape codes managed by the `crossterm` library to dynamically update the terminal output.
 //!
 //! The rendering logic primarily focuses on minimal terminal updates and user feedback during the game.
 
+use crate::classes::config::Config;
+use crate::classes::leaderboard::Leaderboard;
 use crate::classes::level::Level;
-use crate::classes::player::Player;
-use crate::classes::types::{ItemType, Position, TileType};
+use crate::classes::player::{Player, MAX_STAMINA};
+use crate::classes::run_log::RunSummary;
+use crate::classes::settings::Settings;
+use crate::classes::theme::Theme;
+use crate::classes::timing::{scaled, PerfStats};
+use crate::classes::types::{Direction, GlyphMode, ItemCategory, ItemType, Position, TileType};
+use crate::classes::width::{text_width, TILE_WIDTH};
 use crossterm::{
     cursor::{Hide, MoveTo},
-    terminal::{Clear, ClearType},
+    event::{self, Event, KeyEventKind},
+    style::{Attribute, Color, Stylize},
+    terminal::{self, Clear, ClearType},
     ExecutableCommand,
 };
-use std::io::{stdout, Write};
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, stdout, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+// How many tiles away `describe` looks along each direction
+const DESCRIBE_DISTANCE: i16 = 3;
+// Rows and columns each tile takes up on screen, normally and in spacious mode
+const TILE_CELL: (u16, u16) = (1, TILE_WIDTH);
+const SPACIOUS_TILE_CELL: (u16, u16) = (2, TILE_WIDTH + 1);
+// Number of frames each step of a tile animation is shown
+const ANIMATION_PERIOD: u64 = 5;
+// How long a hinted tile stays highlighted
+const HINT_DURATION: Duration = Duration::from_secs(1);
+// Columns inside the frame of the victory and defeat screens
+const END_SCREEN_WIDTH: usize = 44;
+// Number of frames drawn in inverted colors when the screen flashes
+const FLASH_FRAMES: u8 = 2;
+// Number of messages kept in the message log
+const MESSAGE_LOG_SIZE: usize = 5;
+// Lines the controls are wrapped onto to stay within the HUD width
+const CONTROL_ROWS: u16 = 2;
+// Inventory, status, and controls lines below the map, followed by the message log
+const HUD_HEIGHT: u16 = 2 + CONTROL_ROWS + MESSAGE_LOG_SIZE as u16;
+// Enough columns for a full inventory line
+const HUD_WIDTH: u16 = 40;
+// The fewest map rows worth scrolling through when the whole map doesn't fit
+const MIN_VIEW_ROWS: u16 = 5;
+// The title and the return prompt framing an overlay, each followed by a blank line
+const OVERLAY_FRAME_ROWS: u16 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overlay {
+    Inventory,
+    Legend,
+}
 
 pub struct UI {
-    last_rendered_height: u16,
+    messages: VecDeque<Message>,
+    ascii: bool,
+    color: bool,
+    theme: Theme,
+    sound: bool,
+    flash: bool,
+    flash_frames: u8,
+    diagonal: bool,
+    god: bool,
+    peaceful: bool,
+    spacious: bool,
+    danger_zone: HashSet<Position>,
+    speed: f64,
+    player_skin: Option<String>,
+    enemy_skin: Option<String>,
+    hint: Option<(Position, Instant)>,
+    hints_remaining: u8,
+    moves_remaining: Option<u32>,
+    usable_items: Vec<(Direction, ItemType)>,
+    perf: Option<PerfStats>,
+    spikes_raised: bool,
+    frame_count: u64,
+    overlay: Option<Overlay>,
+    legend_page: usize,
+    terminal_rows: u16,
+    level_time: Duration,
+    best_time: Option<Duration>,
+    escort: Option<Position>,
+    companion: Option<Position>,
+    footprints: Vec<Position>,
+    fog: Option<HashSet<Position>>,
+    prompt: Option<&'static str>,
+    camera: Option<Position>,
+    view: Option<(u16, u16)>,
 }
 
 impl Default for UI {
@@ -58,131 +313,1764 @@ impl Default for UI {
 impl UI {
     pub fn new() -> Self {
         Self {
-            last_rendered_height: 0,
+            messages: VecDeque::with_capacity(MESSAGE_LOG_SIZE),
+            ascii: false,
+            color: false,
+            theme: Theme::default(),
+            sound: false,
+            flash: true,
+            flash_frames: 0,
+            diagonal: false,
+            god: false,
+            peaceful: false,
+            spacious: false,
+            danger_zone: HashSet::new(),
+            speed: 1.0,
+            player_skin: None,
+            enemy_skin: None,
+            hint: None,
+            hints_remaining: 0,
+            moves_remaining: None,
+            usable_items: Vec::new(),
+            perf: None,
+            spikes_raised: false,
+            frame_count: 0,
+            overlay: None,
+            legend_page: 0,
+            terminal_rows: 24,
+            level_time: Duration::ZERO,
+            best_time: None,
+            escort: None,
+            companion: None,
+            footprints: Vec::new(),
+            fog: None,
+            prompt: None,
+            camera: None,
+            view: None,
+        }
+    }
+
+    pub fn with_config(config: &Config) -> Self {
+        Self {
+            ascii: config.ascii,
+            color: config.color,
+            theme: config.theme,
+            sound: config.sound,
+            flash: config.flash,
+            diagonal: config.diagonal,
+            god: config.god,
+            peaceful: config.peaceful,
+            spacious: config.spacious,
+            speed: config.speed,
+            player_skin: config.player_glyph.clone(),
+            enemy_skin: config.enemy_glyph.clone(),
+            ..Self::new()
+        }
+    }
+
+    pub fn required_size(map_size: (u8, u8)) -> (u16, u16) {
+        Self::required_size_for(map_size, TILE_CELL)
+    }
+
+    fn required_size_for(map_size: (u8, u8), cell: (u16, u16)) -> (u16, u16) {
+        let cols = (map_size.1 as u16 * cell.1).max(HUD_WIDTH);
+        let rows = map_size.0 as u16 * cell.0 + HUD_HEIGHT;
+        (cols, rows)
+    }
+
+    pub fn fits_terminal(terminal_size: (u16, u16), map_size: (u8, u8)) -> bool {
+        Self::fits_terminal_for(terminal_size, map_size, TILE_CELL)
+    }
+
+    fn fits_terminal_for(terminal_size: (u16, u16), map_size: (u8, u8), cell: (u16, u16)) -> bool {
+        let (cols, rows) = Self::required_size_for(map_size, cell);
+        terminal_size.0 >= cols && terminal_size.1 >= rows
+    }
+
+    pub fn scrolled_view(terminal_size: (u16, u16)) -> Option<(u16, u16)> {
+        Self::scrolled_view_for(terminal_size, TILE_CELL)
+    }
+
+    fn scrolled_view_for(terminal_size: (u16, u16), cell: (u16, u16)) -> Option<(u16, u16)> {
+        let rows = terminal_size.1.saturating_sub(HUD_HEIGHT) / cell.0;
+
+        if terminal_size.0 < HUD_WIDTH || rows < MIN_VIEW_ROWS {
+            return None;
         }
+        Some((rows, terminal_size.0 / cell.1))
     }
 
-    pub fn render(&mut self, level: &Level, player: &Player) {
+    fn tile_cell(&self) -> (u16, u16) {
+        if self.spacious {
+            SPACIOUS_TILE_CELL
+        } else {
+            TILE_CELL
+        }
+    }
+
+    pub fn render(&mut self, level: &Level, player: &Player) -> io::Result<()> {
         let mut stdout = stdout();
-        stdout.execute(Clear(ClearType::All)).unwrap();
-        stdout.execute(MoveTo(0, 0)).unwrap();
-        stdout.execute(Hide).unwrap();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(MoveTo(0, 0))?;
+        stdout.execute(Hide)?;
 
-        let mut frame = String::new();
-        for (row, row_tiles) in level.map.iter().enumerate() {
+        if let Ok(terminal_size) = terminal::size() {
+            self.terminal_rows = terminal_size.1;
+            if Self::fits_terminal_for(terminal_size, level.map_size, self.tile_cell()) {
+                self.view = None;
+            } else if let Some(view) = Self::scrolled_view_for(terminal_size, self.tile_cell()) {
+                self.view = Some(view);
+            } else {
+                write!(
+                    stdout,
+                    "Terminal too small (need {}x{})\r\nPlease resize the window to continue.",
+                    HUD_WIDTH,
+                    HUD_HEIGHT + MIN_VIEW_ROWS
+                )?;
+                return stdout.flush();
+            }
+        }
+
+        let frame = self.apply_flash(self.compose_frame(level, player));
+        self.frame_count = self.frame_count.wrapping_add(1);
+
+        write!(stdout, "{}", frame)?;
+        stdout.flush()
+    }
+
+    pub fn flash(&mut self) {
+        if self.flash {
+            self.flash_frames = FLASH_FRAMES;
+        }
+    }
+
+    fn apply_flash(&mut self, frame: String) -> String {
+        if self.flash_frames == 0 {
+            return frame;
+        }
+        self.flash_frames -= 1;
+        format!("{}{}{}", Attribute::Reverse, frame, Attribute::Reset)
+    }
+
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.ascii = settings.ascii;
+        self.color = settings.color;
+        self.sound = settings.sound;
+    }
+
+    pub fn compose_map(&self, level: &Level, player: &Player) -> String {
+        self.compose_map_with(level, player, self.color && self.ascii)
+    }
+
+    // The first row and column shown, and how many of each, following the player when scrolled
+    fn visible_area(&self, level: &Level, player: &Player) -> ((usize, usize), (usize, usize)) {
+        let window = |size: usize, shown: u16, center: i16| {
+            let shown = (shown as usize).min(size);
+            let start = (center.max(0) as usize)
+                .saturating_sub(shown / 2)
+                .min(size - shown);
+            (start, shown)
+        };
+
+        let center = self.camera.unwrap_or(player.pos);
+        match self.view {
+            Some((rows, cols)) => (
+                window(level.map_size.0 as usize, rows, center.row),
+                window(level.map_size.1 as usize, cols, center.col),
+            ),
+            None => ((0, usize::MAX), (0, usize::MAX)),
+        }
+    }
+
+    pub fn tile_at(
+        &self,
+        level: &Level,
+        player: &Player,
+        column: u16,
+        row: u16,
+    ) -> Option<Position> {
+        if self.overlay.is_some() {
+            return None;
+        }
+
+        let (cell_rows, cell_cols) = self.tile_cell();
+        let ((top, rows), (left, cols)) = self.visible_area(level, player);
+        let (row, col) = ((row / cell_rows) as usize, (column / cell_cols) as usize);
+        if row >= rows.min(level.map_size.0 as usize) || col >= cols.min(level.map_size.1 as usize)
+        {
+            return None;
+        }
+
+        Some(Position {
+            row: (top + row) as i16,
+            col: (left + col) as i16,
+        })
+    }
+
+    fn is_visible(&self, level: &Level, player: &Player, pos: &Position) -> bool {
+        let ((top, rows), (left, cols)) = self.visible_area(level, player);
+        let (row, col) = (pos.row as usize, pos.col as usize);
+
+        row >= top && row - top < rows && col >= left && col - left < cols
+    }
+
+    // Points toward the way out while it is scrolled out of view
+    fn compass(&self, level: &Level, player: &Player) -> Option<String> {
+        self.view?;
+
+        let exit = if self.escort.is_some() {
+            level.player_start
+        } else {
+            level.nearest_exit(&player.pos)?
+        };
+        if self.is_visible(level, player, &exit) {
+            return None;
+        }
+
+        let dir = Direction::toward(&player.pos, &exit)?;
+        Some(format!(" | Goal: {}", self.compass_glyph(dir)))
+    }
+
+    pub fn describe(&self, level: &Level, player: &Player) -> String {
+        let name_at = |pos: &Position| {
+            if level.has_enemy_at(pos) {
+                Some("guard")
+            } else if level.oni.contains(pos) {
+                Some("oni")
+            } else if self.escort == Some(*pos) {
+                Some("princess")
+            } else if self.companion == Some(*pos) {
+                Some("companion")
+            } else {
+                level.get_tile(pos).map(|tile| tile.name())
+            }
+        };
+
+        // Clockwise from the north, the way directions are usually read out
+        let clockwise = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ];
+
+        let neighbors: Vec<(Direction, Position, TileType)> =
+            level.neighbors(&player.pos).collect();
+        let adjacent: Vec<String> = clockwise
+            .into_iter()
+            .map(|dir| {
+                let name = neighbors
+                    .iter()
+                    .find(|(next_dir, _, _)| *next_dir == dir)
+                    .and_then(|(_, next, _)| name_at(next))
+                    .unwrap_or("edge of the map");
+                format!("{} to the {}", name, Self::direction_name(dir))
+            })
+            .collect();
+        let mut description = Self::sentence(&adjacent.join(", "));
+
+        // Look further along each direction for the first thing that isn't plain ground or forest
+        for dir in clockwise {
+            let sighting = (2..=DESCRIBE_DISTANCE).find_map(|distance| {
+                let name = name_at(&player.pos.neighbor(dir, distance))?;
+                (name != "empty" && name != "forest").then_some((name, distance))
+            });
+
+            if let Some((name, distance)) = sighting {
+                let distance = match distance {
+                    2 => "two",
+                    _ => "three",
+                };
+                description.push(' ');
+                description.push_str(&Self::sentence(&format!(
+                    "{} {} tiles {}",
+                    name,
+                    distance,
+                    Self::direction_name(dir)
+                )));
+            }
+        }
+
+        description
+    }
+
+    fn sentence(text: &str) -> String {
+        let mut sentence = format!("{}.", text);
+        if let Some(first) = sentence.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        sentence
+    }
+
+    fn direction_name(dir: Direction) -> &'static str {
+        match dir {
+            Direction::Up => "north",
+            Direction::Down => "south",
+            Direction::Left => "west",
+            Direction::Right => "east",
+            Direction::UpLeft => "northwest",
+            Direction::UpRight => "northeast",
+            Direction::DownLeft => "southwest",
+            Direction::DownRight => "southeast",
+        }
+    }
+
+    fn compass_glyph(&self, dir: Direction) -> &'static str {
+        match (dir, self.ascii) {
+            (Direction::Up, true) => "N",
+            (Direction::Down, true) => "S",
+            (Direction::Left, true) => "W",
+            (Direction::Right, true) => "E",
+            (Direction::UpLeft, true) => "NW",
+            (Direction::UpRight, true) => "NE",
+            (Direction::DownLeft, true) => "SW",
+            (Direction::DownRight, true) => "SE",
+            (Direction::Up, false) => "⬆️",
+            (Direction::Down, false) => "⬇️",
+            (Direction::Left, false) => "⬅️",
+            (Direction::Right, false) => "➡️",
+            (Direction::UpLeft, false) => "↖️",
+            (Direction::UpRight, false) => "↗️",
+            (Direction::DownLeft, false) => "↙️",
+            (Direction::DownRight, false) => "↘️",
+        }
+    }
+
+    fn compose_map_with(&self, level: &Level, player: &Player, color: bool) -> String {
+        let ((top, rows), (left, cols)) = self.visible_area(level, player);
+        let mut map = String::new();
+        for (row, row_tiles) in level.map.iter().enumerate().skip(top).take(rows) {
             let mut line = String::new();
-            for (col, tile) in row_tiles.iter().enumerate() {
+            for (col, tile) in row_tiles.iter().enumerate().skip(left).take(cols) {
                 let pos = Position {
                     row: row as i16,
                     col: col as i16,
                 };
 
-                let char = if pos == player.pos {
-                    "🥷"
-                } else if level.enemies.contains(&pos) {
-                    "🧌"
+                let hidden = self.fog.as_ref().is_some_and(|seen| !seen.contains(&pos));
+                let (char, tint) = if hidden {
+                    // Two columns, as wide as any tile in either glyph mode
+                    ("  ", None)
+                } else if pos == player.pos {
+                    (self.player_glyph(player.facing), Some(self.theme.player))
+                } else if level.has_enemy_at(&pos) {
+                    (self.enemy_glyph(), Some(self.theme.enemy))
+                } else if level.oni.contains(&pos) {
+                    (self.oni_glyph(), Some(self.theme.oni))
+                } else if level.platforms.iter().any(|platform| platform.pos == pos) {
+                    (self.platform_glyph(), Some(self.theme.platform))
+                } else if self.escort == Some(pos) {
+                    (self.tile_glyph(TileType::Princess), Some(self.theme.ally))
+                } else if self.companion == Some(pos) {
+                    (self.tile_glyph(TileType::Companion), Some(self.theme.ally))
+                } else if self.is_hinted(&pos) {
+                    (self.hint_glyph(), Some(self.theme.hint))
+                } else if *tile == TileType::Empty && self.danger_zone.contains(&pos) {
+                    (self.danger_glyph(), Some(self.theme.enemy))
+                } else if let Some(age) = self.footprint_age(tile, &pos) {
+                    // The prints fade as they get older
+                    let tint = if age < self.footprints.len() / 2 {
+                        Color::DarkGrey
+                    } else {
+                        Color::Grey
+                    };
+                    (self.footprint_glyph(), Some(tint))
                 } else {
-                    match tile {
-                        TileType::Empty => "・",
-                        TileType::Wall => "🌲",
-                        TileType::Bamboo => "🎋",
-                        TileType::Mountain => "⛰️\u{200B}",
-                        TileType::Water => "🟦",
-                        TileType::Volcano => "🌋",
-                        TileType::Lava => "🟧",
-                        TileType::SnowMountain => "🗻",
-                        TileType::Goal => "🏯",
-                        TileType::Axe => "🪓",
-                        TileType::WoodLog => "🪵",
-                        TileType::Canoe => "🛶",
-                        TileType::Sword => "🗡\u{200B}",
-                        TileType::Key => "🗝️\u{200B}",
-                        TileType::Door => "🚪",
-                        TileType::DoorOpen => "⛩️\u{200B}",
-                        TileType::Cottage => "🏚️\u{200B}",
-                        TileType::Tomb => "🪦",
-                        TileType::Rock => "🪨",
-                        TileType::Bomb => "💣",
-                        TileType::Hook => "🪝",
-                        TileType::HookStart => "⚓",
-                        TileType::HookEnd => "⚓",
-                        TileType::Link => "🔗",
-                        TileType::CrystalA => "🔮",
-                        TileType::CrystalB => "🔮",
-                        TileType::CrystalC => "🔮",
-                        TileType::FlameA => "🔥",
-                        TileType::FlameB => "🔥",
-                        TileType::FlameC => "🔥",
-                        TileType::Alembic => "⚗️\u{200B}",
-                        TileType::WindChime => "🎐",
-                        TileType::DragonSword => "⚔️\u{200B}",
-                        TileType::Lantern => "🏮",
-                        TileType::Oni => "👹",
-                        TileType::Boss => "🎎",
-                        TileType::Princess => "🧝‍♀️",
-                    }
+                    let glyph = self
+                        .animated_glyph(*tile)
+                        .unwrap_or_else(|| self.tile_glyph(*tile));
+                    (glyph, self.tile_color(*tile))
                 };
-                line.push_str(char);
+
+                match tint {
+                    Some(tint) if color => line.push_str(&char.with(tint).to_string()),
+                    _ => line.push_str(char),
+                }
+                if self.spacious {
+                    line.push(' ');
+                }
             }
             line.push_str("\r\n");
-            frame.push_str(&line);
+            if self.spacious {
+                line.push_str("\r\n");
+            }
+            map.push_str(&line);
         }
+        map
+    }
+
+    pub fn dump_map<W: Write>(
+        &self,
+        out: &mut W,
+        level: &Level,
+        player: &Player,
+    ) -> io::Result<()> {
+        // Outside raw mode, plain newlines are enough, and colors would only clutter a file
+        write!(
+            out,
+            "{}",
+            self.compose_map_with(level, player, false)
+                .replace("\r\n", "\n")
+        )
+    }
 
-        frame.push_str(" 🎒 Inventory: ");
+    pub fn compose_frame(&self, level: &Level, player: &Player) -> String {
+        if let Some(overlay) = self.overlay {
+            return self.compose_overlay(overlay, player);
+        }
+
+        let mut frame = self.compose_map(level, player);
+
+        if self.ascii {
+            frame.push_str(" Inventory: ");
+        } else {
+            frame.push_str(" 🎒 Inventory: ");
+        }
         if player.inventory.is_empty() {
             frame.push_str("Empty");
         } else {
-            for item in &player.inventory {
-                let item_char = match item {
-                    ItemType::Axe => "🪓",
-                    ItemType::Sword => "🗡\u{200B}",
-                    ItemType::Key => "🗝️\u{200B}",
-                    ItemType::Bomb => "💣",
-                    ItemType::Hook => "🪝",
-                    ItemType::WindChime => "🎐",
-                    ItemType::DragonSword => "⚔️",
-                };
-                frame.push_str(item_char);
+            for item in player.sorted_inventory() {
+                let glyph = self.item_glyph(item);
+                if self.color && self.ascii {
+                    frame.push_str(&glyph.with(Self::item_color(item)).to_string());
+                } else {
+                    frame.push_str(glyph);
+                }
                 frame.push(' ');
             }
         }
 
         frame.push_str("\r\n");
-        frame.push_str(" wasd: Move | q: Quit");
+        frame.push_str(&self.status_line(player));
+        if let Some(compass) = self.compass(level, player) {
+            frame.push_str(&compass);
+        }
 
         frame.push_str("\r\n");
+        if let Some(prompt) = self.prompt {
+            frame.push_str(prompt);
+        } else {
+            frame.push_str(&self.controls().join("\r\n"));
+        }
+
+        frame.push_str("\r\n");
+
+        for message in self.messages.iter().filter(|message| message.is_showing()) {
+            frame.push_str(&message.text);
+            frame.push_str("\r\n");
+        }
 
-        self.last_rendered_height = level.map.len() as u16 + 3; // map + inventory + controls + empty
+        frame
+    }
+
+    fn controls(&self) -> Vec<String> {
+        let (movement, quit) = if self.diagonal {
+            ("wasd/qezc:Move", "Esc:Quit")
+        } else {
+            ("wasd:Move", "q:Quit")
+        };
+        let controls = [
+            movement,
+            ".:Wait",
+            "u:Chime",
+            "h:Hint",
+            "i:Items",
+            "?:Legend",
+            "o:Options",
+            quit,
+        ];
+
+        let mut lines: Vec<String> = Vec::new();
+        for control in controls {
+            match lines.last_mut() {
+                Some(line) if line.len() + 2 + control.len() <= HUD_WIDTH as usize => {
+                    line.push_str("  ");
+                    line.push_str(control);
+                }
+                _ => lines.push(format!(" {}", control)),
+            }
+        }
+        lines
+    }
+
+    pub fn open_overlay(&mut self, overlay: Overlay) {
+        self.overlay = Some(overlay);
+        self.legend_page = 0;
+    }
+
+    pub fn close_overlay(&mut self) -> bool {
+        if self.overlay == Some(Overlay::Legend) && self.legend_page + 1 < self.legend_pages().len()
+        {
+            self.legend_page += 1;
+            return true;
+        }
+        self.overlay.take().is_some()
+    }
+
+    fn legend_pages(&self) -> Vec<Vec<String>> {
+        let characters = [
+            (self.player_glyph(Direction::Down), "You, the ghostblade"),
+            (self.enemy_glyph(), "Patrol guard"),
+            (self.oni_glyph(), "Oni, chases you when you get close"),
+            (self.platform_glyph(), "Moving platform"),
+            (self.hint_glyph(), "The next step suggested by a hint"),
+        ];
+        let mut lines: Vec<String> = characters
+            .into_iter()
+            .map(|(glyph, description)| format!(" {} {}", glyph, description))
+            .collect();
+        for tile in TileType::ALL {
+            lines.push(format!(" {} {}", self.tile_glyph(tile), tile.description()));
+        }
+
+        lines.push(String::new());
+        lines.push(" Items".to_string());
+        for item in ItemType::ALL {
+            lines.push(format!(" {} {}", self.item_glyph(item), item.description()));
+        }
+
+        let rows = self.terminal_rows.saturating_sub(OVERLAY_FRAME_ROWS).max(1) as usize;
+        lines.chunks(rows).map(<[String]>::to_vec).collect()
+    }
+
+    fn compose_overlay(&self, overlay: Overlay, player: &Player) -> String {
+        let mut frame = String::new();
+
+        match overlay {
+            Overlay::Inventory => {
+                frame.push_str(if self.ascii {
+                    " Inventory\r\n\r\n"
+                } else {
+                    " 🎒 Inventory\r\n\r\n"
+                });
+
+                if player.inventory.is_empty() {
+                    frame.push_str(" Your pack is empty.\r\n");
+                }
+                for item in player.sorted_inventory() {
+                    frame.push_str(&format!(
+                        " {} {}\r\n",
+                        self.item_glyph(item),
+                        item.description()
+                    ));
+                }
+            }
+            Overlay::Legend => {
+                let pages = self.legend_pages();
+                let page = self.legend_page.min(pages.len() - 1);
+                frame.push_str(&format!(" Legend ({}/{})\r\n\r\n", page + 1, pages.len()));
+                for line in &pages[page] {
+                    frame.push_str(line);
+                    frame.push_str("\r\n");
+                }
+
+                if page + 1 < pages.len() {
+                    frame.push_str("\r\n Press any key for the next page");
+                    return frame;
+                }
+            }
+        }
+
+        frame.push_str("\r\n Press any key to return");
+        frame
+    }
+
+    pub fn show_hint(&mut self, pos: Position) {
+        self.hint = Some((pos, Instant::now()));
+    }
+
+    pub fn set_danger_zone(&mut self, danger_zone: HashSet<Position>) {
+        self.danger_zone = danger_zone;
+    }
+
+    pub fn toggle_photo_mode(&mut self, player: &Player) {
+        self.camera = match self.camera {
+            Some(_) => None,
+            None => Some(player.pos),
+        };
+    }
 
-        write!(stdout, "{}", frame).unwrap();
-        stdout.flush().unwrap();
+    pub fn is_photo_mode(&self) -> bool {
+        self.camera.is_some()
     }
 
-    pub fn show_death_message(&self) {
+    pub fn pan_camera(&mut self, dir: Direction, level: &Level) {
+        if let Some(camera) = &mut self.camera {
+            let (dy, dx) = dir.delta();
+            camera.row = (camera.row + dy).clamp(0, level.map_size.0 as i16 - 1);
+            camera.col = (camera.col + dx).clamp(0, level.map_size.1 as i16 - 1);
+        }
+    }
+
+    pub fn recenter_camera(&mut self, player: &Player) {
+        if self.camera.is_some() {
+            self.camera = Some(player.pos);
+        }
+    }
+
+    pub fn set_prompt(&mut self, prompt: Option<&'static str>) {
+        self.prompt = prompt;
+    }
+
+    pub fn set_escort(&mut self, escort: Option<Position>) {
+        self.escort = escort;
+    }
+
+    pub fn set_footprints(&mut self, footprints: Vec<Position>) {
+        self.footprints = footprints;
+    }
+
+    pub fn set_companion(&mut self, companion: Option<Position>) {
+        self.companion = companion;
+    }
+
+    pub fn set_fog(&mut self, fog: Option<HashSet<Position>>) {
+        self.fog = fog;
+    }
+
+    pub fn set_hints_remaining(&mut self, hints: u8) {
+        self.hints_remaining = hints;
+    }
+
+    pub fn set_spikes_raised(&mut self, raised: bool) {
+        self.spikes_raised = raised;
+    }
+
+    pub fn set_perf_stats(&mut self, perf: PerfStats) {
+        self.perf = Some(perf);
+    }
+
+    pub fn set_moves_remaining(&mut self, moves: Option<u32>) {
+        self.moves_remaining = moves;
+    }
+
+    pub fn set_usable_items(&mut self, usable_items: Vec<(Direction, ItemType)>) {
+        self.usable_items = usable_items;
+    }
+
+    fn is_hinted(&self, pos: &Position) -> bool {
+        matches!(self.hint, Some((hint, shown)) if hint == *pos && shown.elapsed() < scaled(HINT_DURATION, self.speed))
+    }
+
+    pub fn set_level_times(&mut self, level_time: Duration, best_time: Option<Duration>) {
+        self.level_time = level_time;
+        self.best_time = best_time;
+    }
+
+    fn status_line(&self, player: &Player) -> String {
+        let best = self
+            .best_time
+            .map(Leaderboard::format_time)
+            .unwrap_or_else(|| "--:--".to_string());
+        let mut status = format!(
+            " Time: {} | Best: {} | Hints: {}",
+            Leaderboard::format_time(self.level_time),
+            best,
+            self.hints_remaining
+        );
+
+        if let Some(moves) = self.moves_remaining {
+            status.push_str(&format!(" | Moves left: {}", moves));
+        }
+
+        if !self.usable_items.is_empty() {
+            let uses: Vec<String> = self
+                .usable_items
+                .iter()
+                .map(|(dir, item)| {
+                    format!("{} {}", self.item_glyph(*item), Self::direction_name(*dir))
+                })
+                .collect();
+            status.push_str(&format!(" | Use: {}", uses.join(", ")));
+        }
+
+        // Stamina only matters while swimming, so it stays hidden when full
+        if player.stamina < MAX_STAMINA {
+            status.push_str(&format!(" | Stamina: {}/{}", player.stamina, MAX_STAMINA));
+        }
+
+        if self.god {
+            status.push_str(if self.ascii {
+                " | [GOD MODE]"
+            } else {
+                " | ⚡ GOD MODE ⚡"
+            });
+        }
+
+        if self.peaceful {
+            status.push_str(if self.ascii {
+                " | [PEACEFUL]"
+            } else {
+                " | 🕊️\u{200B} PEACEFUL"
+            });
+        }
+
+        if let Some(perf) = self.perf {
+            let millis = |time: Duration| time.as_secs_f64() * 1000.0;
+            status.push_str(&format!(
+                " | Render {:.1}ms Frame {:.1}ms Update {:.1}ms",
+                millis(perf.render),
+                millis(perf.frame),
+                millis(perf.update)
+            ));
+        }
+
+        status
+    }
+
+    fn player_glyph(&self, facing: Direction) -> &str {
+        if let Some(skin) = &self.player_skin {
+            skin
+        } else if self.ascii {
+            match facing {
+                Direction::Up => "^ ",
+                Direction::Down => "v ",
+                Direction::Left => "< ",
+                Direction::Right => "> ",
+                Direction::UpLeft | Direction::DownRight => "\\ ",
+                Direction::UpRight | Direction::DownLeft => "/ ",
+            }
+        } else {
+            "🥷"
+        }
+    }
+
+    fn hint_glyph(&self) -> &'static str {
+        if self.ascii {
+            "??"
+        } else {
+            "✨"
+        }
+    }
+
+    // Where the print at `pos` comes in the trail, the oldest being 0
+    fn footprint_age(&self, tile: &TileType, pos: &Position) -> Option<usize> {
+        if *tile != TileType::Snow {
+            return None;
+        }
+        self.footprints.iter().rposition(|print| print == pos)
+    }
+
+    fn footprint_glyph(&self) -> &'static str {
+        if self.ascii {
+            "o "
+        } else {
+            "👣"
+        }
+    }
+
+    fn danger_glyph(&self) -> &'static str {
+        if self.ascii {
+            "+ "
+        } else {
+            "⚠️\u{200B}"
+        }
+    }
+
+    fn enemy_glyph(&self) -> &str {
+        if let Some(skin) = &self.enemy_skin {
+            skin
+        } else if self.ascii {
+            "E "
+        } else {
+            "🧌"
+        }
+    }
+
+    fn platform_glyph(&self) -> &'static str {
+        if self.ascii {
+            "[]"
+        } else {
+            "🟫"
+        }
+    }
+
+    fn oni_glyph(&self) -> &'static str {
+        if self.ascii {
+            "O "
+        } else {
+            "👹"
+        }
+    }
+
+    // Purely cosmetic; the map itself never changes between animation steps
+    fn animated_glyph(&self, tile: TileType) -> Option<&'static str> {
+        if self.ascii {
+            return None;
+        }
+
+        let frames: &[&'static str] = match tile {
+            TileType::Water => &["🟦", "🌊"],
+            TileType::Lava => &["🟧", "🟥"],
+            // The open flame flares up now and then; candles and lamps burn steadily
+            TileType::FlameA => &["🔥", "🔥", "💥"],
+            _ => return None,
+        };
+
+        let step = (self.frame_count / ANIMATION_PERIOD) as usize % frames.len();
+        Some(frames[step])
+    }
+
+    fn glyph_mode(&self) -> GlyphMode {
+        if self.ascii {
+            GlyphMode::Ascii
+        } else {
+            GlyphMode::Emoji
+        }
+    }
+
+    fn item_glyph(&self, item: ItemType) -> &'static str {
+        item.glyph(self.glyph_mode())
+    }
+
+    fn item_color(item: ItemType) -> Color {
+        match item.category() {
+            ItemCategory::Tool => Color::Green,
+            ItemCategory::KeyItem => Color::Yellow,
+            ItemCategory::Quest => Color::Magenta,
+        }
+    }
+
+    fn tile_color(&self, tile: TileType) -> Option<Color> {
+        match tile {
+            TileType::Goal | TileType::Checkpoint => Some(self.theme.goal),
+            TileType::Wall | TileType::CrackedWall | TileType::Bamboo => Some(self.theme.wall),
+            TileType::Water | TileType::ShallowWater => Some(self.theme.water),
+            TileType::Lava | TileType::Trap | TileType::Spikes => Some(self.theme.hazard),
+            TileType::Princess | TileType::Companion => Some(self.theme.ally),
+            _ => None,
+        }
+    }
+
+    fn tile_glyph(&self, tile: TileType) -> &'static str {
+        tile.glyph_when(self.glyph_mode(), self.spikes_raised)
+    }
+
+    pub fn beep(&self) {
+        let mut stdout = stdout();
+        if stdout.is_terminal() {
+            // A missed bell isn't worth interrupting the game over
+            let _ = self.beep_to(&mut stdout);
+        }
+    }
+
+    pub fn beep_to<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        if self.sound {
+            out.write_all(b"\x07")?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn show_death_message(&mut self) {
         self.show_message("    ☠️  You died ☠️");
     }
 
-    pub fn show_game_clear_message(&self) {
-        self.show_message("   🎊 Game clear 🎊");
+    fn game_clear_verdict(deaths: u32, kills: u32) -> String {
+        let deaths = match deaths {
+            0 => "Flawless, not a single death!".to_string(),
+            1 => "Cleared with 1 death!".to_string(),
+            _ => format!("Cleared with {} deaths!", deaths),
+        };
+        let kills = match kills {
+            0 => "A true pacifist.".to_string(),
+            1 => "1 enemy slain.".to_string(),
+            _ => format!("{} enemies slain.", kills),
+        };
+
+        format!("{} {}", deaths, kills)
+    }
+
+    pub fn compose_victory_screen(&self, summary: &RunSummary) -> String {
+        let art = [
+            r" |>>>                 |>>>",
+            r" |                    |",
+            r"_|_    _   _   _    _|_",
+            r"|  |__| |_| |_| |__|  |",
+            r"|                     |",
+            r"|   |^|    _    |^|   |",
+            r"|   |_|   | |   |_|   |",
+            r"|_________| |_________|",
+        ];
+        let title = if self.ascii {
+            "*** GAME CLEAR ***"
+        } else {
+            "🎊 Game clear 🎊"
+        };
+        let lines = [
+            Self::game_clear_verdict(summary.deaths, summary.kills),
+            String::new(),
+            format!("Time: {}", Leaderboard::format_time(summary.time)),
+            format!("Deaths: {}", summary.deaths),
+            format!("Enemies slain: {}", summary.kills),
+        ];
+        self.compose_end_screen(&art, title, &lines)
+    }
+
+    pub fn compose_defeat_screen(&self, summary: &RunSummary) -> String {
+        let art = [
+            r"   ______",
+            r"  /      \",
+            r" /  R.I.P \",
+            r" |        |",
+            r" |        |",
+            r"_|________|_",
+        ];
+        let title = if self.ascii {
+            "THE CASTLE STILL STANDS"
+        } else {
+            "🏯 The castle still stands 🏯"
+        };
+        let lines = [
+            format!("Level reached: {}", summary.level),
+            format!("Time: {}", Leaderboard::format_time(summary.time)),
+            format!("Deaths: {}", summary.deaths),
+            format!("Enemies slain: {}", summary.kills),
+        ];
+        self.compose_end_screen(&art, title, &lines)
     }
 
-    pub fn show_message(&self, message: &str) {
+    fn compose_end_screen(&self, art: &[&str], title: &str, lines: &[String]) -> String {
+        let (top, bottom, side) = if self.ascii {
+            (("+", "-", "+"), ("+", "-", "+"), "|")
+        } else {
+            (("╔", "═", "╗"), ("╚", "═", "╝"), "║")
+        };
+        let centered = |text: &str| {
+            let left = END_SCREEN_WIDTH.saturating_sub(text_width(text)) / 2;
+            let right = END_SCREEN_WIDTH.saturating_sub(text_width(text) + left);
+            format!(
+                "{}{}{}{}{}\r\n",
+                side,
+                " ".repeat(left),
+                text,
+                " ".repeat(right),
+                side
+            )
+        };
+
+        let mut screen = format!("{}{}{}\r\n", top.0, top.1.repeat(END_SCREEN_WIDTH), top.2);
+        screen.push_str(&centered(""));
+        // The art is centered as a block, so its lines stay aligned with each other
+        let art_width = art.iter().map(|line| text_width(line)).max().unwrap_or(0);
+        for line in art {
+            screen.push_str(&centered(&format!("{:<1$}", line, art_width)));
+        }
+        screen.push_str(&centered(""));
+        screen.push_str(&centered(title));
+        screen.push_str(&centered(""));
+        for line in lines {
+            screen.push_str(&centered(line));
+        }
+        screen.push_str(&centered(""));
+        screen.push_str(&centered("Press any key"));
+        screen.push_str(&format!(
+            "{}{}{}\r\n",
+            bottom.0,
+            bottom.1.repeat(END_SCREEN_WIDTH),
+            bottom.2
+        ));
+        screen
+    }
+
+    pub fn show_victory_screen(&mut self, summary: &RunSummary) -> io::Result<()> {
+        self.show_end_screen(&self.compose_victory_screen(summary))
+    }
+
+    pub fn show_defeat_screen(&mut self, summary: &RunSummary) -> io::Result<()> {
+        self.show_end_screen(&self.compose_defeat_screen(summary))
+    }
+
+    fn show_end_screen(&self, screen: &str) -> io::Result<()> {
         let mut stdout = stdout();
+        stdout.execute(Clear(ClearType::All))?;
+        stdout.execute(MoveTo(0, 0))?;
+        stdout.execute(Hide)?;
+        write!(stdout, "{}", screen)?;
+        stdout.flush()?;
+
+        loop {
+            if let Event::Key(key_event) = event::read()? {
+                if key_event.kind != KeyEventKind::Release {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    pub fn last_message(&self) -> Option<&str> {
+        self.messages.back().map(|message| message.text.as_str())
+    }
+
+    pub fn show_message(&mut self, message: &str) {
+        self.push_message(Message {
+            text: message.to_string(),
+            until: None,
+            dismiss_on_key: false,
+        });
+    }
+
+    pub fn show_message_for(&mut self, message: &str, duration: Duration, dismiss_on_key: bool) {
+        self.push_message(Message {
+            text: message.to_string(),
+            until: Some(Instant::now() + scaled(duration, self.speed)),
+            dismiss_on_key,
+        });
+    }
+
+    fn push_message(&mut self, message: Message) {
+        // Messages that are gone from the screen shouldn't take up room in the log
+        self.messages.retain(Message::is_showing);
+        if self.messages.len() == MESSAGE_LOG_SIZE {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message);
+    }
+
+    pub fn dismiss_messages(&mut self) {
+        let now = Instant::now();
+        for message in &mut self.messages {
+            if message.dismiss_on_key {
+                message.until = Some(now);
+            }
+        }
+    }
+}
+
+struct Message {
+    text: String,
+    until: Option<Instant>,
+    dismiss_on_key: bool,
+}
+
+impl Message {
+    fn remaining(&self) -> Option<Duration> {
+        self.until
+            .map(|until| until.saturating_duration_since(Instant::now()))
+    }
+
+    fn is_showing(&self) -> bool {
+        self.remaining() != Some(Duration::ZERO)
+    }
+}
+
+#[test]
+fn test_fits_terminal() {
+    // Small maps are limited by the HUD width
+    assert_eq!(UI::required_size((10, 11)), (40, 19));
+    assert!(UI::fits_terminal((80, 24), (10, 11)));
+    assert!(UI::fits_terminal((40, 19), (10, 11)));
+    assert!(!UI::fits_terminal((39, 19), (10, 11)));
+    assert!(!UI::fits_terminal((40, 18), (10, 11)));
+
+    // Wide maps need two columns per tile
+    assert_eq!(UI::required_size((20, 30)), (60, 29));
+    assert!(!UI::fits_terminal((59, 30), (20, 30)));
+    assert!(UI::fits_terminal((60, 29), (20, 30)));
+}
+
+#[test]
+fn test_controls_wrap_to_the_hud_width() {
+    for diagonal in [false, true] {
+        let ui = UI::with_config(&Config {
+            diagonal,
+            ..Config::default()
+        });
+        let controls = ui.controls();
+        assert!(controls.len() <= CONTROL_ROWS as usize);
+        for line in &controls {
+            assert!(line.len() <= HUD_WIDTH as usize, "{:?} is too wide", line);
+        }
+        assert!(controls.concat().contains("?:Legend"));
+    }
+}
+
+#[test]
+fn test_crystals_render_distinct_glyphs() {
+    let mut map = vec![vec![TileType::Empty; 3]; 2];
+    map[0] = vec![TileType::CrystalA, TileType::CrystalB, TileType::CrystalC];
+    map[1] = vec![TileType::FlameA, TileType::FlameB, TileType::FlameC];
+
+    let level = Level {
+        map,
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 5, col: 5 },
+        map_size: (2, 3),
+        header: Default::default(),
+    };
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let ascii = Config {
+        ascii: true,
+        ..Config::default()
+    };
 
-        stdout
-            .execute(MoveTo(0, self.last_rendered_height))
-            .unwrap();
-        stdout.execute(Clear(ClearType::CurrentLine)).unwrap();
+    for ui in [UI::new(), UI::with_config(&ascii)] {
+        let frame = ui.compose_frame(&level, &player);
 
-        writeln!(stdout, "{}", message).unwrap();
-        stdout.flush().unwrap();
+        let crystals = [TileType::CrystalA, TileType::CrystalB, TileType::CrystalC]
+            .map(|tile| ui.tile_glyph(tile));
+        let flames =
+            [TileType::FlameA, TileType::FlameB, TileType::FlameC].map(|tile| ui.tile_glyph(tile));
 
-        std::thread::sleep(std::time::Duration::from_secs_f32(1.2));
+        for glyphs in [crystals, flames] {
+            assert_ne!(glyphs[0], glyphs[1]);
+            assert_ne!(glyphs[1], glyphs[2]);
+            assert_ne!(glyphs[0], glyphs[2]);
 
-        stdout
-            .execute(MoveTo(0, self.last_rendered_height))
-            .unwrap();
-        stdout.execute(Clear(ClearType::CurrentLine)).unwrap();
-        stdout.flush().unwrap();
+            for glyph in glyphs {
+                assert!(frame.contains(glyph));
+            }
+        }
     }
 }
+
+#[test]
+fn test_every_glyph_is_unique_in_each_mode() {
+    let ascii = Config {
+        ascii: true,
+        ..Config::default()
+    };
+
+    for mut ui in [UI::new(), UI::with_config(&ascii)] {
+        let mode = ui.glyph_mode();
+        let mut glyphs: Vec<(String, String)> = Vec::new();
+        let mut add = |name: String, glyph: &str| {
+            let repeated_facing = glyphs
+                .iter()
+                .any(|(seen_name, seen)| seen == glyph && seen_name.starts_with("player"));
+            if !(repeated_facing && name.starts_with("player")) {
+                glyphs.push((name, glyph.to_string()));
+            }
+        };
+
+        let directed = Direction::ALL
+            .into_iter()
+            .flat_map(|dir| [TileType::OneWay(dir), TileType::Conveyor(dir)]);
+        let tiles = TileType::ALL
+            .into_iter()
+            .filter(|tile| !matches!(tile, TileType::OneWay(_) | TileType::Conveyor(_)))
+            .chain(directed);
+        for tile in tiles {
+            // Meant to pass for their look-alikes
+            if !matches!(
+                tile,
+                TileType::CrackedWall | TileType::HiddenTrap | TileType::HookEnd
+            ) {
+                add(format!("{:?}", tile), tile.glyph(mode));
+            }
+        }
+        add(
+            "sunk spikes".to_string(),
+            TileType::Spikes.glyph_when(mode, false),
+        );
+
+        // Opposite diagonals share a glyph, and emoji has one for every facing
+        for dir in Direction::ALL.into_iter().chain(Direction::DIAGONALS) {
+            add(format!("player {:?}", dir), ui.player_glyph(dir));
+        }
+        add("danger".to_string(), ui.danger_glyph());
+        add("hint".to_string(), ui.hint_glyph());
+        add("footprint".to_string(), ui.footprint_glyph());
+        add("enemy".to_string(), ui.enemy_glyph());
+        add("platform".to_string(), ui.platform_glyph());
+        add("oni".to_string(), ui.oni_glyph());
+
+        // Every animation frame past the tile's own glyph
+        for tile in TileType::ALL {
+            let mut frames: Vec<&str> = Vec::new();
+            for step in 0..4 {
+                ui.frame_count = step * ANIMATION_PERIOD;
+                if let Some(frame) = ui.animated_glyph(tile) {
+                    if frame != tile.glyph(mode) && !frames.contains(&frame) {
+                        frames.push(frame);
+                    }
+                }
+            }
+            for frame in frames {
+                glyphs.push((format!("{:?} animation", tile), frame.to_string()));
+            }
+        }
+
+        for (at, (name, glyph)) in glyphs.iter().enumerate() {
+            for (other, other_glyph) in &glyphs[at + 1..] {
+                assert_ne!(
+                    glyph, other_glyph,
+                    "{} and {} share the {:?} glyph",
+                    name, other, mode
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_message_log_keeps_last_five() {
+    let mut ui = UI::new();
+
+    for i in 1..=6 {
+        ui.show_message(&format!("Message {}", i));
+    }
+
+    assert_eq!(ui.messages.len(), MESSAGE_LOG_SIZE);
+    assert_eq!(ui.messages.front().unwrap().text, "Message 2");
+    assert_eq!(ui.messages.back().unwrap().text, "Message 6");
+
+    let level = Level {
+        map: vec![vec![TileType::Empty; 3]; 3],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (3, 3),
+        header: Default::default(),
+    };
+    let frame = ui.compose_frame(&level, &Player::new());
+
+    // Newest message at the bottom
+    assert!(!frame.contains("Message 1"));
+    assert!(frame.find("Message 2").unwrap() < frame.find("Message 6").unwrap());
+}
+
+#[test]
+fn test_god_mode_shown_in_hud() {
+    let level = Level {
+        map: vec![vec![TileType::Empty; 3]; 3],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (3, 3),
+        header: Default::default(),
+    };
+    let player = Player::new();
+
+    let god = Config {
+        god: true,
+        ..Config::default()
+    };
+
+    assert!(!UI::new()
+        .compose_frame(&level, &player)
+        .contains("GOD MODE"));
+    assert!(UI::with_config(&god)
+        .compose_frame(&level, &player)
+        .contains("GOD MODE"));
+
+    let peaceful = Config {
+        peaceful: true,
+        ..Config::default()
+    };
+    assert!(UI::with_config(&peaceful)
+        .compose_frame(&level, &player)
+        .contains("PEACEFUL"));
+}
+
+#[test]
+fn test_game_clear_verdict_mentions_deaths() {
+    assert!(UI::game_clear_verdict(0, 0).contains("not a single death"));
+    assert!(UI::game_clear_verdict(1, 0).contains("1 death!"));
+    assert!(UI::game_clear_verdict(7, 0).contains("Cleared with 7 deaths!"));
+    assert!(UI::game_clear_verdict(0, 0).contains("pacifist"));
+    assert!(UI::game_clear_verdict(0, 3).contains("3 enemies slain"));
+}
+
+#[test]
+fn test_animated_tiles_change_between_frames() {
+    let level = Level {
+        map: vec![vec![TileType::Water, TileType::Wall]],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 5, col: 5 },
+        map_size: (1, 2),
+        header: Default::default(),
+    };
+    let mut flames = UI::new();
+    let flame = |ui: &UI| ui.animated_glyph(TileType::FlameA);
+    assert_eq!(flame(&flames), Some("🔥"));
+    flames.frame_count = 2 * ANIMATION_PERIOD;
+    assert_eq!(flame(&flames), Some("💥"));
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let mut ui = UI::new();
+    let first = ui.compose_frame(&level, &player);
+    ui.frame_count = ANIMATION_PERIOD;
+    let second = ui.compose_frame(&level, &player);
+
+    assert_ne!(first, second);
+    assert!(first.contains("🌲") && second.contains("🌲"));
+
+    // ASCII mode never animates
+    let ascii = Config {
+        ascii: true,
+        ..Config::default()
+    };
+    let mut ui = UI::with_config(&ascii);
+    let first = ui.compose_frame(&level, &player);
+    ui.frame_count = ANIMATION_PERIOD;
+    assert_eq!(first, ui.compose_frame(&level, &player));
+}
+
+#[test]
+fn test_inventory_overlay_lists_descriptions() {
+    let level = Level {
+        map: vec![vec![TileType::Empty; 3]; 3],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (3, 3),
+        header: Default::default(),
+    };
+    let mut player = Player::new();
+    player.add_item(ItemType::Key);
+    player.add_item(ItemType::Hook);
+
+    let mut ui = UI::new();
+    ui.open_overlay(Overlay::Inventory);
+    let frame = ui.compose_frame(&level, &player);
+
+    assert!(frame.contains(ItemType::Key.description()));
+    assert!(frame.contains(ItemType::Hook.description()));
+    assert!(!frame.contains(ItemType::Axe.description()));
+
+    assert!(ui.close_overlay());
+    assert!(!ui.close_overlay());
+    assert!(!ui.compose_frame(&level, &player).contains("Press any key"));
+}
+
+#[test]
+fn test_legend_overlay_lists_every_tile() {
+    let level = Level {
+        map: vec![vec![TileType::Empty; 3]; 3],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 0 },
+        map_size: (3, 3),
+        header: Default::default(),
+    };
+    let player = Player::new();
+
+    for ascii in [false, true] {
+        let mut ui = UI::with_config(&Config {
+            ascii,
+            ..Config::default()
+        });
+        ui.terminal_rows = 24;
+        ui.open_overlay(Overlay::Legend);
+
+        // Every page fits the terminal, and any key turns to the next until the last closes it
+        let mut frame = String::new();
+        let mut pages = 0;
+        loop {
+            let page = ui.compose_frame(&level, &player);
+            assert!(page.lines().count() <= 24);
+            frame.push_str(&page);
+            pages += 1;
+            ui.close_overlay();
+            if ui.overlay.is_none() {
+                break;
+            }
+        }
+        assert!(pages > 1);
+        assert!(frame.contains(&format!(" Legend (1/{})", pages)));
+
+        for tile in TileType::ALL {
+            let entry = format!(" {} {}\r\n", ui.tile_glyph(tile), tile.description());
+            assert!(frame.contains(&entry), "legend is missing {:?}", tile);
+        }
+        for item in ItemType::ALL {
+            assert!(
+                frame.contains(item.description()),
+                "legend is missing {:?}",
+                item
+            );
+        }
+    }
+}
+
+#[test]
+fn test_status_line_shows_best_time() {
+    let mut ui = UI::new();
+    let mut player = Player::new();
+    assert!(ui.status_line(&player).contains("Best: --:--"));
+    assert!(!ui.status_line(&player).contains("Stamina"));
+
+    ui.set_level_times(Duration::from_secs(12), Some(Duration::from_secs(42)));
+    player.swim();
+    let status = ui.status_line(&player);
+    assert!(status.contains("Time: 00:12"));
+    assert!(status.contains("Best: 00:42"));
+    assert!(status.contains("Stamina: 4/5"));
+}
+
+#[test]
+fn test_status_line_suggests_items_to_use_nearby() {
+    let mut ui = UI::with_config(&Config {
+        ascii: true,
+        ..Config::default()
+    });
+    let player = Player::new();
+    assert!(!ui.status_line(&player).contains("Use:"));
+
+    ui.set_usable_items(vec![
+        (Direction::Right, ItemType::Key),
+        (Direction::Up, ItemType::Axe),
+    ]);
+    assert!(ui.status_line(&player).contains(" | Use: k east, a north"));
+}
+
+#[test]
+fn test_escorted_princess_trails_player() {
+    let level = Level {
+        map: vec![vec![TileType::Empty; 3]],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 0, col: 2 },
+        map_size: (1, 3),
+        header: Default::default(),
+    };
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let mut ui = UI::new();
+    assert!(!ui.compose_frame(&level, &player).contains("🧝‍♀️"));
+
+    ui.set_escort(Some(Position { row: 0, col: 1 }));
+    assert!(ui.compose_frame(&level, &player).starts_with("・🧝‍♀️🥷"));
+}
+
+#[test]
+fn test_beep_respects_sound_setting() {
+    let mut output = Vec::new();
+    UI::with_config(&Config::default())
+        .beep_to(&mut output)
+        .unwrap();
+    assert_eq!(output, b"\x07");
+
+    let quiet = Config {
+        sound: false,
+        ..Config::default()
+    };
+    let mut output = Vec::new();
+    UI::with_config(&quiet).beep_to(&mut output).unwrap();
+    assert!(output.is_empty());
+}
+
+#[test]
+fn test_trap_glyph_shown_only_after_reveal() {
+    let mut level = Level {
+        map: vec![vec![TileType::Empty, TileType::HiddenTrap]],
+        enemies: vec![],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
+        player_start: Position { row: 5, col: 5 },
+        map_size: (1, 2),
+        header: Default::default(),
+    };
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let ui = UI::new();
+    assert!(ui.compose_frame(&level, &player).starts_with("・・\r\n"));
+
+    level.set_tile(&Position { row: 0, col: 1 }, TileType::Trap);
+    assert!(ui.compose_frame(&level, &player).starts_with("・📍\r\n"));
+}
+
+#[test]
+fn test_color_applies_to_ascii_frames_only() {
+    let level: Level = "ttt\ntpg\nttt".parse().unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let mut ui = UI::new();
+    let mut settings = Settings {
+        color: true,
+        ascii: false,
+        sound: false,
+        difficulty: crate::classes::config::Difficulty::Normal,
+    };
+    ui.apply_settings(&settings);
+    assert!(!ui.compose_frame(&level, &player).contains('\x1b'));
+
+    settings.ascii = true;
+    ui.apply_settings(&settings);
+    assert!(ui.compose_frame(&level, &player).contains('\x1b'));
+
+    let mut output = Vec::new();
+    ui.dump_map(&mut output, &level, &player).unwrap();
+    assert!(!output.contains(&0x1b));
+}
+
+#[test]
+fn test_dump_map_writes_plain_lines() {
+    let level: Level = "ttt\ntpg\nttt".parse().unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let ascii = Config {
+        ascii: true,
+        ..Config::default()
+    };
+    let mut output = Vec::new();
+    UI::with_config(&ascii)
+        .dump_map(&mut output, &level, &player)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "# # # \n# v G \n# # # \n"
+    );
+}
+
+#[test]
+fn test_custom_glyphs_replace_player_and_enemies() {
+    let level: Level = "ttttt\ntpset\nttttt".parse().unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let skinned = Config {
+        ascii: true,
+        player_glyph: crate::classes::width::fit_cell("🦊"),
+        enemy_glyph: crate::classes::width::fit_cell("&"),
+        ..Config::default()
+    };
+    assert_eq!(
+        UI::with_config(&skinned).compose_map(&level, &player),
+        "# # # # # \r\n# 🦊. & # \r\n# # # # # \r\n"
+    );
+}
+
+#[test]
+fn test_compass_points_to_goal_out_of_view() {
+    let level: Level = "tttttttttt\ntsssssssst\ntpssssssgt\ntsssssssst\ntttttttttt"
+        .parse()
+        .unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let mut ui = UI::with_config(&Config {
+        ascii: true,
+        ..Config::default()
+    });
+    assert!(!ui.compose_frame(&level, &player).contains("Goal:"));
+
+    // Only the area around the player is drawn, so the compass shows the way
+    ui.view = Some((3, 4));
+    let frame = ui.compose_frame(&level, &player);
+    assert!(frame.starts_with("# . . . \r\n# v . . \r\n# . . . \r\n Inventory"));
+    assert!(frame.contains("Goal: E"));
+
+    player.reset_position(Position { row: 1, col: 7 });
+    assert!(!ui.compose_frame(&level, &player).contains("Goal:"));
+}
+
+#[test]
+fn test_photo_mode_pans_the_view_without_moving_the_player() {
+    let level: Level = "tttttttttt\ntsssssssst\ntpssssssgt\ntsssssssst\ntttttttttt"
+        .parse()
+        .unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+    let mut ui = UI::with_config(&Config {
+        ascii: true,
+        ..Config::default()
+    });
+    ui.view = Some((3, 4));
+    let around_player = ui.compose_map(&level, &player);
+
+    // Panning outside photo mode does nothing
+    ui.pan_camera(Direction::Right, &level);
+    assert_eq!(ui.compose_map(&level, &player), around_player);
+
+    ui.toggle_photo_mode(&player);
+    assert!(ui.is_photo_mode());
+    for _ in 0..7 {
+        ui.pan_camera(Direction::Right, &level);
+    }
+    assert_eq!(ui.camera, Some(Position { row: 2, col: 8 }));
+    assert!(ui
+        .compose_map(&level, &player)
+        .starts_with(". . . # \r\n. . G # "));
+    assert_eq!(player.pos, level.player_start);
+
+    // The camera stays on the map
+    for _ in 0..5 {
+        ui.pan_camera(Direction::Right, &level);
+    }
+    assert_eq!(ui.camera, Some(Position { row: 2, col: 9 }));
+
+    ui.recenter_camera(&player);
+    assert_eq!(ui.compose_map(&level, &player), around_player);
+
+    ui.pan_camera(Direction::Right, &level);
+    ui.toggle_photo_mode(&player);
+    assert!(!ui.is_photo_mode());
+    assert_eq!(ui.compose_map(&level, &player), around_player);
+}
+
+#[test]
+fn test_scrolled_view_needs_room_for_the_hud() {
+    assert_eq!(UI::scrolled_view((80, 24)), Some((24 - HUD_HEIGHT, 40)));
+    assert_eq!(UI::scrolled_view((39, 24)), None);
+    assert_eq!(UI::scrolled_view((80, HUD_HEIGHT + 4)), None);
+}
+
+#[test]
+fn test_spacious_mode_spreads_out_the_map() {
+    let level: Level = "ttt\ntpg\nttt".parse().unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let ui = UI::with_config(&Config {
+        ascii: true,
+        spacious: true,
+        ..Config::default()
+    });
+
+    assert_eq!(
+        ui.compose_map(&level, &player),
+        "#  #  #  \r\n\r\n#  v  G  \r\n\r\n#  #  #  \r\n\r\n"
+    );
+    assert_eq!(
+        UI::required_size_for((10, 11), SPACIOUS_TILE_CELL),
+        (40, 2 * 10 + HUD_HEIGHT)
+    );
+    assert_eq!(
+        UI::scrolled_view_for((90, 24), SPACIOUS_TILE_CELL),
+        Some(((24 - HUD_HEIGHT) / 2, 30))
+    );
+}
+
+#[test]
+fn test_describe_names_the_surroundings() {
+    let level: Level = "tttttt\ntpsskt\ntdtttt\ntttttt".parse().unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    assert_eq!(
+        UI::new().describe(&level, &player),
+        "Forest to the north, empty to the east, locked door to the south, forest to the west. \
+         Key three tiles east."
+    );
+}
+
+#[test]
+fn test_key_press_dismisses_timed_messages() {
+    let mut ui = UI::new();
+    let player = Player::new();
+    let level: Level = "ttt\ntpg\nttt".parse().unwrap();
+
+    ui.show_message("Stays in the log");
+    ui.show_message_for("Waits for its time", Duration::from_secs(60), false);
+    ui.show_message_for("Gone at the next key", Duration::from_secs(60), true);
+    assert!(ui.messages[2].remaining() > Some(Duration::ZERO));
+
+    ui.dismiss_messages();
+    assert_eq!(ui.messages[2].remaining(), Some(Duration::ZERO));
+    assert!(ui.messages[1].remaining() > Some(Duration::ZERO));
+    assert_eq!(ui.messages[0].remaining(), None);
+
+    let frame = ui.compose_frame(&level, &player);
+    assert!(frame.contains("Stays in the log"));
+    assert!(frame.contains("Waits for its time"));
+    assert!(!frame.contains("Gone at the next key"));
+}
+
+#[test]
+fn test_flash_inverts_a_few_frames() {
+    let mut ui = UI::new();
+    ui.flash();
+    assert_eq!(ui.flash_frames, FLASH_FRAMES);
+
+    let reversed = format!("{}frame{}", Attribute::Reverse, Attribute::Reset);
+    for _ in 0..FLASH_FRAMES {
+        assert_eq!(ui.apply_flash("frame".to_string()), reversed);
+    }
+    assert_eq!(ui.flash_frames, 0);
+    assert_eq!(ui.apply_flash("frame".to_string()), "frame");
+
+    // With flashing off, nothing changes
+    let mut ui = UI::with_config(&Config {
+        flash: false,
+        ..Config::default()
+    });
+    ui.flash();
+    assert_eq!(ui.flash_frames, 0);
+    assert_eq!(ui.apply_flash("frame".to_string()), "frame");
+}
+
+#[test]
+fn test_victory_screen_shows_the_run_stats() {
+    let summary = RunSummary {
+        ended: 1_700_000_000,
+        level: 10,
+        moves: 812,
+        deaths: 3,
+        kills: 5,
+        time: Duration::from_secs(754),
+        items: 9,
+    };
+
+    let screen = UI::new().compose_victory_screen(&summary);
+    assert!(screen.starts_with("╔"));
+    assert!(screen.contains("🎊 Game clear 🎊"));
+    assert!(screen.contains("Time: 12:34"));
+    assert!(screen.contains("Deaths: 3"));
+    assert!(screen.contains("Enemies slain: 5"));
+
+    // In ASCII mode the frame is plain text, every line as wide as the others
+    let ui = UI::with_config(&Config {
+        ascii: true,
+        ..Config::default()
+    });
+    let screen = ui.compose_victory_screen(&summary);
+    assert!(screen.is_ascii());
+    assert!(screen.contains("*** GAME CLEAR ***"));
+    assert!(screen.contains("Time: 12:34"));
+    let lines: Vec<&str> = screen.lines().collect();
+    assert!(lines.iter().all(|line| line.len() == END_SCREEN_WIDTH + 2));
+
+    let screen = ui.compose_defeat_screen(&summary);
+    assert!(screen.contains("THE CASTLE STILL STANDS"));
+    assert!(screen.contains("Level reached: 10"));
+}
+
+#[test]
+fn test_theme_colors_the_map() {
+    let level: Level = "ttt\ntpg\nttt".parse().unwrap();
+    let mut player = Player::new();
+    player.reset_position(level.player_start);
+
+    let theme = Theme {
+        wall: Color::Rgb { r: 1, g: 2, b: 3 },
+        ..Theme::default()
+    };
+    let ui = UI::with_config(&Config {
+        ascii: true,
+        color: true,
+        theme,
+        ..Config::default()
+    });
+    assert_eq!(ui.tile_color(TileType::Wall), Some(theme.wall));
+    assert_eq!(ui.tile_color(TileType::Goal), Some(Theme::default().goal));
+
+    let wall = ui.tile_glyph(TileType::Wall).with(theme.wall).to_string();
+    assert!(ui.compose_frame(&level, &player).starts_with(&wall));
+}
@@ -0,0 +1,139 @@
+//! Validation of all map files in a directory, used by the `--validate` option.
+//!
+//! Every `level_<N>.txt` file in the directory is checked with `Level::lint`,
+//! and a per-level report is produced so designers can see which maps need fixing.
+//! The same scan drives `--list-levels`, which prints a table of the levels instead.
+//!
+//! # Functions
+//!
+//! - `validate_maps`: Lints every level file in a directory. Returns the report, along with
+//!   whether all levels passed.
+//! - `list_levels`: Builds a table of every level file in a directory, with the name and par time
+//!   from its header. Levels without them show `-`, and files that don't load show why.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let (report, ok) = validate_maps("maps");
+//! println!("{}", report);
+//! std::process::exit(if ok { 0 } else { 1 });
+//! ```
+
+use crate::classes::leaderboard::Leaderboard;
+use crate::classes::level::Level;
+use std::fs;
+use std::path::Path;
+
+// The numbers of the level files in a directory, in order, or a message telling why there are none
+fn level_numbers(dir: &Path) -> Result<Vec<usize>, String> {
+    let mut levels: Vec<usize> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                name.strip_prefix("level_")?
+                    .strip_suffix(".txt")?
+                    .parse()
+                    .ok()
+            })
+            .collect(),
+        Err(err) => return Err(format!("Couldn't read {}: {}", dir.display(), err)),
+    };
+    levels.sort();
+
+    if levels.is_empty() {
+        return Err(format!("No level files found in {}", dir.display()));
+    }
+    Ok(levels)
+}
+
+pub fn validate_maps<P: AsRef<Path>>(dir: P) -> (String, bool) {
+    let dir = dir.as_ref();
+    let levels = match level_numbers(dir) {
+        Ok(levels) => levels,
+        Err(message) => return (message, false),
+    };
+
+    let mut report = String::new();
+    let mut all_ok = true;
+
+    for level_number in levels {
+        let path = Level::path(dir, level_number);
+        let problems = match Level::read_contents(&path) {
+            Ok(contents) => Level::lint(&contents),
+            Err(err) => vec![err.to_string()],
+        };
+
+        if problems.is_empty() {
+            report.push_str(&format!("level_{}.txt: OK\n", level_number));
+        } else {
+            all_ok = false;
+            report.push_str(&format!("level_{}.txt: FAILED\n", level_number));
+            for problem in problems {
+                report.push_str(&format!("  - {}\n", problem));
+            }
+        }
+    }
+
+    (report, all_ok)
+}
+
+pub fn list_levels<P: AsRef<Path>>(dir: P) -> (String, bool) {
+    let dir = dir.as_ref();
+    let levels = match level_numbers(dir) {
+        Ok(levels) => levels,
+        Err(message) => return (message, false),
+    };
+
+    let mut table = format!("{:<7}{:<32}{}\n", "Level", "Name", "Par");
+    for level_number in levels {
+        let (name, par) = match Level::read(Level::path(dir, level_number)) {
+            Ok(level) => (
+                level.header.name.unwrap_or_else(|| "-".to_string()),
+                level
+                    .header
+                    .par
+                    .map_or("-".to_string(), Leaderboard::format_time),
+            ),
+            Err(err) => (format!("(couldn't load: {})", err), "-".to_string()),
+        };
+        table.push_str(&format!("{:<7}{:<32}{}\n", level_number, name, par));
+    }
+
+    (table, true)
+}
+
+#[test]
+fn test_bundled_maps_are_valid() {
+    let (report, ok) = validate_maps("maps");
+
+    assert!(ok, "{}", report);
+    assert_eq!(report.lines().count(), 10);
+}
+
+#[test]
+fn test_list_levels_shows_names_and_par_times() {
+    let dir = std::env::temp_dir().join(format!("ghostblade_list_levels_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        Level::path(&dir, 1),
+        "# name: The Outer Wall\n# par: 75\ntttt\ntpgt\ntttt",
+    )
+    .unwrap();
+    fs::write(Level::path(&dir, 2), "tttt\ntpgt\ntttt").unwrap();
+
+    let (table, ok) = list_levels(&dir);
+    let rows: Vec<Vec<&str>> = table
+        .lines()
+        .skip(1)
+        .map(|line| vec![line[..7].trim(), line[7..39].trim(), line[39..].trim()])
+        .collect();
+    assert!(ok);
+    assert_eq!(
+        rows,
+        vec![vec!["1", "The Outer Wall", "01:15"], vec!["2", "-", "-"]]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(list_levels("maps").0.lines().count(), 11);
+}
@@ -0,0 +1,137 @@
+//! Helpers for measuring text in terminal columns.
+//!
+//! Emoji and other wide characters take up two columns, while joiners and variation
+//! selectors take up none, so the length of a string says little about the room it
+//! needs on screen. The UI lines up map cells and framed screens with these helpers,
+//! and the configuration checks custom glyphs with them.
+//!
+//! # Constants
+//!
+//! - `TILE_WIDTH`: The terminal columns each map tile is drawn in.
+//!
+//! # Functions
+//!
+//! - `column_width`: Returns the terminal columns a single character takes up on its own.
+//! - `text_width`: Returns the terminal columns a whole string takes up. A variation selector 16 (U+FE0F) asks for
+//!   the emoji form of the character before it, which is two columns wide, and characters joined by a zero-width
+//!   joiner (U+200D) are drawn as a single emoji, so they count once.
+//! - `fit_cell`: Checks that a custom glyph fills exactly one map cell (one or two terminal columns) and pads it
+//!   to two columns, or returns `None` when it would break the alignment of the map.
+
+// Each tile is drawn two columns wide
+pub const TILE_WIDTH: u16 = 2;
+
+// Terminal columns a character takes up: none for joiners and variation selectors,
+// two for emoji and other wide characters, one for everything else
+pub fn column_width(ch: char) -> u16 {
+    match ch as u32 {
+        0x200B..=0x200D | 0xFE00..=0xFE0F | 0x0300..=0x036F => 0,
+        // The symbols among the arrows, shapes, and dingbats that are drawn as emoji by default
+        0x231A..=0x231B
+        | 0x23E9..=0x23EC
+        | 0x23F0
+        | 0x23F3
+        | 0x25FD..=0x25FE
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x267F
+        | 0x2693
+        | 0x26A1
+        | 0x26AA..=0x26AB
+        | 0x26BD..=0x26BE
+        | 0x26C4..=0x26C5
+        | 0x26CE
+        | 0x26D4
+        | 0x26EA
+        | 0x26F2..=0x26F3
+        | 0x26F5
+        | 0x26FA
+        | 0x26FD
+        | 0x2705
+        | 0x270A..=0x270B
+        | 0x2728
+        | 0x274C
+        | 0x274E
+        | 0x2753..=0x2755
+        | 0x2757
+        | 0x2795..=0x2797
+        | 0x27B0
+        | 0x27BF
+        | 0x2B1B..=0x2B1C
+        | 0x2B50
+        | 0x2B55 => 2,
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1FAFF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+pub fn text_width(text: &str) -> usize {
+    let mut width = 0;
+    // The width of the last character counted, and whether the next one joins it
+    let mut last = 0;
+    let mut joined = false;
+
+    for ch in text.chars() {
+        match ch {
+            '\u{200D}' => joined = true,
+            '\u{FE0F}' => {
+                if last == 1 {
+                    width += 1;
+                    last = 2;
+                }
+            }
+            _ if joined => joined = false,
+            _ => {
+                let columns = column_width(ch) as usize;
+                if columns > 0 {
+                    width += columns;
+                    last = columns;
+                }
+            }
+        }
+    }
+
+    width
+}
+
+pub fn fit_cell(glyph: &str) -> Option<String> {
+    match text_width(glyph) as u16 {
+        1 => Some(format!("{} ", glyph)),
+        TILE_WIDTH => Some(glyph.to_string()),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_wide_and_zero_width_characters_are_measured() {
+    assert_eq!(text_width("ab"), 2);
+    assert_eq!(text_width("🦊"), 2);
+    assert_eq!(text_width("⚓"), 2);
+    // Without a variation selector, a symbol keeps its narrow text form
+    assert_eq!(text_width("⚔"), 1);
+    assert_eq!(text_width("╔══╗"), 4);
+}
+
+#[test]
+fn test_fit_cell_pads_narrow_glyphs_and_rejects_wide_ones() {
+    // Anything wider than a single map cell would push the rest of the row out of line
+    assert_eq!(fit_cell("&"), Some("& ".to_string()));
+    // Emoji forms and joined sequences fill the cell as they are
+    for glyph in ["🧝‍♀️", "⚔️", "⛰️"] {
+        assert_eq!(text_width(glyph), 2, "{}", glyph);
+        assert_eq!(fit_cell(glyph), Some(glyph.to_string()));
+    }
+    assert_eq!(fit_cell("🦊🦊"), None);
+    assert_eq!(fit_cell("ab"), Some("ab".to_string()));
+    assert_eq!(fit_cell("abc"), None);
+    assert_eq!(fit_cell(""), None);
+}
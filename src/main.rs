@@ -3,8 +3,13 @@
 //! This function initializes the game state, sets up the terminal
 //! in raw mode, and runs the main game loop. The game loop handles
 //! user input, updates the game state, processes collisions, and renders
-//! the game at a fixed frame rate. It also handles level progression,
-//! interactions, and game completion.
+//! the game at a configurable frame rate. Input is polled continuously
+//! between frames, so movement does not wait for the next render. Every key
+//! waiting in the terminal is read into an `InputQueue` and applied in the order
+//! it was pressed, so quick bursts of keys aren't dropped, while key releases and
+//! key repeats don't count as extra presses. It also
+//! handles level progression, interactions, and game completion. After the game
+//! is cleared, the player may start over as New Game+, choosing which items to carry.
 //!
 //! # Returns
 //!
@@ -17,90 +22,587 @@
 //! - `s` or `Arrow Down`: Move the player down.
 //! - `a` or `Arrow Left`: Move the player left.
 //! - `d` or `Arrow Right`: Move the player right.
-//! - `q` or `Escape`: Quit the game.
+//! - `q`, `e`, `z`, `c` or numpad `7`, `9`, `1`, `3`: Move diagonally (with `--diagonal`).
+//!   In this mode only `Escape` quits.
+//! - `u`: Ring the Wind Chime to stun nearby enemies.
+//! - `.` or `Space`: Wait in place until the guards take their next step.
+//! - `h`: Highlight the next step toward the goal (limited hints per level).
+//! - `i`: Show the inventory with item descriptions. Any key returns to the game.
+//! - `?`: Show the map legend, listing what every glyph means. Any key turns to the next page, and the
+//!   last page returns to the game.
+//! - `l`: Look around; describe the surroundings in words on the message log, for screen readers.
+//! - `p`: Photo mode; freeze the game and pan the camera around the map with the movement keys,
+//!   for screenshots. `r` brings the camera back to the player, and `p` or `Escape` resumes the game.
+//! - `F2`: Practice restart; start the current level over without counting a death.
+//! - `F3`: Skip the current level without solving it (with `--allow-skip`). Press `y` to confirm.
+//! - `o`: Open the options screen to change colors, ASCII mode, sound, and difficulty.
+//!   The options changed there are saved to `settings.txt` and used the next time the game starts.
+//! - `q` or `Escape`: Quit the game. Press `y` to confirm, or any other key to keep playing.
+//!
+//! # Options
+//! - `--fps N`: Render at `N` frames per second (default 10).
+//! - `--speed X`: Run everything timed at `X` times the normal pace, from 0.1 to 10; `0.5` halves the guards' speed,
+//!   keeps messages up twice as long, and renders half as many frames.
+//! - `--ascii`: Render with plain ASCII characters instead of emoji.
+//! - `--color`: Color the map in ASCII mode.
+//! - `--no-ascii`, `--no-color`: Render with emoji, or without colors, for this run even if the saved settings say otherwise.
+//! - `--theme NAME|FILE`: Color the map with the `classic`, `highcontrast`, or `solarized` theme, or one read from a theme file.
+//! - `--spacious`: Low-vision mode; space the map tiles out with blank columns and rows.
+//! - `--diagonal`: Enable diagonal movement.
+//! - `--god`: Debug mode; enemies, oni, and the boss cannot kill the player.
+//! - `--allow-skip`: Debug option; `F3` skips the current level after confirmation.
+//! - `--peaceful`: No patrol guards, and the oni and the boss only block the way, for exploring the puzzles.
+//! - `--difficulty easy|normal|hard`: Fewer or more enemies on each level.
+//! - `--maps-dir DIR`: Read level files from `DIR` instead of `maps/`.
+//! - `--pack NAME`: Play the map pack installed in the `NAME` subdirectory of the maps directory.
+//! - `--dump-map N`: Print level `N` with the player at the start and exit, without starting the game.
+//! - `--map FILE`: Play a single custom map file instead of the numbered levels.
+//! - `--debug-perf`: Show the average render, frame, and update times in the status line, for diagnosing slow terminals.
+//! - `--player-glyph G` / `--enemy-glyph G`: Draw the player or the patrol guards with a custom glyph,
+//!   such as `🦊`. It must fit in a single map cell.
+//! - `--leash N`: How many steps from home a hunting guard follows you before giving up (default 5).
+//! - `--fog`: Fog of war; see only the tiles near you, farther by lantern light.
+//! - `--danger-zone`: Highlight the tiles the patrol guards could step onto next.
+//! - `--dash`: Tap a direction twice quickly to dash: the second tap moves two tiles instead of one, as long as
+//!   both are clear.
+//! - `--mouse`: Click a tile on the map to step toward it.
+//! - `--mouse-pathfind`: Like `--mouse`, but each click steps along a shortest path to the clicked tile.
+//! - `--demo`: Let a bot play the first level as a demo until a key is pressed, then start the game.
+//! - `--seed N`: Seed the random number generator, so the wandering guards move the same way every run.
+//! - `--give ITEMS`: Debug option; start with a comma-separated list of items, such as `axe,sword,key`.
+//! - `--select`: Pick the starting level from a menu listing every level and its best time.
+//! - `--deadly-traps`: Hidden traps kill the moment they spring, not just once revealed.
+//! - `--no-confirm-quit`: Quit right away without asking for confirmation.
+//! - `--no-sound`: Don't ring the terminal bell on pickups, deaths, and level clears.
+//! - `--sound`: Ring the bell for this run even if the saved settings turned it off.
+//! - `--no-flash`: Don't flash the screen on deaths and boss clashes, for players sensitive to flashing lights.
+//! - `--validate`: Check every map file, print a report, and exit with a non-zero
+//!   status if any map has problems.
+//! - `--list-levels`: Print a table of the levels with their names and par times, and exit.
 //!
 //! # Cleanup
 //! Before exiting, this function ensures that the terminal is restored
-//! to its normal state by disabling raw mode.
+//! to its normal state by disabling raw mode and mouse capture, even when the
+//! game loop in `run` stops on an I/O error.
 
 // Crate crossterm: Cross-platform Terminal Manipulation Library
 // https://docs.rs/crossterm/latest/crossterm/
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     terminal::{disable_raw_mode, enable_raw_mode},
+    ExecutableCommand,
 };
-use std::io;
+use std::io::{self, stdout};
 use std::time::{Duration, Instant};
 
-mod classes;
-
-use classes::game::Game;
-use classes::types::CollisionType;
+use ghostblade::classes::carry_select::CarrySelect;
+use ghostblade::classes::config::Config;
+use ghostblade::classes::demo::{Demo, DEMO_TICKS_PER_STEP};
+use ghostblade::classes::game::{Game, GameError, GoalOutcome};
+use ghostblade::classes::input::{DashDetector, InputQueue};
+use ghostblade::classes::level::Level;
+use ghostblade::classes::level_select::LevelSelect;
+use ghostblade::classes::options::OptionsMenu;
+use ghostblade::classes::player::{MoveResult, Player};
+use ghostblade::classes::settings::Settings;
+use ghostblade::classes::timing::{
+    catch_up, frame_duration, scaled_fps, tick_duration, time_until_next_frame, MovingAverage,
+    PerfStats, PERF_SAMPLES,
+};
+use ghostblade::classes::types::Direction;
+use ghostblade::classes::ui::{Overlay, UI};
+use ghostblade::classes::validate::{list_levels, validate_maps};
 
 fn main() -> io::Result<()> {
-    let mut game = Game::new();
-    let mut player = game.init_player();
+    // Saved settings are the defaults, which command line options can still override
+    let mut base = Config::default();
+    let saved = Settings::load(&base.settings_file).unwrap_or_else(|| Settings::from_config(&base));
+    saved.apply_to(&mut base);
+
+    let config = match Config::from_args_with(base, std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("Usage: ghostblade [--fps N] [--speed X] [--ascii] [--color] [--spacious] [--diagonal]");
+            eprintln!(
+                "                 [--no-ascii] [--no-color] [--god] [--allow-skip] [--peaceful]"
+            );
+            eprintln!("                 [--difficulty easy|normal|hard]");
+            eprintln!(
+                "                 [--maps-dir DIR] [--validate] [--list-levels] [--sound] [--no-sound]"
+            );
+            eprintln!("                 [--no-confirm-quit] [--select] [--deadly-traps]");
+            eprintln!("                 [--dump-map N] [--give ITEM,ITEM,...] [--map FILE]");
+            eprintln!("                 [--debug-perf] [--player-glyph G] [--enemy-glyph G]");
+            eprintln!("                 [--demo] [--seed N] [--danger-zone] [--fog]");
+            eprintln!("                 [--leash N] [--no-flash] [--pack NAME] [--dash]");
+            eprintln!("                 [--theme NAME|FILE] [--mouse] [--mouse-pathfind]");
+            std::process::exit(2);
+        }
+    };
+
+    if config.validate {
+        let (report, ok) = validate_maps(config.levels_dir());
+        print!("{}", report);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
 
+    if config.list_levels {
+        let (table, ok) = list_levels(config.levels_dir());
+        print!("{}", table);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if let Some(level_number) = config.dump_map {
+        let path = Level::path(config.levels_dir(), level_number);
+        let level = match Level::read(&path) {
+            Ok(level) => level,
+            Err(error) => {
+                eprintln!(
+                    "Couldn't load level {} from {}: {}",
+                    level_number,
+                    path.display(),
+                    error
+                );
+                std::process::exit(1);
+            }
+        };
+        let mut player = Player::new();
+        player.reset_position(level.player_start);
+
+        UI::with_config(&config).dump_map(&mut io::stdout(), &level, &player)?;
+        return Ok(());
+    }
+
+    let game = match &config.map {
+        Some(path) => {
+            let level = match Level::read(path) {
+                Ok(level) => level,
+                Err(error) => {
+                    eprintln!("Couldn't load map {}: {}", path, error);
+                    std::process::exit(1);
+                }
+            };
+            if let Err(error) = level.check_exit() {
+                eprintln!("{} can't be played: {}", path, error);
+                std::process::exit(1);
+            }
+            Game::with_level(&config, level)
+        }
+        None => match Game::try_with_config(&config) {
+            Ok(game) => game,
+            Err(error) => {
+                eprintln!("Couldn't start the game: {}", error);
+                if let GameError::MissingPack(_) = error {
+                    let packs = Level::packs(&config.maps_dir);
+                    if packs.is_empty() {
+                        eprintln!("No map packs are installed in {}", config.maps_dir);
+                    } else {
+                        eprintln!("Available packs: {}", packs.join(", "));
+                    }
+                }
+                std::process::exit(1);
+            }
+        },
+    };
+    let settings = Settings::from_config(&config);
+
+    // The demo level is loaded before the terminal changes, so a broken one is reported plainly
+    let demo = if config.demo {
+        match Demo::new(&config) {
+            Ok(demo) => Some(demo),
+            Err(error) => {
+                eprintln!("Couldn't start the demo: {}", error);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // Leave raw mode however the game ends, so an I/O error doesn't wreck the terminal
     enable_raw_mode()?;
+    let result = set_mouse_capture(config.mouse, true)
+        .and_then(|()| run(&config, game, settings, saved, demo));
+
+    // Every teardown step runs even if another fails, and the first error is reported
+    let mouse = set_mouse_capture(config.mouse, false);
+    let raw = disable_raw_mode();
+    result.and(mouse).and(raw)
+}
+
+/// Turns mouse capture on or off, if the mouse is used at all.
+fn set_mouse_capture(mouse: bool, on: bool) -> io::Result<()> {
+    match (mouse, on) {
+        (false, _) => Ok(()),
+        (true, true) => stdout().execute(EnableMouseCapture).map(|_| ()),
+        (true, false) => stdout().execute(DisableMouseCapture).map(|_| ()),
+    }
+}
+
+/// Runs the game in raw mode, from the demo and the level select to quitting.
+fn run(
+    config: &Config,
+    mut game: Game,
+    mut settings: Settings,
+    mut saved: Settings,
+    demo: Option<Demo>,
+) -> io::Result<()> {
+    if let Some(demo) = demo {
+        play_demo(config, demo)?;
+    }
+
+    if config.select {
+        let mut select = LevelSelect::new(game.best_times(), config.ascii);
 
-    let fps = 10;
-    let frame_duration = Duration::from_secs_f32(1.0 / fps as f32);
-    let enemy_move_interval = Duration::from_millis(500);
-    let mut last_enemy_move = Instant::now();
+        match choose_level(&mut select)? {
+            Some(level) => {
+                game.load_level(level);
+            }
+            None => return Ok(()),
+        }
+    }
+
+    let mut player = game.init_player();
+
+    let mut input = InputQueue::new();
+    let mut dash = DashDetector::new();
+    // The speed factor stretches or squeezes every clock the loop runs on
+    let tick = game.tick_interval();
+    let fps = scaled_fps(config.fps, config.speed);
+    let mut next_tick = Instant::now() + tick;
+    let mut frame_start = Instant::now();
+    let mut render_times = MovingAverage::new(PERF_SAMPLES);
+    let mut frame_times = MovingAverage::new(PERF_SAMPLES);
+    let mut update_times = MovingAverage::new(PERF_SAMPLES);
+
+    game.render(&player)?;
 
     'game_loop: loop {
-        let frame_start = Instant::now();
+        // Wait for input until the next frame or tick is due, so key presses are
+        // handled as soon as they arrive rather than once per frame.
+        let timeout = time_until_next_frame(fps, frame_start.elapsed())
+            .min(next_tick.saturating_duration_since(Instant::now()));
 
-        if event::poll(Duration::from_millis(0))? {
-            if let Event::Key(key_event) = event::read()? {
+        // Gather every key pressed since the last pass, then apply them in order
+        if event::poll(timeout)? {
+            input.read_pending()?;
+        }
+
+        while let Some((key_event, pressed_at)) = input.pop() {
+            game.dismiss_messages();
+
+            // Any key dismisses an open overlay and returns to the game
+            if game.close_overlay() {
+                continue;
+            }
+
+            // While the quit prompt is up, only `y` quits; any other key resumes
+            if game.is_quit_pending() {
+                let confirmed = matches!(key_event.code, KeyCode::Char('y') | KeyCode::Char('Y'));
+                if game.answer_quit(confirmed) {
+                    game.handle_give_up()?;
+                    break 'game_loop;
+                }
+                continue;
+            }
+
+            // Likewise, only `y` skips the level
+            if game.is_skip_pending() {
+                let confirmed = matches!(key_event.code, KeyCode::Char('y') | KeyCode::Char('Y'));
+                if game.answer_skip(confirmed, &mut player) == Some(GoalOutcome::GameClear)
+                    && !finish_game(&mut game, &mut player, &mut input, &mut next_tick, tick)?
+                {
+                    break 'game_loop;
+                }
+                continue;
+            }
+
+            // In photo mode the movement keys pan the camera, and the player stays put
+            if game.is_photo_mode() {
                 match key_event.code {
-                    KeyCode::Char('q') | KeyCode::Esc => break 'game_loop,
-                    KeyCode::Char('w') | KeyCode::Up => player.move_up(),
-                    KeyCode::Char('s') | KeyCode::Down => player.move_down(),
-                    KeyCode::Char('a') | KeyCode::Left => player.move_left(),
-                    KeyCode::Char('d') | KeyCode::Right => player.move_right(),
+                    KeyCode::Char('w') | KeyCode::Up => game.pan_camera(Direction::Up),
+                    KeyCode::Char('s') | KeyCode::Down => game.pan_camera(Direction::Down),
+                    KeyCode::Char('a') | KeyCode::Left => game.pan_camera(Direction::Left),
+                    KeyCode::Char('d') | KeyCode::Right => game.pan_camera(Direction::Right),
+                    KeyCode::Char('r') => game.recenter_camera(&player),
+                    KeyCode::Char('p') | KeyCode::Esc => {
+                        game.toggle_photo_mode(&player);
+                        // The world stood still while the photo was taken
+                        next_tick = Instant::now() + tick;
+                    }
                     _ => {}
                 }
+                continue;
             }
-        }
 
-        if last_enemy_move.elapsed() >= enemy_move_interval {
-            game.update_enemies();
-            last_enemy_move = Instant::now();
-        }
-
-        if let Some(new_pos) = player.get_pending_move() {
-            match game.check_collision(&new_pos) {
-                CollisionType::None => player.commit_move(),
-                CollisionType::Goal => {
-                    if game.advance_level() {
-                        player.reset_position(game.get_player_start());
-                    } else {
-                        game.handle_game_clear();
+            let step = match key_event.code {
+                // With diagonal movement on, `q` moves up-left, so only Escape quits
+                KeyCode::Char('q') | KeyCode::Char('7') if config.diagonal => {
+                    Some(Direction::UpLeft)
+                }
+                KeyCode::Char('e') | KeyCode::Char('9') if config.diagonal => {
+                    Some(Direction::UpRight)
+                }
+                KeyCode::Char('z') | KeyCode::Char('1') if config.diagonal => {
+                    Some(Direction::DownLeft)
+                }
+                KeyCode::Char('c') | KeyCode::Char('3') if config.diagonal => {
+                    Some(Direction::DownRight)
+                }
+                // Quits right away, or shows the confirmation prompt
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    if game.request_quit() {
+                        game.handle_give_up()?;
                         break 'game_loop;
                     }
+                    None
                 }
-                CollisionType::Princess => {
-                    game.handle_game_clear();
-                    break 'game_loop;
+                KeyCode::Char('w') | KeyCode::Up => Some(Direction::Up),
+                KeyCode::Char('s') | KeyCode::Down => Some(Direction::Down),
+                KeyCode::Char('a') | KeyCode::Left => Some(Direction::Left),
+                KeyCode::Char('d') | KeyCode::Right => Some(Direction::Right),
+                KeyCode::Char('u') => {
+                    game.use_wind_chime(&player);
+                    None
+                }
+                KeyCode::Char('h') => {
+                    game.request_hint(&player);
+                    None
+                }
+                KeyCode::Char('.') | KeyCode::Char(' ') => {
+                    game.wait(&mut player);
+                    // Waiting fast-forwards the clock; the regular ticks pick up from here
+                    next_tick = Instant::now() + tick;
+                    None
+                }
+                KeyCode::Char('i') => {
+                    game.open_overlay(Overlay::Inventory);
+                    None
                 }
-                CollisionType::Interactive(_) => {
-                    game.handle_interaction(&mut player);
+                KeyCode::Char('?') => {
+                    game.open_overlay(Overlay::Legend);
+                    None
                 }
-                CollisionType::Blocking(_) => {
-                    player.cancel_move();
+                KeyCode::Char('l') => {
+                    game.describe_surroundings(&player);
+                    None
+                }
+                KeyCode::Char('p') => {
+                    game.toggle_photo_mode(&player);
+                    None
+                }
+                KeyCode::F(2) => {
+                    game.practice_restart(&mut player);
+                    None
+                }
+                KeyCode::F(3) => {
+                    game.request_skip();
+                    None
+                }
+                KeyCode::Char('o') => {
+                    let mut menu = OptionsMenu::new(settings);
+                    choose_options(&mut menu)?;
+
+                    if menu.settings() != settings {
+                        // Only what changed on the menu is saved, not this run's command line options
+                        saved = saved.with_changes(&settings, &menu.settings());
+                        settings = menu.settings();
+                        game.apply_settings(&settings);
+                        if saved.save(&config.settings_file).is_err() {
+                            game.show_message("   Couldn't save the settings ");
+                        }
+                    }
+
+                    // Keys pressed before the menu opened are stale by now
+                    input.clear();
+                    // The world stood still while the menu was open
+                    next_tick = Instant::now() + tick;
+                    game.render(&player)?;
+                    None
                 }
+                _ => None,
+            };
+
+            if let Some(dir) = step {
+                let result = if config.dash && dash.press(dir, pressed_at) {
+                    game.dash(&mut player, dir)
+                } else {
+                    game.try_move(&mut player, dir)
+                };
+                if result == MoveResult::GameClear
+                    && !finish_game(&mut game, &mut player, &mut input, &mut next_tick, tick)?
+                {
+                    break 'game_loop;
+                }
+            }
+        }
+
+        // A click steps toward the clicked tile, as if the key of that direction was pressed
+        while let Some((column, row)) = input.pop_click() {
+            game.dismiss_messages();
+            if game.close_overlay()
+                || game.is_quit_pending()
+                || game.is_skip_pending()
+                || game.is_photo_mode()
+            {
+                continue;
+            }
+
+            let Some(dir) = game.click_step(&player, column, row) else {
+                continue;
+            };
+            if game.try_move(&mut player, dir) == MoveResult::GameClear
+                && !finish_game(&mut game, &mut player, &mut input, &mut next_tick, tick)?
+            {
+                break 'game_loop;
             }
         }
 
-        game.render(&player);
+        // Catch up on the ticks that came due, so a slow frame doesn't slow the world down.
+        // Photo mode freezes the world.
+        if !game.is_photo_mode() {
+            let (due, next) = catch_up(next_tick, Instant::now(), tick);
+            next_tick = next;
+            for _ in 0..due {
+                let update_start = Instant::now();
+                game.tick(&mut player);
+                update_times.record(update_start.elapsed());
+            }
+        }
 
-        let elapsed = frame_start.elapsed();
-        if elapsed < frame_duration {
-            std::thread::sleep(frame_duration - elapsed);
+        if frame_start.elapsed() >= frame_duration(fps) {
+            if config.debug_perf {
+                frame_times.record(frame_start.elapsed());
+                game.set_perf_stats(PerfStats {
+                    render: render_times.average(),
+                    frame: frame_times.average(),
+                    update: update_times.average(),
+                });
+            }
+
+            frame_start = Instant::now();
+            game.render(&player)?;
+            render_times.record(frame_start.elapsed());
         }
     }
 
-    disable_raw_mode()?;
+    // The run log is a nicety; failing to write it shouldn't spoil the exit
+    let _ = game.log_run();
     Ok(())
 }
+
+/// Lets the demo bot play until any key is pressed, then hands over to the real game.
+fn play_demo(config: &Config, mut demo: Demo) -> io::Result<()> {
+    demo.render()?;
+
+    loop {
+        if event::poll(tick_duration(config.speed) * DEMO_TICKS_PER_STEP)? {
+            if let Event::Key(_) = event::read()? {
+                return Ok(());
+            }
+            continue;
+        }
+
+        demo.step();
+        demo.render()?;
+    }
+}
+
+/// Shows the level select menu until the player starts a level or backs out.
+///
+/// Returns the chosen level number, or `None` if the player quit.
+fn choose_level(select: &mut LevelSelect) -> io::Result<Option<usize>> {
+    loop {
+        select.render()?;
+
+        let Some(code) = read_key_press()? else {
+            continue;
+        };
+        match code {
+            KeyCode::Char('w') | KeyCode::Up => select.move_up(),
+            KeyCode::Char('s') | KeyCode::Down => select.move_down(),
+            KeyCode::Enter => return Ok(Some(select.selected())),
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Shows the victory screen, then offers New Game+ and sets up the player and the clock for it.
+///
+/// Returns `true` to keep playing, or `false` if the player quit instead.
+fn finish_game(
+    game: &mut Game,
+    player: &mut Player,
+    input: &mut InputQueue,
+    next_tick: &mut Instant,
+    tick: Duration,
+) -> io::Result<bool> {
+    game.handle_game_clear()?;
+    if !offer_new_game_plus(game, player)? {
+        return Ok(false);
+    }
+    *player = game.init_player();
+    input.clear();
+    // The world stood still while the screens were up
+    *next_tick = Instant::now() + tick;
+    Ok(true)
+}
+
+/// After clearing the game, offers New Game+ with a choice of items to carry over.
+///
+/// Returns `true` if the player started New Game+, or `false` if they quit.
+fn offer_new_game_plus(game: &mut Game, player: &Player) -> io::Result<bool> {
+    let mut select = CarrySelect::new(player.sorted_inventory());
+
+    loop {
+        select.render()?;
+
+        let Some(code) = read_key_press()? else {
+            continue;
+        };
+        match code {
+            KeyCode::Char('w') | KeyCode::Up => select.move_up(),
+            KeyCode::Char('s') | KeyCode::Down => select.move_down(),
+            KeyCode::Char(' ') => select.toggle(),
+            KeyCode::Enter => {
+                game.start_new_game_plus(select.carried());
+                return Ok(true);
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+/// Shows the options screen until the player closes it.
+fn choose_options(menu: &mut OptionsMenu) -> io::Result<()> {
+    loop {
+        menu.render()?;
+
+        let Some(code) = read_key_press()? else {
+            continue;
+        };
+        match code {
+            KeyCode::Char('w') | KeyCode::Up => menu.move_up(),
+            KeyCode::Char('s') | KeyCode::Down => menu.move_down(),
+            KeyCode::Enter | KeyCode::Char(' ') => menu.toggle(),
+            KeyCode::Char('o') | KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+/// Waits for the next terminal event, returning the key if it was a key press.
+///
+/// Returns `None` for anything else, such as a resize, so menus can redraw before waiting again.
+fn read_key_press() -> io::Result<Option<KeyCode>> {
+    match event::read()? {
+        // Terminals that report key releases send one after every press
+        Event::Key(key_event) if key_event.kind != KeyEventKind::Release => {
+            Ok(Some(key_event.code))
+        }
+        _ => Ok(None),
+    }
+}
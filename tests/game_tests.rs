@@ -1,5 +1,5 @@
 use ghostblade::classes::game::Game;
-use ghostblade::classes::level::Level;
+use ghostblade::classes::level::{Enemy, Level};
 use ghostblade::classes::player::Player;
 use ghostblade::classes::types::{CollisionType, ItemType, Position, TileType};
 
@@ -47,9 +47,13 @@ fn test_collision_detection() {
 
     let level = Level {
         map,
-        enemies: vec![Position { row: 3, col: 3 }],
+        enemies: vec![Enemy::new(Position { row: 3, col: 3 })],
+        oni: vec![],
+        spawns: vec![],
+        platforms: vec![],
         player_start: Position { row: 0, col: 0 },
         map_size: (5, 5),
+        header: Default::default(),
     };
 
     let mut game = Game::new();
@@ -80,10 +84,10 @@ fn test_collision_detection() {
     }
 
     let out_of_bounds = Position { row: -1, col: 0 };
-    match game.check_collision(&out_of_bounds) {
-        CollisionType::Blocking(_) => (), // Expected
-        _ => panic!("Expected blocking collision with out of bounds"),
-    }
+    assert_eq!(
+        game.check_collision(&out_of_bounds),
+        CollisionType::OutOfBounds
+    );
 }
 
 #[test]
@@ -135,3 +139,47 @@ fn test_level_tile_operations() {
     let invalid_pos = Position { row: -1, col: -1 };
     assert!(level.get_tile(&invalid_pos).is_none());
 }
+
+#[test]
+fn test_validate_rejects_broken_map() {
+    let dir = std::env::temp_dir().join(format!("ghostblade_validate_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("level_1.txt"), "ttttt\ntpsgt\nttttt\n").unwrap();
+    std::fs::write(dir.join("level_2.txt"), "ttttt\ntssgt\ntttt\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_ghostblade"))
+        .args(["--validate", "--maps-dir"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to run ghostblade");
+    let report = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success());
+    assert!(report.contains("level_1.txt: OK"));
+    assert!(report.contains("level_2.txt: FAILED"));
+    assert!(report.contains("Found 0 player starts"));
+    assert!(report.contains("Row 3 has 4 tiles"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_dump_map_prints_level() {
+    let dir = std::env::temp_dir().join(format!("ghostblade_dump_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("level_1.txt"), "tttt\ntpgt\ntett\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_ghostblade"))
+        .args(["--ascii", "--dump-map", "1", "--maps-dir"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to run ghostblade");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "# # # # \n# v G # \n# E # # \n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}